@@ -0,0 +1,144 @@
+//! Building [`ParseOptions`] without struct literals
+
+use super::{Cancellation, Limits, ParseOptions, Progress};
+
+/// Builds [`ParseOptions`], starting from [`ParseOptions::DEFAULT`] (or another preset with
+/// [`ParseOptionsBuilder::from`]). Each method sets the option of the same name
+///
+/// ```
+/// use simple_json_parser::{parse_with_exit_signal, ParseOptions};
+///
+/// let options = ParseOptions::builder()
+///     .allow_comments(false)
+///     .max_depth(64)
+///     .build();
+/// assert!(parse_with_exit_signal("[1, // two\n 2]", |_, _| false, &options).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct ParseOptionsBuilder {
+    options: ParseOptions,
+}
+
+impl ParseOptions {
+    pub fn builder() -> ParseOptionsBuilder {
+        ParseOptionsBuilder::new()
+    }
+}
+
+impl From<ParseOptions> for ParseOptionsBuilder {
+    fn from(options: ParseOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl ParseOptionsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exit_on_first_value(mut self, value: bool) -> Self {
+        self.options.exit_on_first_value = value;
+        self
+    }
+
+    pub fn require_end_of_input(mut self, value: bool) -> Self {
+        self.options.require_end_of_input = value;
+        self
+    }
+
+    pub fn allow_comments(mut self, value: bool) -> Self {
+        self.options.allow_comments = value;
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn allow_trailing_commas(mut self, value: bool) -> Self {
+        self.options.allow_trailing_commas = value;
+        self
+    }
+
+    pub fn json5(mut self, value: bool) -> Self {
+        self.options.json5 = value;
+        self
+    }
+
+    pub fn lenient_numbers(mut self, value: bool) -> Self {
+        self.options.lenient_numbers = value;
+        self
+    }
+
+    pub fn allow_non_finite_numbers(mut self, value: bool) -> Self {
+        self.options.allow_non_finite_numbers = value;
+        self
+    }
+
+    pub fn reject_lone_surrogates(mut self, value: bool) -> Self {
+        self.options.reject_lone_surrogates = value;
+        self
+    }
+
+    pub fn allow_scalar_root(mut self, value: bool) -> Self {
+        self.options.allow_scalar_root = value;
+        self
+    }
+
+    pub fn typed_numbers(mut self, value: bool) -> Self {
+        self.options.typed_numbers = value;
+        self
+    }
+
+    pub fn empty_containers(mut self, value: bool) -> Self {
+        self.options.empty_containers = value;
+        self
+    }
+
+    pub fn progress(mut self, progress: Progress) -> Self {
+        self.options.progress = Some(progress);
+        self
+    }
+
+    pub fn cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.options.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn allow_control_characters(mut self, value: bool) -> Self {
+        self.options.allow_control_characters = value;
+        self
+    }
+
+    pub fn allow_bom(mut self, value: bool) -> Self {
+        self.options.allow_bom = value;
+        self
+    }
+
+    pub fn allow_line_comments(mut self, value: bool) -> Self {
+        self.options.allow_line_comments = value;
+        self
+    }
+
+    pub fn allow_block_comments(mut self, value: bool) -> Self {
+        self.options.allow_block_comments = value;
+        self
+    }
+
+    pub fn allow_hash_comments(mut self, value: bool) -> Self {
+        self.options.allow_hash_comments = value;
+        self
+    }
+
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.options.limits = limits;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> ParseOptions {
+        self.options
+    }
+}
@@ -0,0 +1,248 @@
+//! Parsing `&[u8]` sources without validating all of the input as UTF-8 up front
+
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    key_content, JSONKey, JSONParseError, JSONParseErrorReason, Machine, ParseOptions,
+    ProgressTracker, RootJSONValue, Step, ValueKind,
+};
+
+/// Like [`crate::parse_with_exit_signal`] but for bytes. Only keys and values that are emitted
+/// are checked for being valid UTF-8. Returns the number of bytes parsed.
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input or an emitted key or value is not UTF-8
+pub fn parse_bytes<'a>(
+    on: &'a [u8],
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut machine = Machine::new(options);
+    let mut key_chain = Vec::new();
+    let mut progress = ProgressTracker::new(options);
+    let mut idx = 0;
+
+    loop {
+        idx += machine.skippable(idx, &on[idx..]);
+        if progress.due(idx) {
+            progress.report(idx, &key_chain)?;
+        }
+        let (step, length) = if let Some(&byte) = on.get(idx) {
+            let (chr, length) = if byte.is_ascii() {
+                (char::from(byte), 1)
+            } else if machine.in_text() {
+                // Contents are not needed (until it is emitted), so skip decoding
+                (char::REPLACEMENT_CHARACTER, 1)
+            } else {
+                decode(on, idx)?
+            };
+            (
+                machine
+                    .push_with_keys(idx, chr, &mut key_chain)
+                    .map_err(|err| err.with_path(&key_chain))?,
+                length,
+            )
+        } else if let Some(step) = machine
+            .finish(on.len())
+            .map_err(|err| err.with_path(&key_chain))?
+        {
+            (step, 0)
+        } else {
+            return Ok(on.len());
+        };
+
+        match step {
+            Step::Key { start, end, quoted } => {
+                let (start, end) = key_content(start, end, quoted);
+                key_chain.push(JSONKey::Slice(to_str(on, start, end)?));
+            }
+            Step::Value {
+                kind,
+                start,
+                end,
+                retry,
+            } => {
+                let value = match kind {
+                    ValueKind::String => RootJSONValue::String(to_str(on, start + 1, end - 1)?),
+                    ValueKind::Number => {
+                        RootJSONValue::number(to_str(on, start, end)?, options.typed_numbers)
+                    }
+                    ValueKind::Boolean(value) => RootJSONValue::Boolean(value),
+                    ValueKind::Null => RootJSONValue::Null,
+                    ValueKind::NonFinite => RootJSONValue::non_finite(to_str(on, start, end)?),
+                    ValueKind::EmptyObject => RootJSONValue::EmptyObject,
+                    ValueKind::EmptyArray => RootJSONValue::EmptyArray,
+                };
+                if cb(&key_chain, value) || (options.exits_on_first_value() && machine.is_at_root())
+                {
+                    return Ok(end);
+                }
+                if retry {
+                    continue;
+                }
+            }
+            Step::EndObject { at } | Step::EndArray { at }
+                if options.exits_on_first_value() && machine.is_at_root() =>
+            {
+                return Ok(at + 1);
+            }
+            _ => {}
+        }
+
+        idx += length;
+    }
+}
+
+/// Like [`parse_bytes`] but invalid UTF-8 in strings and keys is replaced with `U+FFFD`
+/// rather than being an error. Invalid UTF-8 elsewhere is still an error.
+///
+/// ```
+/// use simple_json_parser::{parse_bytes_lossy, ParseOptions};
+///
+/// let mut values = Vec::new();
+/// let source = b"{ \"name\": \"caf\xE9\", \"\xFFkey\": 1 }";
+/// parse_bytes_lossy(source, |keys, value| {
+///     values.push((keys[0].to_string(), format!("{value:?}")));
+///     false
+/// }, &ParseOptions::default()).unwrap();
+/// assert_eq!(values[0].1, "String(\"caf\u{fffd}\")");
+/// assert_eq!(values[1].0, "\u{fffd}key");
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input or there is invalid UTF-8 outside
+/// of strings and keys
+pub fn parse_bytes_lossy<'a>(
+    on: &'a [u8],
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut machine = Machine::new(options);
+    let mut key_chain = Vec::new();
+    // Keys which were not valid UTF-8 (with their depth), which have an empty placeholder in
+    // `key_chain`
+    let mut repaired: Vec<(usize, String)> = Vec::new();
+    let mut progress = ProgressTracker::new(options);
+    let mut idx = 0;
+
+    loop {
+        idx += machine.skippable(idx, &on[idx..]);
+        if progress.due(idx) {
+            progress.report(idx, &key_chain)?;
+        }
+        let (step, length) = if let Some(&byte) = on.get(idx) {
+            let (chr, length) = if byte.is_ascii() {
+                (char::from(byte), 1)
+            } else if machine.in_text() {
+                (char::REPLACEMENT_CHARACTER, 1)
+            } else {
+                decode(on, idx)?
+            };
+            (
+                machine
+                    .push_with_keys(idx, chr, &mut key_chain)
+                    .map_err(|err| err.with_path(&key_chain))?,
+                length,
+            )
+        } else if let Some(step) = machine
+            .finish(on.len())
+            .map_err(|err| err.with_path(&key_chain))?
+        {
+            (step, 0)
+        } else {
+            return Ok(on.len());
+        };
+
+        match step {
+            Step::Key { start, end, quoted } => {
+                let (start, end) = key_content(start, end, quoted);
+                let depth = key_chain.len();
+                repaired.retain(|(at, _)| *at < depth);
+                if let Ok(key) = to_str(on, start, end) {
+                    key_chain.push(JSONKey::Slice(key));
+                } else {
+                    key_chain.push(JSONKey::Slice(""));
+                    repaired.push((depth, String::from_utf8_lossy(&on[start..end]).into_owned()));
+                }
+            }
+            // Its first value puts an index at this depth
+            Step::StartArray { .. } => {
+                let depth = key_chain.len();
+                repaired.retain(|(at, _)| *at < depth);
+            }
+            Step::Value {
+                kind,
+                start,
+                end,
+                retry,
+            } => {
+                let string;
+                let value = match kind {
+                    ValueKind::String => {
+                        string = String::from_utf8_lossy(&on[start + 1..end - 1]);
+                        RootJSONValue::String(&string)
+                    }
+                    // Numbers are ASCII
+                    ValueKind::Number => {
+                        RootJSONValue::number(to_str(on, start, end)?, options.typed_numbers)
+                    }
+                    ValueKind::Boolean(value) => RootJSONValue::Boolean(value),
+                    ValueKind::Null => RootJSONValue::Null,
+                    ValueKind::NonFinite => RootJSONValue::non_finite(to_str(on, start, end)?),
+                    ValueKind::EmptyObject => RootJSONValue::EmptyObject,
+                    ValueKind::EmptyArray => RootJSONValue::EmptyArray,
+                };
+                let exit = if repaired.is_empty() {
+                    cb(&key_chain, value)
+                } else {
+                    let mut keys = key_chain.clone();
+                    for (depth, key) in &repaired {
+                        keys[*depth] = JSONKey::Slice(key);
+                    }
+                    cb(&keys, value)
+                };
+                if exit || (options.exits_on_first_value() && machine.is_at_root()) {
+                    return Ok(end);
+                }
+                if retry {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        if let Step::EndObject { at } | Step::EndArray { at } = step {
+            if options.exits_on_first_value() && machine.is_at_root() {
+                return Ok(at + 1);
+            }
+        }
+
+        idx += length;
+    }
+}
+
+/// Decodes a non ASCII character
+fn decode(on: &[u8], idx: usize) -> Result<(char, usize), JSONParseError> {
+    let length = match on[idx] {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    };
+    to_str(on, idx, (idx + length).min(on.len()))?
+        .chars()
+        .next()
+        .map(|chr| (chr, length))
+        .ok_or(JSONParseError {
+            at: idx,
+            reason: JSONParseErrorReason::InvalidUTF8,
+            path: None,
+        })
+}
+
+fn to_str(on: &[u8], start: usize, end: usize) -> Result<&str, JSONParseError> {
+    core::str::from_utf8(&on[start..end]).map_err(|err| JSONParseError {
+        at: start + err.valid_up_to(),
+        reason: JSONParseErrorReason::InvalidUTF8,
+        path: None,
+    })
+}
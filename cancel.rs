@@ -0,0 +1,71 @@
+//! Stopping a parse from outside of the callback
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Set as [`crate::ParseOptions::cancellation`] to stop parsing with
+/// [`crate::JSONParseErrorReason::Cancelled`], for example from another thread or on
+/// Ctrl-C. The check is run as parsing passes every `every` bytes. For streaming sources it
+/// is run once for each chunk that passes a multiple of `every`
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use simple_json_parser::{parse_with_exit_signal, Cancellation, JSONParseErrorReason, ParseOptions};
+///
+/// let source = format!("[{}1]", "1, ".repeat(10_000));
+/// let cancelled = Arc::new(AtomicBool::new(false));
+/// let options = ParseOptions::builder()
+///     .cancellation(Cancellation::flag(cancelled.clone()))
+///     .build();
+///
+/// let mut values = 0;
+/// let result = parse_with_exit_signal(
+///     &source,
+///     |_, _| {
+///         values += 1;
+///         // Would usually be set elsewhere
+///         if values == 100 {
+///             cancelled.store(true, Ordering::Relaxed);
+///         }
+///         false
+///     },
+///     &options,
+/// );
+/// assert!(matches!(result.unwrap_err().reason, JSONParseErrorReason::Cancelled));
+/// assert!(values < 10_000);
+/// ```
+#[derive(Clone)]
+pub struct Cancellation {
+    pub(crate) every: usize,
+    pub(crate) is_cancelled: Arc<Check>,
+}
+
+type Check = dyn Fn() -> bool + Send + Sync;
+
+impl Cancellation {
+    /// Bytes between checks of [`Cancellation::flag`]
+    pub const FLAG_EVERY: usize = 4096;
+
+    /// `every` of zero is treated as one
+    pub fn new(every: usize, is_cancelled: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            every: every.max(1),
+            is_cancelled: Arc::new(is_cancelled),
+        }
+    }
+
+    /// Cancelled once `flag` is set, checked every [`Cancellation::FLAG_EVERY`] bytes
+    #[must_use]
+    pub fn flag(flag: Arc<AtomicBool>) -> Self {
+        Self::new(Self::FLAG_EVERY, move || flag.load(Ordering::Relaxed))
+    }
+}
+
+impl core::fmt::Debug for Cancellation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Cancellation")
+            .field("every", &self.every)
+            .finish_non_exhaustive()
+    }
+}
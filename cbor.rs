@@ -0,0 +1,132 @@
+//! Converting JSON to CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949))
+
+use std::io::Write;
+
+use super::{
+    transcode::{big_integer, transcode, Encoder},
+    unescape_string_content, Container, JSONReadError, ParseOptions, RootJSONValue,
+};
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+
+const FALSE: u8 = 0xF4;
+const TRUE: u8 = 0xF5;
+const NULL: u8 = 0xF6;
+const FLOAT64: u8 = 0xFB;
+const INDEFINITE_ARRAY: u8 = 0x9F;
+const INDEFINITE_MAP: u8 = 0xBF;
+const BREAK: u8 = 0xFF;
+
+/// Parses `source` and writes it to `out` as CBOR, one event at a time, so no tree is built.
+/// Objects and arrays are written with indefinite lengths (as their sizes are not known when
+/// they start), apart from empty ones. Keys and strings are unescaped. Integers (including
+/// those beyond `i64` but within CBOR's 64 bit range) are written as integers and other
+/// numbers as 64 bit floats. Comments are skipped
+///
+/// ```
+/// use simple_json_parser::{json_to_cbor, ParseOptions};
+///
+/// let mut out = Vec::new();
+/// json_to_cbor(r#"{ "a": [1, -2, true] }"#, &mut out, &ParseOptions::default()).unwrap();
+/// assert_eq!(out, [0xBF, 0x61, b'a', 0x9F, 0x01, 0x21, 0xF5, 0xFF, 0xFF]);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or it tries to parse invalid JSON input
+pub fn json_to_cbor(
+    source: &str,
+    out: &mut impl Write,
+    options: &ParseOptions,
+) -> Result<(), JSONReadError> {
+    transcode::<Cbor>(source, out, options)
+}
+
+struct Cbor;
+
+impl Encoder for Cbor {
+    const NEEDS_LENGTHS: bool = false;
+
+    fn start(
+        out: &mut impl Write,
+        container: Container,
+        length: Option<usize>,
+    ) -> std::io::Result<()> {
+        match (container, length) {
+            (Container::Object, Some(0)) => write_head(out, MAP, 0),
+            (Container::Array, Some(0)) => write_head(out, ARRAY, 0),
+            (Container::Object, _) => out.write_all(&[INDEFINITE_MAP]),
+            (Container::Array, _) => out.write_all(&[INDEFINITE_ARRAY]),
+        }
+    }
+
+    fn end(out: &mut impl Write, _container: Container) -> std::io::Result<()> {
+        out.write_all(&[BREAK])
+    }
+
+    fn key(out: &mut impl Write, key: &str) -> std::io::Result<()> {
+        write_text(out, key)
+    }
+
+    fn value(out: &mut impl Write, value: &RootJSONValue) -> std::io::Result<()> {
+        match *value {
+            RootJSONValue::String(value) => write_text(out, &unescape_string_content(value)),
+            RootJSONValue::Integer(value) => {
+                if value < 0 {
+                    // Negative integers are encoded as `-1 - n`
+                    write_head(out, NEGATIVE, value.unsigned_abs() - 1)
+                } else {
+                    write_head(out, UNSIGNED, value.unsigned_abs())
+                }
+            }
+            RootJSONValue::Float(value) | RootJSONValue::NonFinite(value) => {
+                write_float(out, value)
+            }
+            RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                match big_integer(value) {
+                    Some((false, value)) => write_head(out, UNSIGNED, value),
+                    Some((true, value)) => write_head(out, NEGATIVE, value - 1),
+                    None => write_float(out, value.parse().unwrap_or(f64::NAN)),
+                }
+            }
+            RootJSONValue::Boolean(value) => out.write_all(&[if value { TRUE } else { FALSE }]),
+            RootJSONValue::Null => out.write_all(&[NULL]),
+            RootJSONValue::EmptyObject => write_head(out, MAP, 0),
+            RootJSONValue::EmptyArray => write_head(out, ARRAY, 0),
+        }
+    }
+}
+
+fn write_float(out: &mut impl Write, value: f64) -> std::io::Result<()> {
+    out.write_all(&[FLOAT64])?;
+    out.write_all(&value.to_be_bytes())
+}
+
+fn write_text(out: &mut impl Write, value: &str) -> std::io::Result<()> {
+    write_head(out, TEXT, value.len() as u64)?;
+    out.write_all(value.as_bytes())
+}
+
+/// The major type and argument, using the shortest encoding
+fn write_head(out: &mut impl Write, major: u8, argument: u64) -> std::io::Result<()> {
+    let major = major << 5;
+    if let Ok(argument) = u8::try_from(argument) {
+        if argument < 24 {
+            out.write_all(&[major | argument])
+        } else {
+            out.write_all(&[major | 0x18, argument])
+        }
+    } else if let Ok(argument) = u16::try_from(argument) {
+        out.write_all(&[major | 0x19])?;
+        out.write_all(&argument.to_be_bytes())
+    } else if let Ok(argument) = u32::try_from(argument) {
+        out.write_all(&[major | 0x1A])?;
+        out.write_all(&argument.to_be_bytes())
+    } else {
+        out.write_all(&[major | 0x1B])?;
+        out.write_all(&argument.to_be_bytes())
+    }
+}
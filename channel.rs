@@ -0,0 +1,92 @@
+//! Parsing on another thread
+
+use std::{
+    string::String,
+    sync::mpsc::{sync_channel, Receiver},
+    thread,
+};
+
+use super::{
+    unescape_string_content, CommentStyle, JSONEvent, JSONParseError, JsonReader, ParseOptions,
+    Span, Value,
+};
+
+/// An owned [`JSONEvent`], sent by [`parse_to_channel`]. Keys and strings are unescaped
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedEvent {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Key(String),
+    /// Only [`Value::String`], [`Value::Number`], [`Value::Boolean`], [`Value::Null`] and,
+    /// with [`ParseOptions::empty_containers`], empty objects and arrays
+    Value(Value),
+    Comment {
+        text: String,
+        style: CommentStyle,
+        span: Span,
+    },
+}
+
+impl From<JSONEvent<'_>> for OwnedEvent {
+    fn from(event: JSONEvent<'_>) -> Self {
+        match event {
+            JSONEvent::StartObject => OwnedEvent::StartObject,
+            JSONEvent::EndObject => OwnedEvent::EndObject,
+            JSONEvent::StartArray => OwnedEvent::StartArray,
+            JSONEvent::EndArray => OwnedEvent::EndArray,
+            JSONEvent::Key(key) => OwnedEvent::Key(unescape_string_content(key).into_owned()),
+            JSONEvent::Value(value) => OwnedEvent::Value(value.into()),
+            JSONEvent::Comment { text, style, span } => OwnedEvent::Comment {
+                text: text.into(),
+                style,
+                span,
+            },
+        }
+    }
+}
+
+/// Parses `source` on a new thread, sending events to the returned receiver. At most
+/// `capacity` events are buffered, after that the parser waits for them to be received, so
+/// a slow consumer does not build up a backlog. Parsing stops at the first error, which is
+/// sent as the last item, or when the receiver is dropped
+///
+/// ```
+/// use simple_json_parser::{parse_to_channel, OwnedEvent, ParseOptions, Value};
+///
+/// let source = r#"{ "rows": [1, 2, 3] }"#.to_owned();
+/// let receiver = parse_to_channel(source, 16, &ParseOptions::default());
+/// let numbers = receiver
+///     .iter()
+///     .filter_map(|event| match event.unwrap() {
+///         OwnedEvent::Value(Value::Number(number)) => Some(number),
+///         _ => None,
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(numbers, ["1", "2", "3"]);
+/// ```
+#[must_use]
+pub fn parse_to_channel(
+    source: String,
+    capacity: usize,
+    options: &ParseOptions,
+) -> Receiver<Result<OwnedEvent, JSONParseError>> {
+    let (sender, receiver) = sync_channel(capacity);
+    let options = options.clone();
+    thread::spawn(move || {
+        let mut reader = JsonReader::new(&source, &options);
+        loop {
+            let item = match reader.next_event() {
+                Ok(Some(event)) => Ok(event.into()),
+                Ok(None) => break,
+                Err(err) => Err(err),
+            };
+            let stop = item.is_err();
+            if sender.send(item).is_err() || stop {
+                break;
+            }
+        }
+    });
+    receiver
+}
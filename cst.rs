@@ -0,0 +1,181 @@
+//! Lossless syntax tree, for editor tooling which needs the position of everything
+
+use alloc::vec::Vec;
+
+use super::{tokenize, unescape_string_content, JSONParseError, ParseOptions, Span, TokenKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CstKind {
+    /// The root, covering all of the source
+    Document,
+    Object,
+    Array,
+    /// A key, the colon and the value, with any whitespace and comments between them.
+    /// Commas belong to the object
+    Member,
+    /// Everything else, including brackets, whitespace and comments
+    Token(TokenKind),
+}
+
+/// Node from [`parse_cst`]. Children are in source order and their spans cover the span of
+/// the node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CstNode {
+    pub kind: CstKind,
+    pub span: Span,
+    pub children: Vec<CstNode>,
+}
+
+impl CstNode {
+    fn new(kind: CstKind, start: usize) -> Self {
+        Self {
+            kind,
+            span: Span { start, end: start },
+            children: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        self.span.slice(source)
+    }
+
+    /// For [`CstKind::Member`], the key token (including quotes)
+    #[must_use]
+    pub fn key(&self) -> Option<&CstNode> {
+        self.children
+            .iter()
+            .find(|child| child.kind == CstKind::Token(TokenKind::Key))
+    }
+
+    /// For [`CstKind::Member`] the value, for [`CstKind::Document`] the root value
+    #[must_use]
+    pub fn value(&self) -> Option<&CstNode> {
+        self.children.iter().find(|child| child.is_value())
+    }
+
+    /// For [`CstKind::Object`], the last member with the key `key` (compared after
+    /// unescaping)
+    #[must_use]
+    pub fn member(&self, source: &str, key: &str) -> Option<&CstNode> {
+        self.members().rev().find(|member| {
+            member.key().is_some_and(|found| {
+                let text = found.text(source);
+                let text = text
+                    .strip_prefix(['"', '\''])
+                    .and_then(|text| text.strip_suffix(['"', '\'']))
+                    .unwrap_or(text);
+                unescape_string_content(text) == key
+            })
+        })
+    }
+
+    /// For [`CstKind::Object`], its members
+    #[must_use]
+    pub fn members(&self) -> impl DoubleEndedIterator<Item = &CstNode> {
+        self.children
+            .iter()
+            .filter(|child| child.kind == CstKind::Member)
+    }
+
+    /// For [`CstKind::Array`], its items
+    #[must_use]
+    pub fn items(&self) -> impl DoubleEndedIterator<Item = &CstNode> {
+        self.children.iter().filter(|child| child.is_value())
+    }
+
+    fn is_value(&self) -> bool {
+        matches!(
+            self.kind,
+            CstKind::Object
+                | CstKind::Array
+                | CstKind::Token(
+                    TokenKind::String | TokenKind::Number | TokenKind::Boolean | TokenKind::Null
+                )
+        )
+    }
+}
+
+/// Builds a [`CstNode`] tree from the tokens of [`tokenize`]. The tree is lossless, so the text
+/// of the leaves in order is the source
+///
+/// ```
+/// use simple_json_parser::{parse_cst, CstKind, ParseOptions};
+///
+/// let source = r#"{ "name": "ezno", /* comment */ "version": "0.1.0" }"#;
+/// let document = parse_cst(source, &ParseOptions::default()).unwrap();
+/// let object = document.value().unwrap();
+/// assert_eq!(object.kind, CstKind::Object);
+///
+/// // Renaming a key
+/// let key = object.member(source, "version").unwrap().key().unwrap();
+/// let renamed = format!("{}\"v\"{}", &source[..key.span.start], &source[key.span.end..]);
+/// assert_eq!(renamed, r#"{ "name": "ezno", /* comment */ "v": "0.1.0" }"#);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_cst(source: &str, options: &ParseOptions) -> Result<CstNode, JSONParseError> {
+    // Open nodes, starting with the document
+    let mut stack = alloc::vec![CstNode::new(CstKind::Document, 0)];
+
+    for token in tokenize(source, options) {
+        let token = token?;
+        let leaf = CstNode {
+            kind: CstKind::Token(token.kind),
+            span: token.span,
+            children: Vec::new(),
+        };
+        match token.kind {
+            TokenKind::StartObject | TokenKind::StartArray => {
+                let kind = if token.kind == TokenKind::StartObject {
+                    CstKind::Object
+                } else {
+                    CstKind::Array
+                };
+                let mut node = CstNode::new(kind, token.span.start);
+                node.children.push(leaf);
+                stack.push(node);
+            }
+            TokenKind::Key => {
+                let mut member = CstNode::new(CstKind::Member, token.span.start);
+                member.children.push(leaf);
+                stack.push(member);
+            }
+            TokenKind::EndObject | TokenKind::EndArray => {
+                add(&mut stack, leaf);
+                if let Some(container) = stack.pop() {
+                    add_value(&mut stack, container);
+                }
+            }
+            TokenKind::String | TokenKind::Number | TokenKind::Boolean | TokenKind::Null => {
+                add_value(&mut stack, leaf);
+            }
+            TokenKind::Whitespace | TokenKind::Comment(_) | TokenKind::Colon | TokenKind::Comma => {
+                add(&mut stack, leaf);
+            }
+        }
+    }
+
+    let mut document = stack.swap_remove(0);
+    document.span.end = source.len();
+    Ok(document)
+}
+
+/// Adds `node` to the innermost open node
+fn add(stack: &mut [CstNode], node: CstNode) {
+    if let Some(parent) = stack.last_mut() {
+        parent.span.end = node.span.end;
+        parent.children.push(node);
+    }
+}
+
+/// Adds a finished value, which also finishes the member it is the value of
+fn add_value(stack: &mut Vec<CstNode>, node: CstNode) {
+    add(stack, node);
+    if stack.last().is_some_and(|top| top.kind == CstKind::Member) {
+        if let Some(member) = stack.pop() {
+            add(stack, member);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Exporting an array of objects as CSV
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{
+    non_finite_literal, unescape_string_content, JSONEvent, JSONParseError, JsonReader,
+    ParseOptions, RootJSONValue,
+};
+
+/// Options for [`json_to_csv`]
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// The header. When `None` it is the keys of the first [`CsvOptions::infer_rows`] objects,
+    /// in the order they are first found. Keys not in the header are skipped
+    pub columns: Option<Vec<String>>,
+    /// How many objects are buffered to infer the header from. `100` by default
+    pub infer_rows: usize,
+    /// `,` by default
+    pub delimiter: char,
+    pub parse: ParseOptions,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            infer_rows: 100,
+            delimiter: ',',
+            parse: ParseOptions::DEFAULT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvError {
+    /// From the [`Write`] output
+    Format(core::fmt::Error),
+    Parse(JSONParseError),
+    /// The root value at this offset is not an array
+    ExpectedArray {
+        at: usize,
+    },
+    /// The item at this offset is not an object
+    ExpectedObject {
+        at: usize,
+    },
+    /// The member value at this offset is an object or array
+    NestedValue {
+        at: usize,
+    },
+}
+
+impl core::error::Error for CsvError {}
+
+impl core::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            CsvError::Format(err) => f.write_fmt(format_args!("CsvError: {err}")),
+            CsvError::Parse(err) => core::fmt::Display::fmt(err, f),
+            CsvError::ExpectedArray { at } => {
+                f.write_fmt(format_args!("CsvError: expected array at {at}"))
+            }
+            CsvError::ExpectedObject { at } => {
+                f.write_fmt(format_args!("CsvError: expected object at {at}"))
+            }
+            CsvError::NestedValue { at } => {
+                f.write_fmt(format_args!("CsvError: nested value at {at}"))
+            }
+        }
+    }
+}
+
+impl From<core::fmt::Error> for CsvError {
+    fn from(err: core::fmt::Error) -> Self {
+        CsvError::Format(err)
+    }
+}
+
+impl From<JSONParseError> for CsvError {
+    fn from(err: JSONParseError) -> Self {
+        CsvError::Parse(err)
+    }
+}
+
+/// A row of key and cell pairs, before the header is known
+type Row<'a> = Vec<(Cow<'a, str>, Cow<'a, str>)>;
+
+/// Writes a top level array of flat objects as CSV, one row per object. Objects are read one
+/// at a time, so only the rows used to infer the header are kept in memory. Strings are
+/// unescaped, numbers are written as in the source and `null` or missing members are empty.
+/// Cells are quoted when they contain the delimiter, a quote or a new line. Lines end with
+/// `\n`
+///
+/// ```
+/// use simple_json_parser::{json_to_csv, CsvOptions};
+///
+/// let source = r#"[
+///     { "name": "ezno", "stars": 2400 },
+///     { "name": "simple, json", "private": true }
+/// ]"#;
+/// let mut out = String::new();
+/// json_to_csv(source, &mut out, &CsvOptions::default()).unwrap();
+/// assert_eq!(out, "name,stars,private\nezno,2400,\n\"simple, json\",,true\n");
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails, it tries to parse invalid JSON input or the source is
+/// not an array of objects with string, number, boolean or `null` members
+pub fn json_to_csv(
+    source: &str,
+    out: &mut impl Write,
+    options: &CsvOptions,
+) -> Result<(), CsvError> {
+    let parse = ParseOptions {
+        typed_numbers: false,
+        ..options.parse.clone()
+    };
+    let mut reader = JsonReader::new(source, &parse);
+
+    match next_event(&mut reader)? {
+        Some(JSONEvent::StartArray) => {}
+        Some(_) => {
+            return Err(CsvError::ExpectedArray {
+                at: reader.span().start,
+            })
+        }
+        None => return Ok(()),
+    }
+
+    let mut columns = options.columns.clone();
+    if let Some(columns) = &columns {
+        write_record(
+            out,
+            columns.iter().map(|column| Some(column.as_str())),
+            options,
+        )?;
+    }
+    let mut buffered: Vec<Row> = Vec::new();
+
+    loop {
+        match next_event(&mut reader)? {
+            Some(JSONEvent::StartObject) => {}
+            Some(JSONEvent::EndArray) | None => break,
+            Some(_) => {
+                return Err(CsvError::ExpectedObject {
+                    at: reader.span().start,
+                })
+            }
+        }
+        let row = read_row(&mut reader)?;
+        if let Some(columns) = &columns {
+            write_row(out, columns, &row, options)?;
+        } else {
+            buffered.push(row);
+            if buffered.len() >= options.infer_rows {
+                columns = Some(infer_and_flush(out, &mut buffered, options)?);
+            }
+        }
+    }
+    if columns.is_none() && !buffered.is_empty() {
+        infer_and_flush(out, &mut buffered, options)?;
+    }
+
+    // Check the rest of the source
+    while next_event(&mut reader)?.is_some() {}
+    Ok(())
+}
+
+fn next_event<'a>(reader: &mut JsonReader<'a>) -> Result<Option<JSONEvent<'a>>, JSONParseError> {
+    loop {
+        match reader.next_event()? {
+            Some(JSONEvent::Comment { .. }) => {}
+            event => return Ok(event),
+        }
+    }
+}
+
+/// The members of an object, after its `{`
+fn read_row<'a>(reader: &mut JsonReader<'a>) -> Result<Row<'a>, CsvError> {
+    let mut row = Vec::new();
+    while let Some(JSONEvent::Key(key)) = next_event(reader)? {
+        let cell = match next_event(reader)? {
+            Some(JSONEvent::Value(value)) => match value {
+                RootJSONValue::String(value) => unescape_string_content(value),
+                RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                    Cow::Borrowed(value)
+                }
+                RootJSONValue::Boolean(value) => {
+                    Cow::Borrowed(if value { "true" } else { "false" })
+                }
+                RootJSONValue::NonFinite(value) => Cow::Borrowed(non_finite_literal(value)),
+                RootJSONValue::Null => Cow::Borrowed(""),
+                _ => {
+                    return Err(CsvError::NestedValue {
+                        at: reader.span().start,
+                    })
+                }
+            },
+            _ => {
+                return Err(CsvError::NestedValue {
+                    at: reader.span().start,
+                })
+            }
+        };
+        row.push((unescape_string_content(key), cell));
+    }
+    Ok(row)
+}
+
+/// Writes the header from the keys of `buffered`, then the buffered rows
+fn infer_and_flush(
+    out: &mut impl Write,
+    buffered: &mut Vec<Row>,
+    options: &CsvOptions,
+) -> Result<Vec<String>, CsvError> {
+    let mut columns: Vec<String> = Vec::new();
+    for (key, _) in buffered.iter().flatten() {
+        if !columns.iter().any(|column| column == key) {
+            columns.push(key.clone().into_owned());
+        }
+    }
+    write_record(
+        out,
+        columns.iter().map(|column| Some(column.as_str())),
+        options,
+    )?;
+    for row in buffered.drain(..) {
+        write_row(out, &columns, &row, options)?;
+    }
+    Ok(columns)
+}
+
+fn write_row(
+    out: &mut impl Write,
+    columns: &[String],
+    row: &Row,
+    options: &CsvOptions,
+) -> core::fmt::Result {
+    let cells = columns.iter().map(|column| {
+        row.iter()
+            .rev()
+            .find(|(key, _)| key == column)
+            .map(|(_, cell)| cell.as_ref())
+    });
+    write_record(out, cells, options)
+}
+
+fn write_record<'b>(
+    out: &mut impl Write,
+    cells: impl Iterator<Item = Option<&'b str>>,
+    options: &CsvOptions,
+) -> core::fmt::Result {
+    for (idx, cell) in cells.enumerate() {
+        if idx > 0 {
+            out.write_char(options.delimiter)?;
+        }
+        let cell = cell.unwrap_or_default();
+        if cell.contains([options.delimiter, '"', '\n', '\r']) {
+            out.write_char('"')?;
+            out.write_str(&cell.replace('"', "\"\""))?;
+            out.write_char('"')?;
+        } else {
+            out.write_str(cell)?;
+        }
+    }
+    out.write_char('\n')
+}
@@ -0,0 +1,36 @@
+//! [`miette::Diagnostic`] for [`JSONParseError`]. The error does not hold the source, attach it
+//! with [`miette::Report::with_source_code`]
+//!
+//! ```
+//! use simple_json_parser::parse;
+//!
+//! let source = r#"{ "a" = 1 }"#;
+//! let err = parse(source, |_, _| {}).unwrap_err();
+//! let report = miette::Report::new(err).with_source_code(source);
+//! ```
+
+use std::{boxed::Box, fmt::Display, string::ToString};
+
+use miette::{Diagnostic, LabeledSpan};
+
+use super::JSONParseError;
+
+impl Diagnostic for JSONParseError {
+    /// `simple_json_parser::` followed by [`crate::JSONParseErrorReason::name`]
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(std::format!(
+            "simple_json_parser::{}",
+            self.reason.name()
+        )))
+    }
+
+    /// Covers the character found, or is empty at the end of the input
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let length = self.reason.found().map_or(0, char::len_utf8);
+        let label = LabeledSpan::new_primary_with_span(
+            Some(self.reason.label().to_string()),
+            (self.at, length),
+        );
+        Some(Box::new(core::iter::once(label)))
+    }
+}
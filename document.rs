@@ -0,0 +1,309 @@
+//! A JSONC document for making several edits, keeping formatting and comments
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    edit::{
+        insert_splices, remove_splices, set_value_splices, Splice, EDIT_OPTIONS, VALUE_OPTIONS,
+    },
+    patch::{Parent, Target},
+    pointer::pointer_segments,
+    unescape_string_content, EditError, JSONEvent, JSONKey, JSONParseError, JsonReader,
+    ParseOptions, Span,
+};
+
+/// An object member, array item or the root value
+#[derive(Debug, Clone)]
+struct Entry {
+    /// Unescaped keys and indexes
+    path: Vec<String>,
+    /// The key for object members, otherwise the start of the value
+    member_start: usize,
+    value: Span,
+}
+
+/// A JSONC (comments and trailing commas) document which can be edited with
+/// [`JsoncDocument::set_value`], [`JsoncDocument::insert`] and [`JsoncDocument::remove`]. Like
+/// the functions with the same names, only the edited parts of the source change. The source
+/// is parsed once, into the spans of every value, which are moved along as edits are made
+///
+/// ```
+/// use simple_json_parser::JsoncDocument;
+///
+/// let source = "{\n  // settings\n  \"tabSize\": 2,\n  \"files.exclude\": {},\n}\n";
+/// let mut document = JsoncDocument::parse(source).unwrap();
+/// document.set_value("/tabSize", "4").unwrap();
+/// document.insert("/files.exclude", "**/.git", "true").unwrap();
+/// document.insert("", "editor.fontSize", "14").unwrap();
+/// assert_eq!(document.get("/files.exclude/**~1.git"), Some("true"));
+/// document.remove("/tabSize").unwrap();
+/// assert_eq!(
+///     document.as_str(),
+///     "{\n  \"files.exclude\": { \"**/.git\": true },\n  \"editor.fontSize\": 14,\n}\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsoncDocument {
+    source: String,
+    /// In source order, until edits are made
+    entries: Vec<Entry>,
+}
+
+impl JsoncDocument {
+    /// # Errors
+    /// Returns an error if `source` is not valid JSONC
+    pub fn parse(source: impl Into<String>) -> Result<Self, JSONParseError> {
+        let source = source.into();
+        let entries = index(&source, 0, &[], 0, &EDIT_OPTIONS)?;
+        Ok(Self { source, entries })
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.source
+    }
+
+    /// The source of the value at `pointer`, as in [`crate::raw_slice_at`]
+    #[must_use]
+    pub fn get(&self, pointer: &str) -> Option<&str> {
+        let path = pointer_segments(pointer)?;
+        let entry = self.entry(&path)?;
+        Some(entry.value.slice(&self.source))
+    }
+
+    /// Replaces the value at `pointer`, like [`crate::set_value`]
+    ///
+    /// # Errors
+    /// Returns an error if `new_value` is not valid JSON or there is no value at `pointer`
+    pub fn set_value(&mut self, pointer: &str, new_value: &str) -> Result<(), EditError> {
+        let path = pointer_segments(pointer).ok_or(EditError::InvalidPath)?;
+        let target = self.target(&path);
+        let splices = set_value_splices(&target, new_value)?;
+        let member_start = target.member_start;
+        self.apply(&splices);
+
+        let path = owned(&path);
+        let value = splices[0].text.as_str();
+        let entries = index(value, splices[0].start, &path, member_start, &VALUE_OPTIONS)?;
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// Adds a member to the end of the object at `parent_pointer`, like [`crate::insert`]
+    ///
+    /// # Errors
+    /// Returns an error if `value` is not valid JSON, there is no object at `parent_pointer`
+    /// or it already has `key`
+    pub fn insert(
+        &mut self,
+        parent_pointer: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), EditError> {
+        let mut path = pointer_segments(parent_pointer).ok_or(EditError::InvalidPath)?;
+        path.push(Cow::Borrowed(key));
+        let target = self.target(&path);
+        let (splices, inserted) = insert_splices(&self.source, &target, key, value)?;
+        self.apply(&splices);
+
+        // Where the splice is now, after the splices before it have been made
+        let splice_start = splices[..inserted.splice]
+            .iter()
+            .fold(splices[inserted.splice].start, |at, splice| {
+                at + splice.text.len() - (splice.end - splice.start)
+            });
+        let path = owned(&path);
+        let entries = index(
+            value.trim(),
+            splice_start + inserted.value_offset,
+            &path,
+            splice_start + inserted.member_offset,
+            &VALUE_OPTIONS,
+        )?;
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// Removes the member or item at `pointer`, like [`crate::remove`], returning the source
+    /// which was removed. Later items in the same array move down an index
+    ///
+    /// # Errors
+    /// Returns an error if there is no member or item at `pointer`
+    pub fn remove(&mut self, pointer: &str) -> Result<String, EditError> {
+        let path = pointer_segments(pointer).ok_or(EditError::InvalidPath)?;
+        let target = self.target(&path);
+        let (splices, span) = remove_splices(&self.source, &target)?;
+        let removed = span.slice(&self.source).to_string();
+        let in_array = target
+            .parent
+            .as_ref()
+            .is_some_and(|parent| !parent.is_object);
+        self.apply(&splices);
+
+        if let (true, Some((last, parent_path))) = (in_array, path.split_last()) {
+            let removed_index: usize = last.parse().unwrap_or_default();
+            let depth = parent_path.len();
+            for entry in &mut self.entries {
+                let is_later_item = entry.path.len() > depth
+                    && entry.path[..depth] == *parent_path
+                    && entry.path[depth]
+                        .parse::<usize>()
+                        .is_ok_and(|index| index > removed_index);
+                if is_later_item {
+                    let index: usize = entry.path[depth].parse().unwrap_or_default();
+                    entry.path[depth] = (index - 1).to_string();
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn entry(&self, path: &[Cow<'_, str>]) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.path.len() == path.len() && entry.path.iter().eq(path))
+    }
+
+    /// Where `path` is, from the entries rather than parsing the source again
+    fn target(&self, path: &[Cow<'_, str>]) -> Target {
+        let entry = self.entry(path);
+        let mut target = Target {
+            value: entry.map(|entry| entry.value),
+            member_start: entry.map_or(0, |entry| entry.member_start),
+            ..Target::default()
+        };
+        let Some((_, parent_path)) = path.split_last() else {
+            return target;
+        };
+        let Some(parent) = self.entry(parent_path) else {
+            return target;
+        };
+        let is_object = match self.source.as_bytes()[parent.value.start] {
+            b'{' => true,
+            b'[' => false,
+            _ => return target,
+        };
+
+        let mut children: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry.path.len() == path.len()
+                    && entry.path.iter().zip(parent_path).all(|(a, b)| a == b)
+            })
+            .collect();
+        children.sort_by_key(|child| child.value.start);
+        let last = children.last();
+        if let Some(value) = target.value {
+            target.previous_end = children
+                .iter()
+                .take_while(|child| child.value.start < value.start)
+                .last()
+                .map(|child| child.value.end);
+        }
+        target.parent = Some(Parent {
+            is_object,
+            inside: parent.value.start + 1,
+            close: parent.value.end - 1,
+            children: children.len(),
+            last_child_start: last.map(|child| child.member_start),
+            last_child_end: last.map(|child| child.value.end),
+        });
+        target
+    }
+
+    /// Makes the splices and moves the entries after them. Entries inside replaced parts
+    /// are dropped
+    fn apply(&mut self, splices: &[Splice]) {
+        for splice in splices.iter().rev() {
+            let Splice { start, end, text } = splice;
+            let (start, end) = (*start, *end);
+            self.source.replace_range(start..end, text);
+            if start < end {
+                self.entries
+                    .retain(|entry| !(start <= entry.value.start && entry.value.end <= end));
+            }
+            let moved = |at: usize| at - (end - start) + text.len();
+            for entry in &mut self.entries {
+                if entry.member_start >= end {
+                    entry.member_start = moved(entry.member_start);
+                }
+                if entry.value.start >= end {
+                    entry.value.start = moved(entry.value.start);
+                }
+                if entry.value.end >= end && entry.value.end > start {
+                    entry.value.end = moved(entry.value.end);
+                }
+            }
+        }
+    }
+}
+
+fn owned(path: &[Cow<'_, str>]) -> Vec<String> {
+    path.iter().map(ToString::to_string).collect()
+}
+
+/// The entries of `source`, which starts at `offset` and is at `prefix` with `member_start`
+fn index(
+    source: &str,
+    offset: usize,
+    prefix: &[String],
+    member_start: usize,
+    options: &ParseOptions,
+) -> Result<Vec<Entry>, JSONParseError> {
+    let mut entries = Vec::new();
+    // Containers which are open
+    let mut open = Vec::new();
+    let mut key_start = member_start;
+    let mut reader = JsonReader::new(source, options);
+
+    while let Some(event) = reader.next_event()? {
+        let span = reader.span();
+        let (start, end) = (offset + span.start, offset + span.end);
+        let keys = reader.key_chain();
+        let member_start = match keys.last() {
+            None => member_start,
+            Some(JSONKey::Slice(_)) => key_start,
+            Some(JSONKey::Index(_)) => start,
+        };
+        let path = || {
+            let keys = keys.iter().map(|key| match key {
+                JSONKey::Slice(key) => unescape_string_content(key).into_owned(),
+                JSONKey::Index(idx) => idx.to_string(),
+            });
+            prefix.iter().cloned().chain(keys).collect()
+        };
+        match event {
+            JSONEvent::Key(_) => key_start = start,
+            JSONEvent::Value(_) => entries.push(Entry {
+                path: path(),
+                member_start,
+                value: Span { start, end },
+            }),
+            JSONEvent::StartObject | JSONEvent::StartArray => {
+                open.push(entries.len());
+                entries.push(Entry {
+                    path: path(),
+                    member_start,
+                    value: Span { start, end },
+                });
+            }
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                if let Some(idx) = open.pop() {
+                    entries[idx].value.end = end;
+                }
+            }
+            JSONEvent::Comment { .. } => {}
+        }
+    }
+    Ok(entries)
+}
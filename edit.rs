@@ -0,0 +1,409 @@
+//! Editing values in the source text, keeping formatting and comments
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+use super::{
+    is_valid,
+    patch::{locate_with_options, Target},
+    pointer::pointer_segments,
+    writer::write_string,
+    JSONParseError, ParseOptions, Span,
+};
+
+/// New values are spliced in as is, so must be one value without comments.
+/// `require_end_of_input` also rejects empty values
+pub(crate) const VALUE_OPTIONS: ParseOptions = ParseOptions {
+    allow_comments: false,
+    require_end_of_input: true,
+    ..ParseOptions::DEFAULT
+};
+
+/// Comments and trailing commas, as in JSONC files
+pub(crate) const EDIT_OPTIONS: ParseOptions = ParseOptions {
+    allow_trailing_commas: true,
+    ..ParseOptions::DEFAULT
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The source is not valid JSON
+    Parse(JSONParseError),
+    /// The new value is not valid JSON
+    InvalidValue(JSONParseError),
+    /// The path is not a valid JSON Pointer
+    InvalidPath,
+    /// There is no value at the path
+    NotFound,
+    /// The parent to insert into is not an object
+    NotAnObject,
+    /// The key to insert is already in the object
+    KeyExists,
+}
+
+impl core::error::Error for EditError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EditError::Parse(err) | EditError::InvalidValue(err) => Some(err),
+            EditError::InvalidPath
+            | EditError::NotFound
+            | EditError::NotAnObject
+            | EditError::KeyExists => None,
+        }
+    }
+}
+
+impl core::fmt::Display for EditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            EditError::Parse(err) => core::fmt::Display::fmt(err, f),
+            EditError::InvalidValue(err) => {
+                f.write_fmt(format_args!("EditError: invalid value: {err}"))
+            }
+            EditError::InvalidPath => f.write_str("EditError: invalid path"),
+            EditError::NotFound => f.write_str("EditError: path not found"),
+            EditError::NotAnObject => f.write_str("EditError: parent is not an object"),
+            EditError::KeyExists => f.write_str("EditError: key already exists"),
+        }
+    }
+}
+
+impl From<JSONParseError> for EditError {
+    fn from(err: JSONParseError) -> Self {
+        EditError::Parse(err)
+    }
+}
+
+/// A replacement of `start..end` of the source
+pub(crate) struct Splice {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) text: String,
+}
+
+/// Applies splices, which are in source order and do not overlap. Splices at the same
+/// position are written in the order they are in
+pub(crate) fn apply_splices(source: &str, splices: &[Splice]) -> String {
+    let mut result = source.to_owned();
+    for splice in splices.iter().rev() {
+        result.replace_range(splice.start..splice.end, &splice.text);
+    }
+    result
+}
+
+/// A copy of `source` with the value at `pointer` replaced by `new_value` (JSON source,
+/// written as is, without comments). Only the bytes of the old value change, so whitespace,
+/// comments and the order of keys are kept
+///
+/// ```
+/// use simple_json_parser::set_value;
+///
+/// let source = "{\n  \"name\": \"app\", // the name\n  \"version\": \"1.0.0\"\n}\n";
+/// let bumped = set_value(source, "/version", "\"1.1.0\"").unwrap();
+/// assert_eq!(bumped, "{\n  \"name\": \"app\", // the name\n  \"version\": \"1.1.0\"\n}\n");
+/// ```
+///
+/// # Errors
+/// Returns an error if `source` or `new_value` is not valid JSON, or there is no value at
+/// `pointer`
+pub fn set_value(source: &str, pointer: &str, new_value: &str) -> Result<String, EditError> {
+    let path = pointer_segments(pointer).ok_or(EditError::InvalidPath)?;
+    let target = locate_with_options(source, &path, &EDIT_OPTIONS)?;
+    let splices = set_value_splices(&target, new_value)?;
+    Ok(apply_splices(source, &splices))
+}
+
+pub(crate) fn set_value_splices(
+    target: &Target,
+    new_value: &str,
+) -> Result<Vec<Splice>, EditError> {
+    is_valid(new_value, &VALUE_OPTIONS).map_err(EditError::InvalidValue)?;
+    let span = target.value.ok_or(EditError::NotFound)?;
+    Ok(vec![Splice {
+        start: span.start,
+        end: span.end,
+        text: new_value.trim().to_owned(),
+    }])
+}
+
+/// A copy of `source` with a `key` member with `value` (JSON source, without comments) added
+/// to the end of the object at `parent_pointer`. The indentation and spacing of the new
+/// member follow the existing members, or the rest of the source for empty objects. If the
+/// last member has a trailing comma, so does the new one. Comments at the end of the line
+/// of the last member stay on that line
+///
+/// ```
+/// use simple_json_parser::insert;
+///
+/// let source = "{\n  \"name\": \"app\", // the name\n  \"scripts\": {}\n}\n";
+/// let source = insert(source, "", "private", "true").unwrap();
+/// let source = insert(&source, "/scripts", "build", "\"tsc\"").unwrap();
+/// assert_eq!(
+///     source,
+///     "{\n  \"name\": \"app\", // the name\n  \"scripts\": { \"build\": \"tsc\" },\n  \"private\": true\n}\n"
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if `source` or `value` is not valid JSON, there is no object at
+/// `parent_pointer` or it already has `key`
+pub fn insert(
+    source: &str,
+    parent_pointer: &str,
+    key: &str,
+    value: &str,
+) -> Result<String, EditError> {
+    let mut path = pointer_segments(parent_pointer).ok_or(EditError::InvalidPath)?;
+    path.push(Cow::Borrowed(key));
+    let target = locate_with_options(source, &path, &EDIT_OPTIONS)?;
+    let (splices, _) = insert_splices(source, &target, key, value)?;
+    Ok(apply_splices(source, &splices))
+}
+
+/// Where the new member is in the splices from [`insert_splices`]
+pub(crate) struct InsertedMember {
+    /// Index of the splice
+    pub(crate) splice: usize,
+    /// Offsets into the text of the splice
+    pub(crate) member_offset: usize,
+    pub(crate) value_offset: usize,
+}
+
+pub(crate) fn insert_splices(
+    source: &str,
+    target: &Target,
+    key: &str,
+    value: &str,
+) -> Result<(Vec<Splice>, InsertedMember), EditError> {
+    is_valid(value, &VALUE_OPTIONS).map_err(EditError::InvalidValue)?;
+    let parent = target.parent.as_ref().ok_or(EditError::NotFound)?;
+    if !parent.is_object {
+        return Err(EditError::NotAnObject);
+    }
+    if target.value.is_some() {
+        return Err(EditError::KeyExists);
+    }
+
+    // Without any whitespace the source is probably minified
+    let spaced = source.contains(char::is_whitespace);
+    let mut member = String::new();
+    // Writing to a `String` does not fail
+    let _ = write_string(&mut member, key);
+    member.push_str(if spaced { ": " } else { ":" });
+    member.push_str(value.trim());
+    let value_offset = member.len() - value.trim().len();
+    let position = |splice, offset| InsertedMember {
+        splice,
+        member_offset: offset,
+        value_offset: offset + value_offset,
+    };
+
+    let (Some(last_start), Some(last_end)) = (parent.last_child_start, parent.last_child_end)
+    else {
+        let inner = &source[parent.inside..parent.close];
+        if inner.contains('\n') {
+            // Before the new line of the closing brace (and any comments)
+            let newline = newline(source);
+            let indent = [indentation(source, parent.inside), indent_unit(source)].concat();
+            let splice = Splice {
+                start: parent.inside,
+                end: parent.inside,
+                text: [newline, &indent, &member].concat(),
+            };
+            return Ok((vec![splice], position(0, newline.len() + indent.len())));
+        }
+        let (text, offset) = if spaced {
+            ([" ", &member, " "].concat(), 1)
+        } else {
+            (member, 0)
+        };
+        // Keep comments
+        let end = if inner.trim().is_empty() {
+            parent.close
+        } else {
+            parent.inside
+        };
+        let splice = Splice {
+            start: parent.inside,
+            end,
+            text,
+        };
+        return Ok((vec![splice], position(0, offset)));
+    };
+
+    // After the last member and its trailing comma, if it has one
+    let mut at = last_end;
+    let after = &source[last_end..];
+    let trailing_comma = after.trim_start().starts_with(',');
+    if trailing_comma {
+        at = source.len() - after.trim_start()[1..].len();
+    }
+    let insert_at = |at, text| Splice {
+        start: at,
+        end: at,
+        text,
+    };
+    let comma = insert_at(last_end, ",".to_owned());
+
+    let on_own_line = source[line_start(source, last_start)..last_start]
+        .trim()
+        .is_empty();
+    if on_own_line {
+        let line_end = source[at..].find('\n').map_or(source.len(), |idx| at + idx);
+        let line_end = if source[..line_end].ends_with('\r') {
+            line_end - 1
+        } else {
+            line_end
+        };
+        let rest = source[at..line_end].trim();
+        if rest.is_empty() || is_comment_line(rest) {
+            at = line_end;
+        }
+        let newline = newline(source);
+        let indent = indentation(source, last_start);
+        let trailing = if trailing_comma { "," } else { "" };
+        let splice = insert_at(at, [newline, indent, &member, trailing].concat());
+        let offset = newline.len() + indent.len();
+        return Ok(if trailing_comma {
+            (vec![splice], position(0, offset))
+        } else {
+            (vec![comma, splice], position(1, offset))
+        });
+    }
+
+    let separator = if spaced { " " } else { "" };
+    Ok(if trailing_comma {
+        let splice = insert_at(at, [separator, &member, ","].concat());
+        (vec![splice], position(0, separator.len()))
+    } else {
+        let splice = insert_at(last_end, [",", separator, &member].concat());
+        (vec![splice], position(0, 1 + separator.len()))
+    })
+}
+
+/// A copy of `source` without the member (or item) at `pointer`, and the span in `source`
+/// which was removed. The comma after it (or before it, for the last member) is also
+/// removed, along with a comment on the rest of its line. If it is on its own line, the
+/// line and comment lines directly above it are removed as well
+///
+/// ```
+/// use simple_json_parser::remove;
+///
+/// let source = "{\n  \"a\": 1,\n  // the b\n  \"b\": 2, // two\n  \"c\": 3\n}";
+/// let (removed, span) = remove(source, "/b").unwrap();
+/// assert_eq!(removed, "{\n  \"a\": 1,\n  \"c\": 3\n}");
+/// assert_eq!(span.slice(source), "  // the b\n  \"b\": 2, // two\n");
+/// ```
+///
+/// # Errors
+/// Returns an error if `source` is not valid JSON or there is no member or item at
+/// `pointer` (including when it is the root)
+pub fn remove(source: &str, pointer: &str) -> Result<(String, Span), EditError> {
+    let path = pointer_segments(pointer).ok_or(EditError::InvalidPath)?;
+    let target = locate_with_options(source, &path, &EDIT_OPTIONS)?;
+    let (splices, span) = remove_splices(source, &target)?;
+    Ok((apply_splices(source, &splices), span))
+}
+
+/// Also returns the span of the main splice
+pub(crate) fn remove_splices(
+    source: &str,
+    target: &Target,
+) -> Result<(Vec<Splice>, Span), EditError> {
+    let (Some(value), Some(_)) = (target.value, &target.parent) else {
+        return Err(EditError::NotFound);
+    };
+    let removal = |start, end| Splice {
+        start,
+        end,
+        text: String::new(),
+    };
+
+    let mut start = target.member_start;
+    let mut end = value.end;
+    let after = source[end..].trim_start();
+    let trailing_comma = after.starts_with(',');
+    if trailing_comma {
+        end = source.len() - after.len() + 1;
+    }
+
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |idx| end + idx);
+    let rest = source[end..line_end].trim();
+    let rest_is_comment = is_comment_line(rest);
+    let on_own_line = source[line_start(source, start)..start].trim().is_empty();
+
+    if on_own_line && (rest.is_empty() || rest_is_comment) {
+        start = line_start(source, start);
+        while start > 0 {
+            let above = line_start(source, start - 1);
+            if !is_comment_line(source[above..start].trim()) {
+                break;
+            }
+            start = above;
+        }
+        end = (line_end + 1).min(source.len());
+    } else if rest_is_comment {
+        end = line_end;
+    } else if trailing_comma {
+        // The space before the next member
+        end = source.len() - source[end..].trim_start_matches([' ', '\t']).len();
+    }
+
+    let span = Span { start, end };
+    if trailing_comma {
+        return Ok((vec![removal(start, end)], span));
+    }
+    // The comma after the previous value, if there is one
+    let Some(comma) = target.previous_end.and_then(|previous_end| {
+        let after = source[previous_end..].trim_start();
+        after.starts_with(',').then(|| source.len() - after.len())
+    }) else {
+        return Ok((vec![removal(start, end)], span));
+    };
+    if on_own_line {
+        Ok((vec![removal(comma, comma + 1), removal(start, end)], span))
+    } else {
+        // Also the space between the comma and the member
+        Ok((vec![removal(comma, end)], Span { start: comma, end }))
+    }
+}
+
+/// Whether a (trimmed) line is only a comment
+fn is_comment_line(line: &str) -> bool {
+    line.starts_with("//")
+        || line.starts_with('#')
+        || (line.starts_with("/*") && line.ends_with("*/"))
+}
+
+fn line_start(source: &str, at: usize) -> usize {
+    source[..at].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+/// The whitespace at the start of the line which `at` is on
+fn indentation(source: &str, at: usize) -> &str {
+    let line = &source[line_start(source, at)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// The line ending of the first line, `\r\n` or `\n`
+fn newline(source: &str) -> &'static str {
+    match source.find('\n') {
+        Some(idx) if source[..idx].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+/// The indentation of the first indented line, or four spaces
+fn indent_unit(source: &str) -> &str {
+    source
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("    ")
+}
@@ -0,0 +1,152 @@
+//! Handling of escape sequences in string content
+
+use alloc::{borrow::Cow, string::String};
+
+use super::{JSONParseError, JSONParseErrorReason};
+
+/// Decodes escape sequences in the content of a string value or key (as given by the
+/// callback). Only allocates if there is an escape sequence.
+///
+/// This is lenient: a lone surrogate becomes U+FFFD and an invalid escape is kept as is.
+/// Use [`try_unescape_string_content`] to reject them. It also decodes the additional
+/// JSON5 escapes (`\'`, `\v`, `\0`, `\x41` and escaped line breaks)
+///
+/// ```
+/// use simple_json_parser::unescape_string_content;
+///
+/// assert_eq!(unescape_string_content(r"caf\u00e9"), "café");
+/// assert_eq!(unescape_string_content(r"\ud83e\udd80"), "🦀");
+/// assert_eq!(unescape_string_content(r#"\"quoted\"\n"#), "\"quoted\"\n");
+/// ```
+#[must_use]
+pub fn unescape_string_content(on: &str) -> Cow<'_, str> {
+    match unescape(on, false) {
+        Ok(result) => result,
+        Err(_) => unreachable!("lenient unescape does not error"),
+    }
+}
+
+/// Like [`unescape_string_content`] but errors on invalid escapes (including lone
+/// surrogates). [`JSONParseError::at`] is relative to the start of `on`
+///
+/// # Errors
+/// Returns [`JSONParseErrorReason::InvalidEscape`] at the backslash of the invalid escape
+pub fn try_unescape_string_content(on: &str) -> Result<Cow<'_, str>, JSONParseError> {
+    unescape(on, true)
+}
+
+fn unescape(on: &str, strict: bool) -> Result<Cow<'_, str>, JSONParseError> {
+    if !on.contains('\\') {
+        return Ok(Cow::Borrowed(on));
+    }
+
+    let mut result = String::with_capacity(on.len());
+    let mut rest = on;
+    while let Some((before, after)) = rest.split_once('\\') {
+        result.push_str(before);
+        let at = on.len() - after.len() - 1;
+        let invalid = JSONParseError {
+            at,
+            reason: JSONParseErrorReason::InvalidEscape,
+            path: None,
+        };
+
+        let mut chars = after.chars();
+        let escaped = match chars.next() {
+            Some('u') => match unicode_escape(chars.as_str()) {
+                Some((Ok(chr), after)) => {
+                    result.push(chr);
+                    rest = after;
+                    continue;
+                }
+                Some((Err(()), after)) if !strict => {
+                    result.push(char::REPLACEMENT_CHARACTER);
+                    rest = after;
+                    continue;
+                }
+                _ if strict => return Err(invalid),
+                _ => {
+                    result.push_str("\\u");
+                    rest = chars.as_str();
+                    continue;
+                }
+            },
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some(chr @ ('"' | '\\' | '/')) => chr,
+            Some('\'') if !strict => '\'',
+            Some('v') if !strict => '\u{b}',
+            Some('0')
+                if !strict && !chars.as_str().starts_with(|chr: char| chr.is_ascii_digit()) =>
+            {
+                '\0'
+            }
+            Some('x') if !strict => {
+                let code = chars.as_str().get(..2).and_then(|digits| {
+                    digits
+                        .bytes()
+                        .all(|byte| byte.is_ascii_hexdigit())
+                        .then(|| u8::from_str_radix(digits, 16).ok())
+                        .flatten()
+                });
+                if let Some(code) = code {
+                    result.push(char::from(code));
+                    rest = &chars.as_str()[2..];
+                    continue;
+                }
+                result.push('\\');
+                'x'
+            }
+            // Line continuation
+            Some('\n' | '\u{2028}' | '\u{2029}') if !strict => {
+                rest = chars.as_str();
+                continue;
+            }
+            Some('\r') if !strict => {
+                rest = chars.as_str();
+                rest = rest.strip_prefix('\n').unwrap_or(rest);
+                continue;
+            }
+            Some(_) | None if strict => return Err(invalid),
+            Some(chr) => {
+                result.push('\\');
+                chr
+            }
+            None => '\\',
+        };
+        result.push(escaped);
+        rest = chars.as_str();
+    }
+    result.push_str(rest);
+
+    Ok(Cow::Owned(result))
+}
+
+/// `on` is after `\u`. Returns the character (or `Err` for a lone surrogate) and what
+/// follows. `None` if not four hex digits
+fn unicode_escape(on: &str) -> Option<(Result<char, ()>, &str)> {
+    let (code, rest) = hex_code(on)?;
+    if (0xD800..0xDC00).contains(&code) {
+        if let Some((low, after)) = rest.strip_prefix("\\u").and_then(hex_code) {
+            if (0xDC00..0xE000).contains(&low) {
+                let combined = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                return Some((char::from_u32(combined).ok_or(()), after));
+            }
+        }
+        Some((Err(()), rest))
+    } else {
+        Some((char::from_u32(code).ok_or(()), rest))
+    }
+}
+
+fn hex_code(on: &str) -> Option<(u32, &str)> {
+    let digits = on.get(..4)?;
+    if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let code = u32::from_str_radix(digits, 16).ok()?;
+    Some((code, &on[4..]))
+}
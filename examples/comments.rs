@@ -1,4 +1,4 @@
-use simple_json_parser::parse;
+use simple_json_parser::{parse_with_exit_signal, ParseOptions};
 
 fn main() {
     let content = r#"// Something
@@ -9,9 +9,14 @@ fn main() {
         # another comment
     }"#;
 
-    let result = parse(content, |keys, value| {
-        eprintln!("{keys:?} -> {value:?}");
-    });
+    let result = parse_with_exit_signal(
+        content,
+        |keys, value| {
+            eprintln!("{keys:?} -> {value:?}");
+            false
+        },
+        &ParseOptions::builder().allow_trailing_commas(true).build(),
+    );
 
     assert!(result.is_ok());
 }
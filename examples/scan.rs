@@ -89,7 +89,7 @@ fn main() {
 
     let result = parse(&content, |keys, value| eprintln!("{keys:?} -> {value:?}"));
 
-    if let Err(JSONParseError { at, reason }) = result {
+    if let Err(JSONParseError { at, reason, .. }) = result {
         eprintln!("{reason:?} @ {at}");
     }
 }
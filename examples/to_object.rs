@@ -3,9 +3,6 @@ use std::collections::HashMap;
 use simple_json_parser::{parse, JSONKey, RootJSONValue};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
-    let content = std::fs::read_to_string(path)?;
-
     pub type Object = HashMap<String, Value>;
 
     #[derive(Debug)]
@@ -32,9 +29,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     };
                     let value = match value {
                         RootJSONValue::String(s) => Value::String(s.to_string()),
-                        RootJSONValue::Number(n) => Value::Number(n.to_string()),
+                        RootJSONValue::Number(n) | RootJSONValue::BigNumber(n) => {
+                            Value::Number(n.to_string())
+                        }
+                        RootJSONValue::Integer(n) => Value::Number(n.to_string()),
+                        RootJSONValue::Float(n) | RootJSONValue::NonFinite(n) => {
+                            Value::Number(n.to_string())
+                        }
                         RootJSONValue::Boolean(v) => Value::Boolean(v),
                         RootJSONValue::Null => Value::Null,
+                        RootJSONValue::EmptyObject | RootJSONValue::EmptyArray => {
+                            Value::new_empty_object()
+                        }
                     };
                     let existing = obj.insert(name, value);
                     debug_assert!(existing.is_none());
@@ -55,6 +61,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
+    let content = std::fs::read_to_string(path)?;
+
     let mut root = Value::new_empty_object();
 
     parse(&content, |keys, value| root.set(keys, value))?;
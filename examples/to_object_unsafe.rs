@@ -5,9 +5,6 @@ use std::collections::HashMap;
 use simple_json_parser::{parse, JSONKey, RootJSONValue};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
-    let content = std::fs::read_to_string(path)?;
-
     pub type Object = HashMap<String, Value>;
 
     #[derive(Debug)]
@@ -26,6 +23,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
+    let content = std::fs::read_to_string(path)?;
+
     let mut root = Object::new();
 
     let _res = parse(&content, |keys, value| {
@@ -62,9 +62,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         };
         let value = match value {
             RootJSONValue::String(s) => Value::String(s.to_string()),
-            RootJSONValue::Number(n) => Value::Number(n.to_string()),
+            RootJSONValue::Number(n) | RootJSONValue::BigNumber(n) => Value::Number(n.to_string()),
+            RootJSONValue::Integer(n) => Value::Number(n.to_string()),
+            RootJSONValue::Float(n) | RootJSONValue::NonFinite(n) => Value::Number(n.to_string()),
             RootJSONValue::Boolean(v) => Value::Boolean(v),
             RootJSONValue::Null => Value::Null,
+            RootJSONValue::EmptyObject | RootJSONValue::EmptyArray => Value::new_empty_object(),
         };
         unsafe {
             (to_add_to.as_mut().unwrap()).insert(name, value);
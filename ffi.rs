@@ -0,0 +1,177 @@
+//! C API, declared in `include/simple_json_parser.h`. Build a library to link against with
+//! `cargo rustc --release --features ffi --crate-type staticlib` (or `cdylib`)
+
+use alloc::string::String;
+use core::ffi::{c_char, c_void};
+
+use super::{non_finite_literal, parse_bytes, pointer::write_pointer, ParseOptions, RootJSONValue};
+
+/// Kind of the value passed to [`SjpCallback`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SjpValueKind {
+    /// The value is the content between the quotes, without unescaping
+    String = 0,
+    /// The value is the source of the number
+    Number = 1,
+    /// The value is `true` or `false`
+    Boolean = 2,
+    Null = 3,
+    /// Only with [`SjpOptions::empty_containers`]
+    EmptyObject = 4,
+    EmptyArray = 5,
+}
+
+/// Options for [`sjp_parse`]. Pass null for the defaults (comments allowed, everything
+/// else off)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SjpOptions {
+    pub allow_comments: bool,
+    pub allow_trailing_commas: bool,
+    pub json5: bool,
+    pub empty_containers: bool,
+    /// Zero for no limit
+    pub max_depth: usize,
+}
+
+/// Filled in by [`sjp_parse`] on an error
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SjpError {
+    /// Byte offset in the input
+    pub at: usize,
+    /// See [`sjp_error_reason_name`]
+    pub reason: u32,
+}
+
+/// Called with `user_data`, the JSON Pointer of the value (not null terminated) and the value.
+/// Both slices are only valid during the call. Return `true` to stop parsing
+pub type SjpCallback = extern "C" fn(
+    user_data: *mut c_void,
+    path: *const c_char,
+    path_len: usize,
+    kind: SjpValueKind,
+    value: *const c_char,
+    value_len: usize,
+) -> bool;
+
+/// Parses `len` bytes from `input`, calling `callback` for each value. Returns the number of
+/// bytes parsed or `-1` on an error, in which case `error` (if not null) is filled in.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes, `options` must be null or point to an
+/// [`SjpOptions`] and `error` must be null or point to a writable [`SjpError`]
+#[no_mangle]
+pub unsafe extern "C" fn sjp_parse(
+    input: *const u8,
+    len: usize,
+    options: *const SjpOptions,
+    callback: SjpCallback,
+    user_data: *mut c_void,
+    error: *mut SjpError,
+) -> isize {
+    let input = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees `input` points to `len` bytes
+        unsafe { core::slice::from_raw_parts(input, len) }
+    };
+    // SAFETY: the caller guarantees `options` is null or valid
+    let options = match unsafe { options.as_ref() } {
+        Some(options) => ParseOptions {
+            allow_comments: options.allow_comments,
+            allow_trailing_commas: options.allow_trailing_commas,
+            json5: options.json5,
+            empty_containers: options.empty_containers,
+            max_depth: (options.max_depth != 0).then_some(options.max_depth),
+            ..ParseOptions::default()
+        },
+        None => ParseOptions::default(),
+    };
+
+    let mut path = String::new();
+    let result = parse_bytes(
+        input,
+        |keys, value| {
+            path.clear();
+            write_pointer(&mut path, keys);
+            let (kind, value) = match value {
+                RootJSONValue::String(value) => (SjpValueKind::String, value),
+                RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                    (SjpValueKind::Number, value)
+                }
+                RootJSONValue::Boolean(value) => {
+                    (SjpValueKind::Boolean, if value { "true" } else { "false" })
+                }
+                RootJSONValue::NonFinite(value) => {
+                    (SjpValueKind::Number, non_finite_literal(value))
+                }
+                RootJSONValue::Null => (SjpValueKind::Null, "null"),
+                RootJSONValue::EmptyObject => (SjpValueKind::EmptyObject, "{}"),
+                RootJSONValue::EmptyArray => (SjpValueKind::EmptyArray, "[]"),
+                // `typed_numbers` is not set
+                RootJSONValue::Integer(_) | RootJSONValue::Float(_) => unreachable!(),
+            };
+            callback(
+                user_data,
+                path.as_ptr().cast(),
+                path.len(),
+                kind,
+                value.as_ptr().cast(),
+                value.len(),
+            )
+        },
+        &options,
+    );
+
+    match result {
+        Ok(parsed) => isize::try_from(parsed).unwrap_or(isize::MAX),
+        Err(err) => {
+            // SAFETY: the caller guarantees `error` is null or valid
+            if let Some(error) = unsafe { error.as_mut() } {
+                *error = SjpError {
+                    at: err.at,
+                    reason: err.reason.code(),
+                };
+            }
+            -1
+        }
+    }
+}
+
+/// Null terminated name of an [`SjpError::reason`], or null if it is not a reason
+#[no_mangle]
+pub extern "C" fn sjp_error_reason_name(reason: u32) -> *const c_char {
+    let name: &'static core::ffi::CStr = match reason {
+        1 => c"ExpectedColon",
+        2 => c"ExpectedEndOfValue",
+        3 => c"ExpectedBracket",
+        4 => c"ExpectedTrueFalseNull",
+        5 => c"ExpectedKey",
+        6 => c"ExpectedValue",
+        7 => c"ExpectedEndOfMultilineComment",
+        8 => c"ExpectedQuote",
+        9 => c"InvalidUTF8",
+        10 => c"InvalidNumber",
+        11 => c"InvalidEscape",
+        12 => c"DepthLimitExceeded",
+        13 => c"ExpectedObjectOrArray",
+        14 => c"ControlCharacterInString",
+        15 => c"StringTooLong",
+        16 => c"TooManyKeys",
+        17 => c"TooManyValues",
+        18 => c"DisallowedComment",
+        19 => c"ExpectedEndOfInput",
+        20 => c"Cancelled",
+        21 => c"InputTooLarge",
+        22 => c"DeadlineExceeded",
+        23 => c"LoneSurrogate",
+        24 => c"UnterminatedString",
+        25 => c"UnexpectedTrailingContent",
+        26 => c"UnexpectedEndOfInput",
+        _ => return core::ptr::null(),
+    };
+    name.as_ptr()
+}
@@ -0,0 +1,32 @@
+//! Parsing files without reading them into memory first
+
+use std::{fs::File, path::Path};
+
+use memmap2::Mmap;
+
+use super::{parse_bytes, JSONKey, JSONReadError, ParseOptions, RootJSONValue};
+
+/// Parses a file by memory mapping it, so large files do not have to be read into a `String`
+/// first. Like [`crate::parse_bytes`], only emitted keys and values are checked for being
+/// valid UTF-8. Returns the number of bytes parsed.
+///
+/// The file should not be modified while it is being parsed
+///
+/// # Errors
+/// Returns an error if the file cannot be opened or mapped, or it tries to parse invalid
+/// JSON input (including emitted keys and values which are not UTF-8)
+pub fn parse_file(
+    path: impl AsRef<Path>,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONReadError> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(parse_bytes(&[], |keys, value| cb(keys, value), options)?);
+    }
+    // SAFETY: the map is only read from, and only while this function runs. If the file is
+    // changed by another process the output may be wrong, but parsing does not rely on the
+    // content for memory safety
+    let map = unsafe { Mmap::map(&file)? };
+    Ok(parse_bytes(&map, |keys, value| cb(keys, value), options)?)
+}
@@ -0,0 +1,291 @@
+//! Flattening documents into path and value pairs, and back
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use super::{
+    pointer::{pointer_segments, write_pointer},
+    unescape_string_content, Container, JSONKey, JSONParseError, JSONWriter, JsonReader,
+    ParseOptions, Value,
+};
+
+/// How [`flatten`] writes (and [`unflatten`] reads) the key chain of each value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// Keys and indexes joined with `.`, e.g. `a.list.0`. Keys are not escaped, so keys
+    /// containing `.` are ambiguous
+    #[default]
+    Dotted,
+    /// A JSON Pointer, e.g. `/a/list/0`
+    Pointer,
+}
+
+/// Iterator returned by [`flatten`]
+pub struct Flatten<'a> {
+    reader: JsonReader<'a>,
+    style: PathStyle,
+}
+
+/// The leaf values of `source` with their paths. Empty objects and arrays are leaves, so
+/// they are kept. The root value has the empty path
+///
+/// ```
+/// use simple_json_parser::{flatten, ParseOptions, PathStyle, Value};
+///
+/// let source = r#"{ "a": { "b": [1, true] }, "c": {} }"#;
+/// let pairs = flatten(source, PathStyle::Dotted, &ParseOptions::default())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(
+///     pairs,
+///     [
+///         ("a.b.0".to_owned(), Value::Number("1".to_owned())),
+///         ("a.b.1".to_owned(), Value::Boolean(true)),
+///         ("c".to_owned(), Value::Object(Vec::new())),
+///     ]
+/// );
+/// ```
+#[must_use]
+pub fn flatten<'a>(source: &'a str, style: PathStyle, options: &ParseOptions) -> Flatten<'a> {
+    let options = ParseOptions {
+        empty_containers: true,
+        ..options.clone()
+    };
+    Flatten {
+        reader: JsonReader::new(source, &options),
+        style,
+    }
+}
+
+impl Iterator for Flatten<'_> {
+    type Item = Result<(String, Value), JSONParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = match self.reader.next_value() {
+            Ok(Some(value)) => value,
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err)),
+        };
+        let keys = self.reader.key_chain();
+        let mut path = String::new();
+        match self.style {
+            PathStyle::Dotted => {
+                for (idx, key) in keys.iter().enumerate() {
+                    if idx > 0 {
+                        path.push('.');
+                    }
+                    match key {
+                        JSONKey::Slice(key) => path.push_str(&unescape_string_content(key)),
+                        JSONKey::Index(idx) => path.push_str(&idx.to_string()),
+                    }
+                }
+            }
+            PathStyle::Pointer => write_pointer(&mut path, keys),
+        }
+        Some(Ok((path, value.into())))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnflattenError {
+    /// From the [`Write`] output
+    Format(core::fmt::Error),
+    /// The path of the pair at this index is not a valid JSON Pointer
+    InvalidPath { index: usize },
+    /// The path of the pair at this index is (or is inside) the path of the previous pair,
+    /// or a non index segment is used in an array
+    Conflict { index: usize },
+    /// The pair at this index skips or goes back to an earlier array index
+    OutOfOrder { index: usize },
+}
+
+impl core::error::Error for UnflattenError {}
+
+impl core::fmt::Display for UnflattenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            UnflattenError::Format(err) => f.write_fmt(format_args!("UnflattenError: {err}")),
+            UnflattenError::InvalidPath { index } => f.write_fmt(format_args!(
+                "UnflattenError: invalid path for pair {index}"
+            )),
+            UnflattenError::Conflict { index } => f.write_fmt(format_args!(
+                "UnflattenError: path of pair {index} conflicts with the previous pair"
+            )),
+            UnflattenError::OutOfOrder { index } => {
+                f.write_fmt(format_args!("UnflattenError: pair {index} is out of order"))
+            }
+        }
+    }
+}
+
+impl From<core::fmt::Error> for UnflattenError {
+    fn from(err: core::fmt::Error) -> Self {
+        UnflattenError::Format(err)
+    }
+}
+
+/// Writes the pairs from [`flatten`] back as nested JSON. Objects and arrays are opened and
+/// closed as the paths change, so nothing is buffered, which means pairs in the same object
+/// or array must be next to each other (as [`flatten`] gives them). Segments which are
+/// indexes (`0` or digits without a leading zero) make arrays, and must count up from `0`
+///
+/// ```
+/// use simple_json_parser::{flatten, unflatten, JSONWriter, ParseOptions, PathStyle, Value};
+///
+/// let pairs = [
+///     ("a.b.0", Value::Number("1".to_owned())),
+///     ("a.b.1", Value::Boolean(true)),
+///     ("c", Value::Object(Vec::new())),
+/// ];
+/// let mut writer = JSONWriter::new(String::new());
+/// unflatten(pairs, PathStyle::Dotted, &mut writer).unwrap();
+/// assert_eq!(writer.into_inner(), r#"{"a":{"b":[1,true]},"c":{}}"#);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or the paths cannot make a document
+pub fn unflatten<W: Write, P: AsRef<str>>(
+    pairs: impl IntoIterator<Item = (P, Value)>,
+    style: PathStyle,
+    writer: &mut JSONWriter<W>,
+) -> Result<(), UnflattenError> {
+    // The segments of the objects and arrays which are open (below the root)
+    let mut open: Vec<String> = Vec::new();
+    // Open objects and arrays, with the next index for arrays
+    let mut containers: Vec<(Container, usize)> = Vec::new();
+    let mut previous: Option<Vec<String>> = None;
+
+    for (index, (path, value)) in pairs.into_iter().enumerate() {
+        let segments: Vec<String> = match style {
+            PathStyle::Dotted if path.as_ref().is_empty() => Vec::new(),
+            PathStyle::Dotted => path.as_ref().split('.').map(ToString::to_string).collect(),
+            PathStyle::Pointer => pointer_segments(path.as_ref())
+                .ok_or(UnflattenError::InvalidPath { index })?
+                .into_iter()
+                .map(Cow::into_owned)
+                .collect(),
+        };
+
+        if let Some(previous) = &previous {
+            let common = previous
+                .iter()
+                .zip(&segments)
+                .take_while(|(a, b)| a == b)
+                .count();
+            if segments.is_empty() || common == previous.len() || common == segments.len() {
+                return Err(UnflattenError::Conflict { index });
+            }
+        } else if let Some(first) = segments.first() {
+            open_container(writer, &mut containers, first)?;
+        }
+
+        let (last, parents) = segments
+            .split_last()
+            .map_or((None, &segments[..]), |(last, parents)| {
+                (Some(last), parents)
+            });
+        let common = open.iter().zip(parents).take_while(|(a, b)| a == b).count();
+        while open.len() > common {
+            open.pop();
+            close_container(writer, &mut containers)?;
+        }
+        for (idx, segment) in parents.iter().enumerate().skip(common) {
+            write_segment(writer, &mut containers, segment, index)?;
+            open_container(writer, &mut containers, &segments[idx + 1])?;
+            open.push(segment.clone());
+        }
+        if let Some(last) = last {
+            write_segment(writer, &mut containers, last, index)?;
+        }
+        write_value(writer, &value)?;
+        previous = Some(segments);
+    }
+
+    while !containers.is_empty() {
+        close_container(writer, &mut containers)?;
+    }
+    Ok(())
+}
+
+fn is_index(segment: &str) -> bool {
+    (segment == "0" || !segment.starts_with('0'))
+        && !segment.is_empty()
+        && segment.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Opens an object or array, depending on whether its first `segment` is an index
+fn open_container<W: Write>(
+    writer: &mut JSONWriter<W>,
+    containers: &mut Vec<(Container, usize)>,
+    segment: &str,
+) -> core::fmt::Result {
+    if is_index(segment) {
+        containers.push((Container::Array, 0));
+        writer.begin_array()
+    } else {
+        containers.push((Container::Object, 0));
+        writer.begin_object()
+    }
+}
+
+fn close_container<W: Write>(
+    writer: &mut JSONWriter<W>,
+    containers: &mut Vec<(Container, usize)>,
+) -> core::fmt::Result {
+    match containers.pop() {
+        Some((Container::Object, _)) => writer.end_object(),
+        Some((Container::Array, _)) => writer.end_array(),
+        None => Ok(()),
+    }
+}
+
+/// Writes the key, or checks the index, of `segment` in the innermost container
+fn write_segment<W: Write>(
+    writer: &mut JSONWriter<W>,
+    containers: &mut [(Container, usize)],
+    segment: &str,
+    index: usize,
+) -> Result<(), UnflattenError> {
+    match containers.last_mut() {
+        Some((Container::Object, _)) => Ok(writer.key(segment)?),
+        Some((Container::Array, next)) => {
+            if !is_index(segment) {
+                return Err(UnflattenError::Conflict { index });
+            }
+            if segment.parse() != Ok(*next) {
+                return Err(UnflattenError::OutOfOrder { index });
+            }
+            *next += 1;
+            Ok(())
+        }
+        None => Err(UnflattenError::Conflict { index }),
+    }
+}
+
+fn write_value<W: Write>(writer: &mut JSONWriter<W>, value: &Value) -> core::fmt::Result {
+    match value {
+        Value::Object(members) => {
+            writer.begin_object()?;
+            for (key, value) in members {
+                writer.key(key)?;
+                write_value(writer, value)?;
+            }
+            writer.end_object()
+        }
+        Value::Array(items) => {
+            writer.begin_array()?;
+            for item in items {
+                write_value(writer, item)?;
+            }
+            writer.end_array()
+        }
+        Value::String(value) => writer.string(value),
+        Value::Number(value) => writer.number(value),
+        Value::Boolean(value) => writer.boolean(*value),
+        Value::Null => writer.null(),
+    }
+}
@@ -0,0 +1,466 @@
+//! Writing the source back out with different whitespace (or without comments)
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{
+    parse_cst, tokenize, unescape_string_content, CommentStyle, CstKind, CstNode, JSONParseError,
+    ParseOptions, TokenKind,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JSONFormatError {
+    /// From the [`Write`] output
+    Format(core::fmt::Error),
+    Parse(JSONParseError),
+}
+
+impl core::error::Error for JSONFormatError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            JSONFormatError::Format(err) => Some(err),
+            JSONFormatError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl core::fmt::Display for JSONFormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            JSONFormatError::Format(err) => f.write_fmt(format_args!("JSONFormatError: {err}")),
+            JSONFormatError::Parse(err) => core::fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl From<core::fmt::Error> for JSONFormatError {
+    fn from(err: core::fmt::Error) -> Self {
+        JSONFormatError::Format(err)
+    }
+}
+
+impl From<JSONParseError> for JSONFormatError {
+    fn from(err: JSONParseError) -> Self {
+        JSONFormatError::Parse(err)
+    }
+}
+
+/// Writes `source` without whitespace, comments or trailing commas. Keys and values are
+/// written as they are in the source
+///
+/// ```
+/// use simple_json_parser::minify;
+///
+/// let mut out = String::new();
+/// minify("{\n  \"a\": [1, 2,], // comment\n}", &mut out).unwrap();
+/// assert_eq!(out, r#"{"a":[1,2]}"#);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or it tries to parse invalid JSON input
+pub fn minify(source: &str, out: &mut impl Write) -> Result<(), JSONFormatError> {
+    let options = ParseOptions {
+        allow_trailing_commas: true,
+        ..ParseOptions::default()
+    };
+    // Written once it is known not to be trailing
+    let mut comma = false;
+
+    for token in tokenize(source, &options) {
+        let token = token?;
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::Comment(_) => {}
+            TokenKind::Comma => comma = true,
+            kind => {
+                if comma && !matches!(kind, TokenKind::EndObject | TokenKind::EndArray) {
+                    out.write_char(',')?;
+                }
+                comma = false;
+                out.write_str(token.text)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Written for each level of nesting
+    pub indent: String,
+    pub newline: String,
+    /// Otherwise comments are removed
+    pub preserve_comments: bool,
+    /// End the output with [`FormatOptions::newline`]
+    pub final_newline: bool,
+    /// Writes the members of objects (at every level) in order of their keys. Members with
+    /// the same key keep their order. Comments before a member move with it, as do comments
+    /// after it on the same line
+    pub sort_keys: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "    ".into(),
+            newline: "\n".into(),
+            preserve_comments: true,
+            final_newline: true,
+            sort_keys: false,
+        }
+    }
+}
+
+/// Writes `source` with consistent indentation. Each key or array item goes on its own
+/// line and empty objects and arrays are written as `{}` and `[]`. Trailing commas are
+/// removed. Comments on the same line as the previous token stay on that line (after the
+/// comma if there is one), others go on their own line
+///
+/// ```
+/// use simple_json_parser::{format, FormatOptions};
+///
+/// let mut out = String::new();
+/// let options = FormatOptions { indent: "  ".into(), ..FormatOptions::default() };
+/// format(r#"{"a": [1, 2], "b": {}} // end"#, &mut out, &options).unwrap();
+/// assert_eq!(out, "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n} // end\n");
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or it tries to parse invalid JSON input
+pub fn format(
+    source: &str,
+    out: &mut impl Write,
+    options: &FormatOptions,
+) -> Result<(), JSONFormatError> {
+    let parse_options = ParseOptions {
+        allow_trailing_commas: true,
+        ..ParseOptions::default()
+    };
+    let sorted;
+    let source = if options.sort_keys {
+        let mut out = String::with_capacity(source.len());
+        sort_members(&parse_cst(source, &parse_options)?, source, &mut out);
+        sorted = out;
+        &sorted
+    } else {
+        source
+    };
+    let mut formatter = Formatter {
+        out,
+        options,
+        depth: 0,
+        previous: Previous::Nothing,
+        comma: false,
+        newline_in_whitespace: false,
+        needs_newline: false,
+        trailing_comments: Vec::new(),
+    };
+
+    for token in tokenize(source, &parse_options) {
+        let token = token?;
+        match token.kind {
+            TokenKind::Whitespace => {
+                formatter.newline_in_whitespace |= token.text.contains('\n');
+                continue;
+            }
+            TokenKind::Comment(style) => {
+                if options.preserve_comments {
+                    formatter.comment(token.text, style)?;
+                }
+            }
+            TokenKind::Comma => formatter.comma = true,
+            TokenKind::Colon => {
+                formatter.write_trailing_comments()?;
+                formatter.newline_if_needed()?;
+                formatter.out.write_char(':')?;
+                formatter.previous = Previous::Colon;
+            }
+            TokenKind::EndObject | TokenKind::EndArray => {
+                formatter.depth -= 1;
+                formatter.comma = false;
+                formatter.write_trailing_comments()?;
+                if formatter.previous != Previous::Open || formatter.needs_newline {
+                    formatter.line()?;
+                }
+                formatter.out.write_str(token.text)?;
+                formatter.previous = Previous::Item;
+            }
+            TokenKind::StartObject | TokenKind::StartArray => {
+                formatter.start_item()?;
+                formatter.out.write_str(token.text)?;
+                formatter.depth += 1;
+                formatter.previous = Previous::Open;
+            }
+            _ => {
+                formatter.start_item()?;
+                formatter.out.write_str(token.text)?;
+                formatter.previous = Previous::Item;
+            }
+        }
+        formatter.newline_in_whitespace = false;
+    }
+
+    formatter.write_trailing_comments()?;
+    if options.final_newline && formatter.previous != Previous::Nothing {
+        formatter.out.write_str(&options.newline)?;
+    }
+    Ok(())
+}
+
+/// Writes `node` with the members of objects sorted by key. Whitespace and comments are
+/// kept, anything before a member (after the previous comma) moves with it, apart from
+/// comments on the same line as the previous comma which stay with the previous member
+fn sort_members(node: &CstNode, source: &str, out: &mut String) {
+    if node.kind != CstKind::Object {
+        if node.children.is_empty() {
+            out.push_str(node.text(source));
+        }
+        for child in &node.children {
+            sort_members(child, source, out);
+        }
+        return;
+    }
+
+    let [open, inner @ .., close] = node.children.as_slice() else {
+        return;
+    };
+    // Members with everything around them up to the commas, and the comments after the
+    // comma on the same line
+    let mut groups: Vec<(Cow<'_, str>, &[CstNode], &[CstNode])> = Vec::new();
+    let mut group_start = 0;
+    let mut tail: &[CstNode] = &[];
+    for (idx, child) in inner.iter().enumerate() {
+        let is_comma = child.kind == CstKind::Token(TokenKind::Comma);
+        let ends_group = idx >= group_start && (is_comma || idx + 1 == inner.len());
+        if !ends_group {
+            continue;
+        }
+        let end = if is_comma { idx } else { idx + 1 };
+        let group = &inner[group_start..end];
+        group_start = idx + 1;
+        let Some(member) = group.iter().find(|part| part.kind == CstKind::Member) else {
+            // After a trailing comma or in an empty object
+            tail = group;
+            continue;
+        };
+        let key = member.key().map_or("", |key| key.text(source));
+        let key = key
+            .strip_prefix(['"', '\''])
+            .and_then(|key| key.strip_suffix(['"', '\'']))
+            .unwrap_or(key);
+        let trailing = if is_comma {
+            let length = same_line_comments(&inner[group_start..], source);
+            group_start += length;
+            &inner[idx + 1..group_start]
+        } else {
+            &[]
+        };
+        groups.push((unescape_string_content(key), group, trailing));
+    }
+    groups.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+    out.push_str(open.text(source));
+    for (idx, (_, group, trailing)) in groups.iter().enumerate() {
+        for part in *group {
+            sort_members(part, source, out);
+        }
+        if idx + 1 < groups.len() {
+            out.push(',');
+        }
+        for part in *trailing {
+            sort_members(part, source, out);
+        }
+        // The member after it in the output may not have started on a new line
+        if let Some(CstKind::Token(TokenKind::Comment(CommentStyle::Line | CommentStyle::Hash))) =
+            trailing.last().map(|part| part.kind)
+        {
+            out.push('\n');
+        }
+    }
+    for part in tail {
+        sort_members(part, source, out);
+    }
+    out.push_str(close.text(source));
+}
+
+/// Number of `nodes` up to and including the last comment before a new line
+fn same_line_comments(nodes: &[CstNode], source: &str) -> usize {
+    let mut length = 0;
+    for (idx, node) in nodes.iter().enumerate() {
+        match node.kind {
+            CstKind::Token(TokenKind::Whitespace) if !node.text(source).contains('\n') => {}
+            CstKind::Token(TokenKind::Comment(style)) => {
+                length = idx + 1;
+                if style != CommentStyle::Block {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    length
+}
+
+/// Last thing written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Previous {
+    Nothing,
+    /// `{` or `[`
+    Open,
+    Colon,
+    /// Key, value, end of object or array or comment
+    Item,
+}
+
+struct Formatter<'a, W> {
+    out: &'a mut W,
+    options: &'a FormatOptions,
+    depth: usize,
+    previous: Previous,
+    /// Written before the next item, so trailing commas are dropped
+    comma: bool,
+    /// Between the previous token and this one in the source
+    newline_in_whitespace: bool,
+    /// After a comment which (could) run to the end of the line
+    needs_newline: bool,
+    /// Comments on the same line as the previous token, written once the next token is
+    /// known so that a comma after them in the source goes before them
+    trailing_comments: Vec<(&'a str, CommentStyle)>,
+}
+
+impl<'a, W: Write> Formatter<'a, W> {
+    fn line(&mut self) -> Result<(), core::fmt::Error> {
+        self.out.write_str(&self.options.newline)?;
+        for _ in 0..self.depth {
+            self.out.write_str(&self.options.indent)?;
+        }
+        self.needs_newline = false;
+        Ok(())
+    }
+
+    fn newline_if_needed(&mut self) -> Result<(), core::fmt::Error> {
+        if self.needs_newline {
+            self.line()?;
+        }
+        Ok(())
+    }
+
+    fn write_comma(&mut self) -> Result<(), core::fmt::Error> {
+        if self.comma {
+            self.out.write_char(',')?;
+            self.comma = false;
+        }
+        Ok(())
+    }
+
+    /// Before a key or value
+    fn start_item(&mut self) -> Result<(), core::fmt::Error> {
+        self.write_comma()?;
+        self.write_trailing_comments()?;
+        match self.previous {
+            Previous::Nothing => self.newline_if_needed(),
+            Previous::Colon if self.needs_newline => self.line(),
+            Previous::Colon => self.out.write_char(' '),
+            Previous::Open | Previous::Item => self.line(),
+        }
+    }
+
+    fn comment(&mut self, text: &'a str, style: CommentStyle) -> Result<(), core::fmt::Error> {
+        if !self.newline_in_whitespace && self.previous != Previous::Nothing {
+            self.trailing_comments.push((text, style));
+            return Ok(());
+        }
+        self.write_comma()?;
+        self.write_trailing_comments()?;
+        self.write_comment(text, style, false)
+    }
+
+    fn write_trailing_comments(&mut self) -> Result<(), core::fmt::Error> {
+        for (text, style) in core::mem::take(&mut self.trailing_comments) {
+            self.write_comment(text, style, true)?;
+        }
+        Ok(())
+    }
+
+    fn write_comment(
+        &mut self,
+        text: &str,
+        style: CommentStyle,
+        trailing: bool,
+    ) -> Result<(), core::fmt::Error> {
+        if trailing && !self.needs_newline {
+            self.out.write_char(' ')?;
+        } else if self.previous != Previous::Nothing {
+            self.line()?;
+        }
+        self.out.write_str(text)?;
+        self.needs_newline = !trailing || style != CommentStyle::Block;
+        if self.previous != Previous::Open && self.previous != Previous::Colon {
+            self.previous = Previous::Item;
+        }
+        Ok(())
+    }
+}
+
+/// Turns JSONC into JSON by replacing comments (and trailing commas if
+/// `remove_trailing_commas`) with spaces. Line breaks in comments are kept, so positions
+/// (byte offsets, lines and columns) are the same as in the source. Only allocates if
+/// there is something to remove
+///
+/// ```
+/// use simple_json_parser::strip_extensions;
+///
+/// let stripped = strip_extensions("{\"a\": 1, // comment\n}", true).unwrap();
+/// assert_eq!(stripped, "{\"a\": 1            \n}");
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn strip_extensions(
+    source: &str,
+    remove_trailing_commas: bool,
+) -> Result<Cow<'_, str>, JSONParseError> {
+    let options = ParseOptions {
+        allow_trailing_commas: true,
+        ..ParseOptions::default()
+    };
+    let mut remove = Vec::new();
+    let mut comma = None;
+
+    for token in tokenize(source, &options) {
+        let token = token?;
+        match token.kind {
+            TokenKind::Whitespace => {}
+            TokenKind::Comment(_) => remove.push(token.span),
+            TokenKind::Comma => comma = Some(token.span),
+            TokenKind::EndObject | TokenKind::EndArray => {
+                if let (true, Some(comma)) = (remove_trailing_commas, comma.take()) {
+                    remove.push(comma);
+                }
+            }
+            _ => comma = None,
+        }
+    }
+
+    if remove.is_empty() {
+        return Ok(Cow::Borrowed(source));
+    }
+
+    remove.sort_by_key(|span| span.start);
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+    for span in remove {
+        result.push_str(&source[last..span.start]);
+        for chr in span.slice(source).chars() {
+            if matches!(chr, '\n' | '\r') {
+                result.push(chr);
+            } else {
+                result.extend(core::iter::repeat_n(' ', chr.len_utf8()));
+            }
+        }
+        last = span.end;
+    }
+    result.push_str(&source[last..]);
+
+    Ok(Cow::Owned(result))
+}
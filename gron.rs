@@ -0,0 +1,108 @@
+//! Printing values as assignments, like [gron](https://github.com/tomnomnom/gron)
+
+use core::fmt::Write;
+
+use super::{
+    non_finite_literal, unescape_string_content, writer::write_string, JSONEvent, JSONFormatError,
+    JSONKey, JsonReader, ParseOptions, RootJSONValue,
+};
+
+/// Writes each object, array and value of `source` as an assignment on its own line, so the
+/// document can be searched with line based tools. Objects and arrays are assigned `{}` and
+/// `[]` before their contents. Keys which are identifiers use `.key`, others `["key"]`.
+/// Strings are unescaped and written with JSON escapes. Keys are in source order and
+/// comments are skipped
+///
+/// ```
+/// use simple_json_parser::{gron, ParseOptions};
+///
+/// let source = r#"{ "build": { "entries": ["./src/main", "./src/initialised"], "a-b": null } }"#;
+/// let mut out = String::new();
+/// gron(source, &mut out, &ParseOptions::default()).unwrap();
+/// assert_eq!(
+///     out,
+///     r#"json = {};
+/// json.build = {};
+/// json.build.entries = [];
+/// json.build.entries[0] = "./src/main";
+/// json.build.entries[1] = "./src/initialised";
+/// json.build["a-b"] = null;
+/// "#
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or it tries to parse invalid JSON input
+pub fn gron(
+    source: &str,
+    out: &mut impl Write,
+    options: &ParseOptions,
+) -> Result<(), JSONFormatError> {
+    let options = ParseOptions {
+        typed_numbers: false,
+        ..options.clone()
+    };
+    let mut reader = JsonReader::new(source, &options);
+    while let Some(event) = reader.next_event()? {
+        let value = match event {
+            JSONEvent::StartObject => "{}",
+            JSONEvent::StartArray => "[]",
+            JSONEvent::Value(value) => {
+                write_path(out, reader.key_chain())?;
+                match value {
+                    RootJSONValue::String(value) => {
+                        write_string(out, &unescape_string_content(value))?;
+                    }
+                    RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                        out.write_str(value)?;
+                    }
+                    RootJSONValue::Boolean(value) => {
+                        out.write_str(if value { "true" } else { "false" })?;
+                    }
+                    RootJSONValue::NonFinite(value) => out.write_str(non_finite_literal(value))?,
+                    RootJSONValue::Null => out.write_str("null")?,
+                    RootJSONValue::EmptyObject => out.write_str("{}")?,
+                    RootJSONValue::EmptyArray => out.write_str("[]")?,
+                    // `typed_numbers` is not set
+                    RootJSONValue::Integer(_) | RootJSONValue::Float(_) => unreachable!(),
+                }
+                out.write_str(";\n")?;
+                continue;
+            }
+            _ => continue,
+        };
+        write_path(out, reader.key_chain())?;
+        out.write_str(value)?;
+        out.write_str(";\n")?;
+    }
+    Ok(())
+}
+
+fn write_path(out: &mut impl Write, keys: &[JSONKey<'_>]) -> core::fmt::Result {
+    out.write_str("json")?;
+    for key in keys {
+        match key {
+            JSONKey::Slice(key) => {
+                let key = unescape_string_content(key);
+                if is_identifier(&key) {
+                    out.write_char('.')?;
+                    out.write_str(&key)?;
+                } else {
+                    out.write_char('[')?;
+                    write_string(out, &key)?;
+                    out.write_char(']')?;
+                }
+            }
+            JSONKey::Index(idx) => write!(out, "[{idx}]")?,
+        }
+    }
+    out.write_str(" = ")
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|chr| chr.is_ascii_alphabetic() || chr == '_' || chr == '$')
+        && chars.all(|chr| chr.is_ascii_alphanumeric() || chr == '_' || chr == '$')
+}
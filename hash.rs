@@ -0,0 +1,87 @@
+//! Hashing the content of the input, without building a tree
+
+use core::hash::Hasher;
+
+use super::{
+    non_finite_literal, parse_with_exit_signal, unescape_string_content, JSONKey, JSONParseError,
+    ParseOptions, RootJSONValue,
+};
+
+/// Feeds each value and its key chain into `hasher`, so inputs which differ only in
+/// whitespace, comments, quote style or escapes give the same hash. The order of keys
+/// matters and numbers are hashed as written (so `1.0` and `1` differ). Empty objects and
+/// arrays are included whatever [`ParseOptions::empty_containers`] is
+///
+/// ```
+/// use std::hash::{DefaultHasher, Hasher};
+/// use simple_json_parser::{hash, ParseOptions};
+///
+/// let digest = |source: &str| {
+///     let mut hasher = DefaultHasher::new();
+///     hash(source, &mut hasher, &ParseOptions::json5()).unwrap();
+///     hasher.finish()
+/// };
+/// assert_eq!(digest(r#"{ "a": [1, "x"] }"#), digest("{a:[1,'x']} // same"));
+/// assert_ne!(digest(r#"{ "a": [1, "x"] }"#), digest(r#"{ "a": [1, "y"] }"#));
+/// assert_ne!(digest(r#"{ "a": {} }"#), digest(r#"{ "a": [] }"#));
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn hash(
+    source: &str,
+    hasher: &mut impl Hasher,
+    options: &ParseOptions,
+) -> Result<(), JSONParseError> {
+    let options = ParseOptions {
+        empty_containers: true,
+        typed_numbers: false,
+        ..options.clone()
+    };
+    parse_with_exit_signal(
+        source,
+        |keys, value| {
+            for key in keys {
+                match key {
+                    JSONKey::Slice(key) => {
+                        hasher.write_u8(0);
+                        write_str(hasher, &unescape_string_content(key));
+                    }
+                    JSONKey::Index(idx) => {
+                        hasher.write_u8(1);
+                        hasher.write_u64(*idx as u64);
+                    }
+                }
+            }
+            match value {
+                RootJSONValue::String(value) => {
+                    hasher.write_u8(2);
+                    write_str(hasher, &unescape_string_content(value));
+                }
+                RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                    hasher.write_u8(3);
+                    write_str(hasher, value);
+                }
+                RootJSONValue::NonFinite(value) => {
+                    hasher.write_u8(3);
+                    write_str(hasher, non_finite_literal(value));
+                }
+                RootJSONValue::Boolean(value) => hasher.write_u8(if value { 4 } else { 5 }),
+                RootJSONValue::Null => hasher.write_u8(6),
+                RootJSONValue::EmptyObject => hasher.write_u8(7),
+                RootJSONValue::EmptyArray => hasher.write_u8(8),
+                // `typed_numbers` is not set
+                RootJSONValue::Integer(_) | RootJSONValue::Float(_) => unreachable!(),
+            }
+            false
+        },
+        &options,
+    )?;
+    Ok(())
+}
+
+/// Length prefixed, so the boundaries are part of the hash
+fn write_str(hasher: &mut impl Hasher, value: &str) {
+    hasher.write_u64(value.len() as u64);
+    hasher.write(value.as_bytes());
+}
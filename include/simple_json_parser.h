@@ -0,0 +1,59 @@
+/* C API for simple-json-parser, built with the `ffi` feature */
+#ifndef SIMPLE_JSON_PARSER_H
+#define SIMPLE_JSON_PARSER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/types.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    /* The content between the quotes, without unescaping */
+    SJP_STRING = 0,
+    /* The source of the number */
+    SJP_NUMBER = 1,
+    SJP_BOOLEAN = 2,
+    SJP_NULL = 3,
+    /* Only with `empty_containers` */
+    SJP_EMPTY_OBJECT = 4,
+    SJP_EMPTY_ARRAY = 5,
+} sjp_value_kind;
+
+typedef struct {
+    bool allow_comments;
+    bool allow_trailing_commas;
+    bool json5;
+    bool empty_containers;
+    /* Zero for no limit */
+    size_t max_depth;
+} sjp_options;
+
+typedef struct {
+    /* Byte offset in the input */
+    size_t at;
+    /* See `sjp_error_reason_name` */
+    uint32_t reason;
+} sjp_error;
+
+/* `path` is the JSON Pointer of the value. Neither slice is null terminated and both are only
+   valid during the call. Return true to stop parsing */
+typedef bool (*sjp_callback)(void *user_data, const char *path, size_t path_len,
+                             sjp_value_kind kind, const char *value, size_t value_len);
+
+/* Returns the number of bytes parsed, or -1 on an error (filling in `error` if it is not
+   NULL). `options` can be NULL for the defaults */
+ssize_t sjp_parse(const uint8_t *input, size_t len, const sjp_options *options,
+                  sjp_callback callback, void *user_data, sjp_error *error);
+
+/* Null terminated name of a reason, or NULL */
+const char *sjp_error_reason_name(uint32_t reason);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
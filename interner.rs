@@ -0,0 +1,161 @@
+//! Deduplicating keys across a parse
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use super::{JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue, Step};
+
+/// Identifies a key in a [`KeyInterner`]. Equal keys have equal ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyId(u32);
+
+impl KeyId {
+    /// Ids are given out in order from zero, so this can index a `Vec`
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Segment of the key chain given by [`parse_with_interned_keys`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InternedKey {
+    Key(KeyId),
+    Index(usize),
+}
+
+/// Maps keys (as in the source, so without unescaping) to [`KeyId`]s. Can be reused across
+/// parses of sources which live for `'a`
+#[derive(Debug, Default, Clone)]
+pub struct KeyInterner<'a> {
+    ids: BTreeMap<&'a str, KeyId>,
+    keys: Vec<&'a str>,
+}
+
+impl<'a> KeyInterner<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    /// If there are more than `u32::MAX` different keys
+    pub fn intern(&mut self, key: &'a str) -> KeyId {
+        if let Some(id) = self.ids.get(key) {
+            return *id;
+        }
+        let id = KeyId(u32::try_from(self.keys.len()).expect("too many keys"));
+        self.keys.push(key);
+        self.ids.insert(key, id);
+        id
+    }
+
+    /// The id of `key`, if it has been interned
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<KeyId> {
+        self.ids.get(key).copied()
+    }
+
+    /// The key for `id`. Equal keys resolve to the same `&str`
+    ///
+    /// # Panics
+    /// If `id` is from another interner
+    #[must_use]
+    pub fn resolve(&self, id: KeyId) -> &'a str {
+        self.keys[id.index()]
+    }
+
+    /// Number of different keys
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The key chain with keys resolved
+    #[must_use]
+    pub fn resolve_chain(&self, keys: &[InternedKey]) -> Vec<JSONKey<'a>> {
+        keys.iter()
+            .map(|key| match key {
+                InternedKey::Key(id) => JSONKey::Slice(self.resolve(*id)),
+                InternedKey::Index(idx) => JSONKey::Index(*idx),
+            })
+            .collect()
+    }
+}
+
+/// Like [`crate::parse_with_exit_signal`] but keys in the key chain are given as
+/// [`KeyId`]s from `interner`, which is also passed to the callback. Comparing and grouping
+/// by ids is cheaper than by strings. Returns the number of bytes parsed.
+///
+/// ```
+/// use simple_json_parser::{parse_with_interned_keys, InternedKey, KeyInterner, ParseOptions};
+///
+/// let source = r#"[{ "id": 1, "name": "a" }, { "id": 2, "name": "b" }]"#;
+/// let mut interner = KeyInterner::new();
+/// let mut ids = Vec::new();
+/// parse_with_interned_keys(
+///     source,
+///     &mut interner,
+///     |keys, _value, _interner| {
+///         ids.push(keys[1]);
+///         false
+///     },
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(interner.len(), 2);
+/// assert_eq!(ids[0], ids[2]);
+/// let InternedKey::Key(name) = ids[1] else { panic!() };
+/// assert_eq!(interner.resolve(name), "name");
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_interned_keys<'a>(
+    on: &'a str,
+    interner: &mut KeyInterner<'a>,
+    mut cb: impl for<'b> FnMut(&'b [InternedKey], RootJSONValue<'a>, &'b KeyInterner<'a>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    // Keys are set on `Step::Key`, indexes are copied before each value
+    let mut keys = Vec::new();
+
+    while let Some(step) = reader.next_step()? {
+        match step {
+            Step::Key { .. } => {
+                if let Some((JSONKey::Slice(key), parent)) = reader.key_chain.split_last() {
+                    keys.resize(parent.len(), InternedKey::Index(0));
+                    keys.push(InternedKey::Key(interner.intern(key)));
+                }
+            }
+            Step::Value {
+                kind, start, end, ..
+            } => {
+                keys.resize(reader.key_chain.len(), InternedKey::Index(0));
+                for (interned, key) in keys.iter_mut().zip(&reader.key_chain) {
+                    if let JSONKey::Index(idx) = key {
+                        *interned = InternedKey::Index(*idx);
+                    }
+                }
+                let value = reader.value(kind, start, end);
+                if cb(&keys, value, interner)
+                    || (options.exits_on_first_value() && reader.machine.is_at_root())
+                {
+                    return Ok(end);
+                }
+            }
+            Step::EndObject { at } | Step::EndArray { at }
+                if options.exits_on_first_value() && reader.machine.is_at_root() =>
+            {
+                return Ok(at + 1);
+            }
+            _ => {}
+        }
+    }
+    Ok(on.len())
+}
@@ -0,0 +1,317 @@
+//! A subset of [JSONPath](https://www.rfc-editor.org/rfc/rfc9535) that can be evaluated while
+//! parsing
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+use super::{
+    unescape_string_content, JSONEvent, JSONKey, JSONParseError, JsonReader, ParseOptions,
+    RootJSONValue,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JSONPathErrorReason {
+    /// Paths start with `$`
+    ExpectedRoot,
+    /// After `.`, `..` or in `[...]`
+    ExpectedSelector,
+    ExpectedBracket,
+    ExpectedQuote,
+    /// Negative indexes and slices need the length of the array, which is not known while
+    /// streaming
+    NegativeIndex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JSONPathError {
+    pub at: usize,
+    pub reason: JSONPathErrorReason,
+}
+
+impl core::error::Error for JSONPathError {}
+
+impl core::fmt::Display for JSONPathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_fmt(format_args!(
+            "JSONPathError: {:?} at {:?}",
+            self.reason, self.at
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(usize),
+    Slice {
+        start: usize,
+        end: Option<usize>,
+        step: usize,
+    },
+}
+
+impl Selector {
+    fn matches(&self, key: &JSONKey<'_>) -> bool {
+        match (self, key) {
+            (Selector::Wildcard, _) => true,
+            (Selector::Name(name), JSONKey::Slice(key)) => unescape_string_content(key) == *name,
+            (Selector::Index(idx), JSONKey::Index(key)) => idx == key,
+            (Selector::Slice { start, end, step }, JSONKey::Index(key)) => {
+                key >= start && end.is_none_or(|end| *key < end) && (key - start) % step == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Segment {
+    /// `..`
+    descendant: bool,
+    /// More than one for unions (`[0,2]`)
+    selectors: Vec<Selector>,
+}
+
+impl Segment {
+    fn matches(&self, key: &JSONKey<'_>) -> bool {
+        self.selectors.iter().any(|selector| selector.matches(key))
+    }
+}
+
+/// A compiled `JSONPath` expression. Supports `$`, `.name`, `['name']`, `.*`, `[*]`, `..`,
+/// indexes, unions (`[0,2]`) and slices (`[1:5:2]`) with non negative bounds. Filters and
+/// functions are not supported.
+///
+/// ```
+/// use simple_json_parser::JSONPath;
+///
+/// let path = JSONPath::compile("$.store.book[*].author").unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JSONPath {
+    segments: Vec<Segment>,
+}
+
+impl JSONPath {
+    /// # Errors
+    /// Returns an error if the expression is not valid or uses an unsupported feature
+    pub fn compile(expression: &str) -> Result<Self, JSONPathError> {
+        let mut parser = PathParser {
+            on: expression,
+            idx: 0,
+        };
+        if !parser.eat('$') {
+            return Err(parser.error(JSONPathErrorReason::ExpectedRoot));
+        }
+
+        let mut segments = Vec::new();
+        while parser.idx < expression.len() {
+            let descendant = parser.eat_str("..");
+            let selectors = if parser.eat('[') {
+                parser.bracket()?
+            } else if descendant || parser.eat('.') {
+                vec![parser.dot_selector()?]
+            } else {
+                return Err(parser.error(JSONPathErrorReason::ExpectedSelector));
+            };
+            segments.push(Segment {
+                descendant,
+                selectors,
+            });
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Whether a value at this key chain is selected by the path
+    #[must_use]
+    pub fn matches(&self, keys: &[JSONKey<'_>]) -> bool {
+        fn matches_from(segments: &[Segment], keys: &[JSONKey<'_>]) -> bool {
+            let Some((segment, rest)) = segments.split_first() else {
+                return keys.is_empty();
+            };
+            if segment.descendant {
+                (0..keys.len()).any(|idx| {
+                    segment.matches(&keys[idx]) && matches_from(rest, &keys[(idx + 1)..])
+                })
+            } else {
+                keys.split_first()
+                    .is_some_and(|(key, keys)| segment.matches(key) && matches_from(rest, keys))
+            }
+        }
+
+        matches_from(&self.segments, keys)
+    }
+}
+
+struct PathParser<'a> {
+    on: &'a str,
+    idx: usize,
+}
+
+impl PathParser<'_> {
+    fn rest(&self) -> &str {
+        &self.on[self.idx..]
+    }
+
+    fn error(&self, reason: JSONPathErrorReason) -> JSONPathError {
+        JSONPathError {
+            at: self.idx,
+            reason,
+        }
+    }
+
+    fn eat(&mut self, chr: char) -> bool {
+        let found = self.rest().starts_with(chr);
+        if found {
+            self.idx += chr.len_utf8();
+        }
+        found
+    }
+
+    fn eat_str(&mut self, value: &str) -> bool {
+        let found = self.rest().starts_with(value);
+        if found {
+            self.idx += value.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.idx = self.on.len() - self.rest().trim_start().len();
+    }
+
+    /// After `.` or `..`
+    fn dot_selector(&mut self) -> Result<Selector, JSONPathError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let length = self
+            .rest()
+            .find(|chr: char| matches!(chr, '.' | '[') || chr.is_whitespace())
+            .unwrap_or(self.rest().len());
+        if length == 0 {
+            return Err(self.error(JSONPathErrorReason::ExpectedSelector));
+        }
+        let name = self.rest()[..length].to_owned();
+        self.idx += length;
+        Ok(Selector::Name(name))
+    }
+
+    /// After `[`
+    fn bracket(&mut self) -> Result<Vec<Selector>, JSONPathError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.bracket_selector()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(selectors);
+            } else if !self.eat(',') {
+                return Err(self.error(JSONPathErrorReason::ExpectedBracket));
+            }
+        }
+    }
+
+    fn bracket_selector(&mut self) -> Result<Selector, JSONPathError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        for quote in ['\'', '"'] {
+            if self.eat(quote) {
+                let Some(length) = self.rest().find(quote) else {
+                    return Err(self.error(JSONPathErrorReason::ExpectedQuote));
+                };
+                let name = self.rest()[..length].to_owned();
+                self.idx += length + quote.len_utf8();
+                return Ok(Selector::Name(name));
+            }
+        }
+
+        let start = self.index()?;
+        if !self.eat(':') {
+            return start
+                .map(Selector::Index)
+                .ok_or_else(|| self.error(JSONPathErrorReason::ExpectedSelector));
+        }
+        let end = self.index()?;
+        let step = if self.eat(':') { self.index()? } else { None };
+        Ok(Selector::Slice {
+            start: start.unwrap_or(0),
+            end,
+            step: step.unwrap_or(1).max(1),
+        })
+    }
+
+    fn index(&mut self) -> Result<Option<usize>, JSONPathError> {
+        if self.rest().starts_with('-') {
+            return Err(self.error(JSONPathErrorReason::NegativeIndex));
+        }
+        let length = self
+            .rest()
+            .find(|chr: char| !chr.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        if length == 0 {
+            return Ok(None);
+        }
+        let index = self.rest()[..length]
+            .parse()
+            .map_err(|_| self.error(JSONPathErrorReason::ExpectedSelector))?;
+        self.idx += length;
+        Ok(Some(index))
+    }
+}
+
+/// Selected by a [`JSONPath`]
+#[derive(Debug, PartialEq)]
+pub enum PathMatch<'a> {
+    Value(RootJSONValue<'a>),
+    /// Source of the selected object
+    Object(&'a str),
+    /// Source of the selected array
+    Array(&'a str),
+}
+
+/// Calls `cb` for everything `path` selects while parsing. Objects and arrays are given
+/// as their source once they have ended, so come after any selected values inside them
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn query_json_path<'a>(
+    on: &'a str,
+    path: &JSONPath,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], PathMatch<'a>),
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    // Start of open objects and arrays which are selected
+    let mut open: Vec<Option<usize>> = Vec::new();
+
+    while let Some(event) = reader.next_event()? {
+        match event {
+            JSONEvent::StartObject | JSONEvent::StartArray => {
+                let selected = path.matches(reader.key_chain());
+                open.push(selected.then_some(reader.span().start));
+            }
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                if let Some(Some(start)) = open.pop() {
+                    let source = &on[start..reader.span().end];
+                    let selected = if let JSONEvent::EndObject = event {
+                        PathMatch::Object(source)
+                    } else {
+                        PathMatch::Array(source)
+                    };
+                    cb(reader.key_chain(), selected);
+                }
+            }
+            JSONEvent::Value(value) => {
+                if path.matches(reader.key_chain()) {
+                    cb(reader.key_chain(), PathMatch::Value(value));
+                }
+            }
+            JSONEvent::Key(_) | JSONEvent::Comment { .. } => {}
+        }
+    }
+
+    Ok(on.len())
+}
@@ -0,0 +1,132 @@
+//! [RFC 7464](https://www.rfc-editor.org/rfc/rfc7464) JSON text sequences
+//! (`application/json-seq`)
+
+use alloc::vec::Vec;
+
+use super::{
+    JSONEvent, JSONKey, JSONParseError, JSONParseErrorReason, JsonReader, ParseOptions, Span,
+};
+
+/// Record separator, which starts each record
+const RS: char = '\u{1E}';
+
+/// Outcome of a record from [`parse_json_seq`]
+#[derive(Debug)]
+pub enum JSONSeqRecord {
+    /// Span of the JSON text in the record
+    Valid(Span),
+    /// The record ended before the JSON text did (or a top-level number, `true`, `false` or
+    /// `null` is not followed by whitespace, so could have been cut short). Span of the record
+    Truncated(Span),
+    Invalid(JSONParseError),
+}
+
+/// Parses a JSON text sequence, where each record starts with RS (`0x1E`) and usually ends
+/// with a line feed. The callback receives the index of the record and events as in
+/// [`crate::parse_events`]. A truncated or invalid record does not stop later records
+/// being parsed, but events from the start of it will have been emitted.
+///
+/// ```
+/// use simple_json_parser::{parse_json_seq, JSONSeqRecord, ParseOptions};
+///
+/// let source = "\u{1E}{\"level\":\"info\"}\n\u{1E}{\"level\":\n\u{1E}42\n";
+/// let records = parse_json_seq(source, |_record, _keys, _event| {}, &ParseOptions::default());
+/// assert!(matches!(records.as_slice(), [
+///     JSONSeqRecord::Valid(_),
+///     JSONSeqRecord::Truncated(_),
+///     JSONSeqRecord::Valid(_),
+/// ]));
+/// ```
+pub fn parse_json_seq<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(usize, &'b [JSONKey<'a>], JSONEvent<'a>),
+    options: &ParseOptions,
+) -> Vec<JSONSeqRecord> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+
+    for (idx, record) in on.split(RS).enumerate() {
+        let start = offset;
+        offset += record.len() + RS.len_utf8();
+        // Empty records (`RS RS`) are skipped
+        if record.trim().is_empty() {
+            continue;
+        }
+        let result = if idx == 0 {
+            // Text before the first separator
+            JSONSeqRecord::Invalid(JSONParseError {
+                at: 0,
+                reason: JSONParseErrorReason::ExpectedValue { found: None },
+                path: None,
+            })
+        } else {
+            parse_record(record, start, records.len(), &mut cb, options)
+        };
+        records.push(result);
+    }
+
+    records
+}
+
+fn parse_record<'a>(
+    record: &'a str,
+    start: usize,
+    index: usize,
+    cb: &mut impl for<'b> FnMut(usize, &'b [JSONKey<'a>], JSONEvent<'a>),
+    options: &ParseOptions,
+) -> JSONSeqRecord {
+    let record_span = Span {
+        start,
+        end: start + record.len(),
+    };
+    let mut reader = JsonReader::new(record, options);
+    let mut text: Option<Span> = None;
+    let mut scalar = false;
+
+    loop {
+        match reader.next_event() {
+            Ok(Some(event @ JSONEvent::Comment { .. })) => {
+                cb(index, reader.key_chain(), event);
+            }
+            Ok(Some(event)) => {
+                let span = reader.span();
+                if text.is_none() {
+                    scalar = matches!(event, JSONEvent::Value(_));
+                }
+                let text_start = text.map_or(span.start, |text| text.start);
+                text = Some(Span {
+                    start: text_start,
+                    end: span.end,
+                });
+                cb(index, reader.key_chain(), event);
+            }
+            Ok(None) => break,
+            Err(err) if err.at == record.len() => return JSONSeqRecord::Truncated(record_span),
+            Err(err) => {
+                return JSONSeqRecord::Invalid(JSONParseError {
+                    at: start + err.at,
+                    ..err
+                })
+            }
+        }
+    }
+
+    // Only comments
+    let Some(text) = text else {
+        return JSONSeqRecord::Invalid(JSONParseError {
+            at: record_span.end,
+            reason: JSONParseErrorReason::ExpectedValue { found: None },
+            path: None,
+        });
+    };
+    let followed_by_whitespace = record[text.end..].starts_with(char::is_whitespace);
+    let is_string = record[text.start..].starts_with(['"', '\'']);
+    if scalar && !is_string && !followed_by_whitespace {
+        JSONSeqRecord::Truncated(record_span)
+    } else {
+        JSONSeqRecord::Valid(Span {
+            start: start + text.start,
+            end: start + text.end,
+        })
+    }
+}
@@ -0,0 +1,135 @@
+//! Parsing without allocating, keeping the key chain in a buffer from the caller
+
+use super::{
+    key_content, JSONKey, JSONParseError, KeyStack, Machine, ParseOptions, ProgressTracker,
+    RootJSONValue, Step, ValueKind,
+};
+
+/// Like [`crate::parse_with_exit_signal`] but the key chain is kept in `keys` rather than a
+/// `Vec`, so parsing does not allocate (unless nested more than 256 levels deep). Objects and
+/// arrays nested deeper than `keys.len()` are a
+/// [`crate::JSONParseErrorReason::DepthLimitExceeded`] error. Returns the number of bytes
+/// parsed.
+///
+/// ```
+/// use simple_json_parser::{parse_with_key_buffer, JSONKey, ParseOptions, RootJSONValue};
+///
+/// let mut keys = [JSONKey::Index(0); 8];
+/// let mut port = None;
+/// parse_with_key_buffer(r#"{ "server": { "port": 8080 } }"#, &mut keys, |keys, value| {
+///     if let ([JSONKey::Slice("server"), JSONKey::Slice("port")], RootJSONValue::Number(value)) = (keys, value) {
+///         port = value.parse::<u16>().ok();
+///     }
+///     false
+/// }, &ParseOptions::default()).unwrap();
+/// assert_eq!(port, Some(8080));
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input or it is nested too deeply
+pub fn parse_with_key_buffer<'a>(
+    on: &'a str,
+    keys: &mut [JSONKey<'a>],
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut machine = Machine::new(options);
+    // There is at most one key for each open object or array, so the buffer cannot overflow
+    machine.max_depth = Some(
+        options
+            .max_depth
+            .map_or(keys.len(), |max| max.min(keys.len())),
+    );
+    let mut key_chain = KeyBuffer { keys, len: 0 };
+    let mut progress = ProgressTracker::new(options);
+    let mut idx = 0;
+
+    loop {
+        idx += machine.skippable(idx, &on.as_bytes()[idx..]);
+        if progress.due(idx) {
+            progress.report(idx, key_chain.as_slice())?;
+        }
+        let (step, length) = if let Some(chr) = on[idx..].chars().next() {
+            (
+                machine
+                    .push_with_keys(idx, chr, &mut key_chain)
+                    .map_err(|err| err.with_path(key_chain.as_slice()))?,
+                chr.len_utf8(),
+            )
+        } else if let Some(step) = machine
+            .finish(on.len())
+            .map_err(|err| err.with_path(key_chain.as_slice()))?
+        {
+            (step, 0)
+        } else {
+            return Ok(on.len());
+        };
+
+        match step {
+            Step::Key { start, end, quoted } => {
+                let (start, end) = key_content(start, end, quoted);
+                key_chain.push(JSONKey::Slice(&on[start..end]));
+            }
+            Step::Value {
+                kind,
+                start,
+                end,
+                retry,
+            } => {
+                let value = match kind {
+                    ValueKind::String => RootJSONValue::String(&on[(start + 1)..(end - 1)]),
+                    ValueKind::Number => {
+                        RootJSONValue::number(&on[start..end], options.typed_numbers)
+                    }
+                    ValueKind::Boolean(value) => RootJSONValue::Boolean(value),
+                    ValueKind::Null => RootJSONValue::Null,
+                    ValueKind::NonFinite => RootJSONValue::non_finite(&on[start..end]),
+                    ValueKind::EmptyObject => RootJSONValue::EmptyObject,
+                    ValueKind::EmptyArray => RootJSONValue::EmptyArray,
+                };
+                if cb(key_chain.as_slice(), value)
+                    || (options.exits_on_first_value() && machine.is_at_root())
+                {
+                    return Ok(end);
+                }
+                if retry {
+                    continue;
+                }
+            }
+            Step::EndObject { at } | Step::EndArray { at }
+                if options.exits_on_first_value() && machine.is_at_root() =>
+            {
+                return Ok(at + 1);
+            }
+            _ => {}
+        }
+
+        idx += length;
+    }
+}
+
+struct KeyBuffer<'b, K> {
+    keys: &'b mut [K],
+    len: usize,
+}
+
+impl<K> KeyBuffer<'_, K> {
+    fn as_slice(&self) -> &[K] {
+        &self.keys[..self.len]
+    }
+}
+
+impl<K> KeyStack<K> for KeyBuffer<'_, K> {
+    fn push(&mut self, key: K) {
+        self.keys[self.len] = key;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) {
+        self.len = self.len.saturating_sub(1);
+    }
+
+    fn last_mut(&mut self) -> Option<&mut K> {
+        self.keys[..self.len].last_mut()
+    }
+}
@@ -0,0 +1,337 @@
+//! Helpers for working with the key chains given to callbacks
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    ops::Deref,
+};
+
+use super::{key_chain_matches, pointer::to_pointer, JSONKey, KeyPattern};
+
+/// A key chain, either borrowed from a callback or owned. Dereferences to the slice of keys.
+/// Displays as a dotted path (`build.entries[1].input`), which [`KeyChain::matches`] accepts
+///
+/// ```
+/// use simple_json_parser::{parse, JSONKey, KeyChain};
+///
+/// let mut inputs = Vec::new();
+/// parse(r#"{ "build": { "entries": [{ "input": "a.ts" }, { "input": "b.ts" }] } }"#, |keys, value| {
+///     let keys = KeyChain::new(keys);
+///     if keys.matches("build.entries[*].input") {
+///         inputs.push((keys.to_pointer(), value));
+///     }
+/// })
+/// .unwrap();
+/// assert_eq!(inputs[1].0, "/build/entries/1/input");
+///
+/// let keys = [JSONKey::Slice("scripts"), JSONKey::Slice("build:prod"), JSONKey::Index(0)];
+/// assert_eq!(KeyChain::new(&keys).to_string(), "scripts.build:prod[0]");
+/// let keys = [JSONKey::Slice("a.b"), JSONKey::Slice("")];
+/// assert_eq!(KeyChain::new(&keys).to_string(), r#"["a.b"][""]"#);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyChain<'k, 'a> {
+    keys: Keys<'k, 'a>,
+}
+
+/// Like `Cow`, but keeps [`KeyChain`] covariant
+#[derive(Debug, Clone)]
+enum Keys<'k, 'a> {
+    Borrowed(&'k [JSONKey<'a>]),
+    Owned(Vec<JSONKey<'a>>),
+}
+
+impl<'k, 'a> KeyChain<'k, 'a> {
+    #[must_use]
+    pub fn new(keys: &'k [JSONKey<'a>]) -> Self {
+        Self {
+            keys: Keys::Borrowed(keys),
+        }
+    }
+
+    /// Parses a dotted path (`build.entries[1].input`, see [`KeyChain::matches`]) without
+    /// wildcards. Keys borrow from `path`
+    ///
+    /// ```
+    /// use simple_json_parser::{JSONKey, KeyChain};
+    ///
+    /// let chain = KeyChain::parse(r#"dependencies["@types/node"]"#).unwrap();
+    /// assert_eq!(*chain, [JSONKey::Slice("dependencies"), JSONKey::Slice("@types/node")]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `path` is not a valid path or has a wildcard
+    pub fn parse(path: &'a str) -> Result<Self, KeyChainError> {
+        let keys = path_patterns(path, false)?
+            .into_iter()
+            .map(|pattern| match pattern {
+                KeyPattern::Index(idx) => JSONKey::Index(idx),
+                KeyPattern::Key(key) => JSONKey::Slice(key),
+                KeyPattern::AnyKey | KeyPattern::AnyIndex | KeyPattern::AnySuffix => {
+                    unreachable!("wildcards are not allowed")
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(keys.into())
+    }
+
+    /// Parses a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901). Segments of
+    /// digits (without leading zeros) are taken as indexes. Keys borrow from `pointer`, so
+    /// segments with `~0` or `~1` escapes are not supported (use [`KeyChain::parse`] with a
+    /// quoted key instead)
+    ///
+    /// ```
+    /// use simple_json_parser::{JSONKey, KeyChain};
+    ///
+    /// let chain = KeyChain::from_pointer("/a/b/0").unwrap();
+    /// assert_eq!(*chain, [JSONKey::Slice("a"), JSONKey::Slice("b"), JSONKey::Index(0)]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `pointer` is not empty and does not start with `/`, or has an
+    /// escaped segment
+    pub fn from_pointer(pointer: &'a str) -> Result<Self, KeyChainError> {
+        if pointer.is_empty() {
+            return Ok(Vec::new().into());
+        }
+        let Some(rest) = pointer.strip_prefix('/') else {
+            return Err(KeyChainError {
+                at: 0,
+                reason: KeyChainErrorReason::ExpectedSlash,
+            });
+        };
+        let mut keys = Vec::new();
+        let mut at = 1;
+        for segment in rest.split('/') {
+            if let Some(offset) = segment.find('~') {
+                return Err(KeyChainError {
+                    at: at + offset,
+                    reason: KeyChainErrorReason::EscapedSegment,
+                });
+            }
+            let is_index = segment == "0"
+                || (!segment.starts_with('0')
+                    && !segment.is_empty()
+                    && segment.bytes().all(|byte| byte.is_ascii_digit()));
+            let key = match segment.parse() {
+                Ok(idx) if is_index => JSONKey::Index(idx),
+                _ => JSONKey::Slice(segment),
+            };
+            keys.push(key);
+            at += segment.len() + 1;
+        }
+        Ok(keys.into())
+    }
+
+    /// Number of keys and indexes
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.len()
+    }
+
+    #[must_use]
+    pub fn starts_with(&self, prefix: &[JSONKey<'_>]) -> bool {
+        self.len() >= prefix.len() && self.iter().zip(prefix).all(|(a, b)| a == b)
+    }
+
+    /// Whether the last segment is the key `key` (compared against the source, so without
+    /// unescaping)
+    #[must_use]
+    pub fn ends_with_key(&self, key: &str) -> bool {
+        matches!(self.last(), Some(JSONKey::Slice(last)) if *last == key)
+    }
+
+    /// The chain without the last segment
+    #[must_use]
+    pub fn parent(&self) -> Option<KeyChain<'_, 'a>> {
+        let (_, parent) = self.split_last()?;
+        Some(KeyChain::new(parent))
+    }
+
+    /// The [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) for this location
+    #[must_use]
+    pub fn to_pointer(&self) -> String {
+        to_pointer(self)
+    }
+
+    /// Whether the chain matches a dotted path such as `a.b[0]`. Keys can be quoted
+    /// (`["a.b"]`), `*` matches any key, `[*]` any index and a final `**` any remaining
+    /// segments. Returns `false` if `path` is not valid
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        path_patterns(path, true).is_ok_and(|pattern| key_chain_matches(self, &pattern))
+    }
+
+    #[must_use]
+    pub fn into_owned(self) -> KeyChain<'a, 'a> {
+        KeyChain {
+            keys: match self.keys {
+                Keys::Borrowed(keys) => Keys::Owned(keys.to_vec()),
+                Keys::Owned(keys) => Keys::Owned(keys),
+            },
+        }
+    }
+}
+
+impl fmt::Display for KeyChain<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, key) in self.iter().enumerate() {
+            match key {
+                JSONKey::Slice(key) if is_plain_key(key) => {
+                    if idx > 0 {
+                        f.write_char('.')?;
+                    }
+                    f.write_str(key)?;
+                }
+                // Keys are as in the source, so quotes in them are already escaped (apart
+                // from `"` in JSON5 single quoted keys)
+                JSONKey::Slice(key) => {
+                    let quote = if has_unescaped_quote(key) { '\'' } else { '"' };
+                    write!(f, "[{quote}{key}{quote}]")?;
+                }
+                JSONKey::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the key can be written without quotes in a dotted path
+fn is_plain_key(key: &str) -> bool {
+    !matches!(key, "" | "*" | "**") && !key.contains(['.', '[', ']', '"', '\''])
+}
+
+fn has_unescaped_quote(key: &str) -> bool {
+    let mut escaped = false;
+    key.chars().any(|chr| {
+        let quote = !escaped && chr == '"';
+        escaped = !escaped && chr == '\\';
+        quote
+    })
+}
+
+impl<'a> Deref for KeyChain<'_, 'a> {
+    type Target = [JSONKey<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        match &self.keys {
+            Keys::Borrowed(keys) => keys,
+            Keys::Owned(keys) => keys,
+        }
+    }
+}
+
+impl PartialEq for KeyChain<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for KeyChain<'_, '_> {}
+
+impl<'k, 'a> From<&'k [JSONKey<'a>]> for KeyChain<'k, 'a> {
+    fn from(keys: &'k [JSONKey<'a>]) -> Self {
+        Self::new(keys)
+    }
+}
+
+impl<'a> From<Vec<JSONKey<'a>>> for KeyChain<'_, 'a> {
+    fn from(keys: Vec<JSONKey<'a>>) -> Self {
+        Self {
+            keys: Keys::Owned(keys),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyChainErrorReason {
+    InvalidPath,
+    /// [`KeyChain::parse`] does not accept `*`, `[*]` or `**`
+    Wildcard,
+    /// Non empty pointers start with `/`
+    ExpectedSlash,
+    /// A pointer segment with `~0` or `~1`
+    EscapedSegment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChainError {
+    pub at: usize,
+    pub reason: KeyChainErrorReason,
+}
+
+impl core::error::Error for KeyChainError {}
+
+impl fmt::Display for KeyChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_fmt(format_args!(
+            "KeyChainError: {:?} at {:?}",
+            self.reason, self.at
+        ))
+    }
+}
+
+/// Segments of a dotted path
+fn path_patterns(path: &str, wildcards: bool) -> Result<Vec<KeyPattern<'_>>, KeyChainError> {
+    let mut patterns = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        let at = path.len() - rest.len();
+        let invalid = || KeyChainError {
+            at,
+            reason: KeyChainErrorReason::InvalidPath,
+        };
+        if let Some(after) = rest.strip_prefix('[') {
+            let (pattern, after) = bracket_segment(after).ok_or_else(invalid)?;
+            patterns.push(pattern);
+            rest = after;
+        } else {
+            // A leading `.` is only allowed between segments
+            if !patterns.is_empty() {
+                rest = rest.strip_prefix('.').ok_or_else(invalid)?;
+            }
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let (key, after) = rest.split_at(end);
+            let pattern = match key {
+                "" => return Err(invalid()),
+                "*" => KeyPattern::AnyKey,
+                "**" if after.is_empty() => KeyPattern::AnySuffix,
+                key => KeyPattern::Key(key),
+            };
+            patterns.push(pattern);
+            rest = after;
+        }
+        let is_wildcard = matches!(
+            patterns.last(),
+            Some(KeyPattern::AnyKey | KeyPattern::AnyIndex | KeyPattern::AnySuffix)
+        );
+        if is_wildcard && !wildcards {
+            return Err(KeyChainError {
+                at,
+                reason: KeyChainErrorReason::Wildcard,
+            });
+        }
+    }
+    Ok(patterns)
+}
+
+/// After `[`, an index, `*` or a quoted key, up to and including the `]`
+fn bracket_segment(rest: &str) -> Option<(KeyPattern<'_>, &str)> {
+    if let Some(after) = rest.strip_prefix('*') {
+        return Some((KeyPattern::AnyIndex, after.strip_prefix(']')?));
+    }
+    if let Some(quote @ ('"' | '\'')) = rest.chars().next() {
+        let content = &rest[1..];
+        let mut escaped = false;
+        let end = content.find(|chr| {
+            let end = !escaped && chr == quote;
+            escaped = !escaped && chr == '\\';
+            end
+        })?;
+        let after = content[end + 1..].strip_prefix(']')?;
+        return Some((KeyPattern::Key(&content[..end]), after));
+    }
+    let end = rest.find(']')?;
+    let index = rest[..end].parse().ok()?;
+    Some((KeyPattern::Index(index), &rest[end + 1..]))
+}
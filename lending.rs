@@ -0,0 +1,60 @@
+//! Pull parsing where the key chain is lent rather than cloned
+
+use super::{JSONKey, JSONParseError, JsonReader, RootJSONValue};
+
+/// An iterator whose items can borrow from the iterator itself, so they are only valid until
+/// the next call to [`LendingIterator::next`]
+pub trait LendingIterator {
+    type Item<'b>
+    where
+        Self: 'b;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// [`JsonReader`] as a [`LendingIterator`]. Created by [`JsonReader::lending`]
+///
+/// ```
+/// use simple_json_parser::{JsonReader, JSONKey, LendingIterator, ParseOptions, RootJSONValue};
+///
+/// let mut reader = JsonReader::new(r#"{ "a": [1, true] }"#, &ParseOptions::default()).lending();
+/// let mut found = Vec::new();
+/// while let Some(item) = reader.next() {
+///     let (keys, value) = item.unwrap();
+///     if let [JSONKey::Slice("a"), JSONKey::Index(idx)] = keys {
+///         found.push((*idx, value));
+///     }
+/// }
+/// assert_eq!(found, [(0, RootJSONValue::Number("1")), (1, RootJSONValue::Boolean(true))]);
+/// ```
+pub struct LendingReader<'a>(JsonReader<'a>);
+
+impl<'a> JsonReader<'a> {
+    /// Iterate without cloning the key chain for each value (unlike [`Iterator::next`])
+    #[must_use]
+    pub fn lending(self) -> LendingReader<'a> {
+        LendingReader(self)
+    }
+}
+
+impl<'a> LendingReader<'a> {
+    #[must_use]
+    pub fn into_inner(self) -> JsonReader<'a> {
+        self.0
+    }
+}
+
+impl<'a> LendingIterator for LendingReader<'a> {
+    type Item<'b>
+        = Result<(&'b [JSONKey<'a>], RootJSONValue<'a>), JSONParseError>
+    where
+        Self: 'b;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        match self.0.next_value() {
+            Ok(Some(value)) => Some(Ok((self.0.key_chain(), value))),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Raw tokens, without checking how they are arranged. For syntax highlighting and other
+//! tools which need to handle invalid input. See [`crate::tokenize`] for tokens from the
+//! parser, which are only given for valid input
+
+use super::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawToken {
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    /// With either quote. Includes the quotes, runs to the end of the line if there is no
+    /// closing quote
+    String(Span),
+    /// Any run of digits, letters, `.` and signs (after an exponent) starting with a digit,
+    /// `-`, `+` or `.`. So invalid numbers are still a single token
+    Number(Span),
+    /// `true`, `false`, `null` and other identifiers (such as JSON5 unquoted keys)
+    Literal(Span),
+    /// `//`, `#` (up to the line break) or `/* */` comments
+    Comment(Span),
+    /// Including a byte order mark
+    Whitespace(Span),
+    /// A character which does not start a token
+    Invalid(Span),
+}
+
+/// Splits `source` into [`RawToken`]s. Every character is in exactly one token
+///
+/// ```
+/// use simple_json_parser::lexer::{lex, RawToken};
+///
+/// let source = r#"{ "a": [1, tru] // broken"#;
+/// let mut lexer = lex(source);
+/// assert_eq!(lexer.next(), Some(RawToken::LBrace));
+/// assert_eq!(lexer.span().start, 0);
+/// let tokens = lexer.collect::<Vec<_>>();
+/// assert!(matches!(tokens[8], RawToken::Literal(span) if span.slice(source) == "tru"));
+/// assert!(matches!(tokens.last(), Some(RawToken::Comment(_))));
+/// ```
+#[must_use]
+pub fn lex(source: &str) -> Lexer<'_> {
+    Lexer {
+        source,
+        position: 0,
+        span: Span { start: 0, end: 0 },
+    }
+}
+
+/// Iterator from [`lex`]
+pub struct Lexer<'a> {
+    source: &'a str,
+    position: usize,
+    span: Span,
+}
+
+impl Lexer<'_> {
+    /// Span of the last token
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Offset of the first character in the rest of the input which does not match
+    fn end_of(&self, from: usize, mut matches: impl FnMut(char) -> bool) -> usize {
+        self.source[from..]
+            .char_indices()
+            .find(|(_, chr)| !matches(*chr))
+            .map_or(self.source.len(), |(idx, _)| from + idx)
+    }
+
+    /// After the closing quote, or before the line break if there is not one. Escaped line
+    /// breaks (JSON5 line continuations) are part of the string
+    fn end_of_string(&self, quote: char) -> usize {
+        let mut escaped = false;
+        // After an escaped `\r`, so `\n` is part of the same line continuation
+        let mut continued = false;
+        let rest = &self.source[self.position + 1..];
+        for (idx, chr) in rest.char_indices() {
+            let in_escape = escaped || (continued && chr == '\n');
+            if !in_escape {
+                if chr == quote {
+                    return self.position + 1 + idx + 1;
+                }
+                if matches!(chr, '\n' | '\r') {
+                    return self.position + 1 + idx;
+                }
+            }
+            continued = escaped && chr == '\r';
+            escaped = !escaped && chr == '\\';
+        }
+        self.source.len()
+    }
+
+    fn end_of_block_comment(&self) -> usize {
+        self.source[self.position + 2..]
+            .find("*/")
+            .map_or(self.source.len(), |idx| self.position + 2 + idx + 2)
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = RawToken;
+
+    fn next(&mut self) -> Option<RawToken> {
+        let start = self.position;
+        let mut chars = self.source[start..].chars();
+        let chr = chars.next()?;
+        let after = start + chr.len_utf8();
+
+        let (end, token): (usize, fn(Span) -> RawToken) = match chr {
+            '{' => (after, |_| RawToken::LBrace),
+            '}' => (after, |_| RawToken::RBrace),
+            '[' => (after, |_| RawToken::LBracket),
+            ']' => (after, |_| RawToken::RBracket),
+            ':' => (after, |_| RawToken::Colon),
+            ',' => (after, |_| RawToken::Comma),
+            '"' | '\'' => (self.end_of_string(chr), RawToken::String),
+            '/' => match chars.next() {
+                Some('/') => (self.end_of(after, |chr| chr != '\n'), RawToken::Comment),
+                Some('*') => (self.end_of_block_comment(), RawToken::Comment),
+                _ => (after, RawToken::Invalid),
+            },
+            '#' => (self.end_of(after, |chr| chr != '\n'), RawToken::Comment),
+            '0'..='9' | '-' | '+' | '.' => {
+                let mut last = chr;
+                let end = self.end_of(after, |chr| {
+                    let part = chr.is_ascii_alphanumeric()
+                        || chr == '.'
+                        || (matches!(chr, '+' | '-') && matches!(last, 'e' | 'E'));
+                    last = chr;
+                    part
+                });
+                (end, RawToken::Number)
+            }
+            chr if chr.is_whitespace() || chr == '\u{FEFF}' => (
+                self.end_of(after, |chr| chr.is_whitespace() || chr == '\u{FEFF}'),
+                RawToken::Whitespace,
+            ),
+            chr if chr.is_alphabetic() || matches!(chr, '$' | '_') => (
+                self.end_of(after, |chr| {
+                    chr.is_alphanumeric() || matches!(chr, '$' | '_')
+                }),
+                RawToken::Literal,
+            ),
+            _ => (after, RawToken::Invalid),
+        };
+
+        self.position = end;
+        self.span = Span { start, end };
+        Some(token(self.span))
+    }
+}
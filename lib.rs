@@ -1,196 +1,2166 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+mod builder;
+mod bytes;
+mod cancel;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "std")]
+mod channel;
+mod cst;
+mod csv;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod document;
+mod edit;
+mod escape;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "mmap")]
+mod file;
+mod flatten;
+mod format;
+mod gron;
+mod hash;
+mod interner;
+mod json_path;
+mod json_seq;
+mod key_buffer;
+mod key_chain;
+mod lending;
+pub mod lexer;
+mod lint;
+mod merge;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod multi_document;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partial;
+mod patch;
+mod pointer;
+mod progress;
+mod raw;
+mod recovery;
+mod redact;
+mod schema;
+mod skip;
+mod streaming;
+mod tokens;
+#[cfg(any(feature = "cbor", feature = "msgpack"))]
+mod transcode;
+mod tsconfig;
+mod validate;
+mod value;
+mod writer;
+
+pub use builder::ParseOptionsBuilder;
+pub use bytes::{parse_bytes, parse_bytes_lossy};
+pub use cancel::Cancellation;
+#[cfg(feature = "cbor")]
+pub use cbor::json_to_cbor;
+#[cfg(feature = "std")]
+pub use channel::{parse_to_channel, OwnedEvent};
+pub use cst::{parse_cst, CstKind, CstNode};
+pub use csv::{json_to_csv, CsvError, CsvOptions};
+pub use document::JsoncDocument;
+pub use edit::{insert, remove, set_value, EditError};
+pub use escape::{try_unescape_string_content, unescape_string_content};
+#[cfg(feature = "mmap")]
+pub use file::parse_file;
+pub use flatten::{flatten, unflatten, Flatten, PathStyle, UnflattenError};
+pub use format::{format, minify, strip_extensions, FormatOptions, JSONFormatError};
+pub use gron::gron;
+pub use hash::hash;
+pub use interner::{parse_with_interned_keys, InternedKey, KeyId, KeyInterner};
+pub use json_path::{query_json_path, JSONPath, JSONPathError, JSONPathErrorReason, PathMatch};
+pub use json_seq::{parse_json_seq, JSONSeqRecord};
+pub use key_buffer::parse_with_key_buffer;
+pub use key_chain::{KeyChain, KeyChainError, KeyChainErrorReason};
+pub use lending::{LendingIterator, LendingReader};
+pub use lint::{lint, Lint, LintKind, LintOptions};
+pub use merge::{merge, MergeStrategy};
+#[cfg(feature = "msgpack")]
+pub use msgpack::json_to_msgpack;
+pub use multi_document::parse_all;
+#[cfg(feature = "rayon")]
+pub use parallel::validate_array_parallel;
+pub use partial::{parse_partial, Completeness, Missing};
+pub use patch::{apply_patch, merge_patch, JSONPatchError};
+pub use pointer::{get_at_pointer, get_bool_at, get_f64_at, get_i64_at, get_string_at};
+pub use progress::Progress;
+use progress::ProgressTracker;
+pub use raw::{parse_with_raw_subtrees, raw_slice_at, CapturedValue};
+pub use recovery::parse_with_recovery;
+pub use redact::redact;
+pub use schema::{Schema, SchemaError, SchemaType, SchemaViolation, ViolationKind};
+#[cfg(feature = "tokio")]
+pub use streaming::parse_async;
+#[cfg(feature = "std")]
+pub use streaming::{parse_reader, JSONReadError};
+pub use streaming::{FeedResult, PushParser};
+pub use tokens::{tokenize, Token, TokenKind, Tokens};
+pub use tsconfig::{TsConfig, TsConfigError};
+pub use validate::is_valid;
+pub use value::{json_equals, parse_to_value, EqualityOptions, Value};
+#[cfg(feature = "std")]
+pub use writer::IoWriter;
+pub use writer::JSONWriter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JSONKey<'a> {
     Slice(&'a str),
     Index(usize),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum RootJSONValue<'a> {
-    String(&'a str),
-    Number(&'a str),
-    Boolean(bool),
-    Null,
+/// Writes the key as in the source (so without unescaping) or the index
+impl core::fmt::Display for JSONKey<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JSONKey::Slice(key) => f.write_str(key),
+            JSONKey::Index(idx) => write!(f, "{idx}"),
+        }
+    }
+}
+
+/// Segment of a pattern for [`key_chain_matches`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyPattern<'a> {
+    /// A key equal to this (compared against the source, so without unescaping)
+    Key(&'a str),
+    Index(usize),
+    /// Any object key
+    AnyKey,
+    /// Any array index
+    AnyIndex,
+    /// Any remaining segments (including none). Should be the last segment
+    AnySuffix,
+}
+
+/// Whether a key chain matches `pattern`.
+///
+/// ```
+/// use simple_json_parser::{key_chain_matches, JSONKey, KeyPattern};
+///
+/// let keys = [JSONKey::Slice("dependencies"), JSONKey::Slice("simple-json-parser")];
+/// assert!(key_chain_matches(&keys, &[KeyPattern::Key("dependencies"), KeyPattern::AnyKey]));
+/// assert!(key_chain_matches(&keys, &[KeyPattern::Key("dependencies"), KeyPattern::AnySuffix]));
+/// assert!(!key_chain_matches(&keys, &[KeyPattern::Key("dependencies"), KeyPattern::AnyIndex]));
+/// ```
+#[must_use]
+pub fn key_chain_matches(keys: &[JSONKey<'_>], pattern: &[KeyPattern<'_>]) -> bool {
+    let mut keys = keys.iter();
+    for segment in pattern {
+        if let KeyPattern::AnySuffix = segment {
+            return true;
+        }
+        let matches = match (segment, keys.next()) {
+            (KeyPattern::Key(expected), Some(JSONKey::Slice(key))) => expected == key,
+            (KeyPattern::Index(expected), Some(JSONKey::Index(idx))) => expected == idx,
+            (KeyPattern::AnyKey, Some(JSONKey::Slice(_)))
+            | (KeyPattern::AnyIndex, Some(JSONKey::Index(_))) => true,
+            _ => false,
+        };
+        if !matches {
+            return false;
+        }
+    }
+    keys.next().is_none()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RootJSONValue<'a> {
+    String(&'a str),
+    /// The source of the number. With [`ParseOptions::typed_numbers`], numbers are given as
+    /// [`RootJSONValue::Integer`], [`RootJSONValue::Float`] or [`RootJSONValue::BigNumber`]
+    /// instead
+    Number(&'a str),
+    /// A number without a fraction or exponent which fits in an `i64`
+    Integer(i64),
+    /// A number with a fraction or exponent. Also JSON5 `Infinity` and `NaN`, unless
+    /// [`ParseOptions::allow_non_finite_numbers`] is set
+    Float(f64),
+    /// The source of an integer which does not fit in an `i64` or a number which is too
+    /// large for an `f64`
+    BigNumber(&'a str),
+    /// `NaN`, `Infinity` or `-Infinity`, only with [`ParseOptions::allow_non_finite_numbers`]
+    NonFinite(f64),
+    Boolean(bool),
+    Null,
+    /// `{}`, only with [`ParseOptions::empty_containers`]
+    EmptyObject,
+    /// `[]`, only with [`ParseOptions::empty_containers`]
+    EmptyArray,
+}
+
+impl<'a> RootJSONValue<'a> {
+    fn number(source: &'a str, typed: bool) -> Self {
+        if !typed {
+            return RootJSONValue::Number(source);
+        }
+        let (negative, digits) = match source.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, source.strip_prefix('+').unwrap_or(source)),
+        };
+
+        if let Some(hex) = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            let value = i64::from_str_radix(hex, 16).ok().and_then(|value| {
+                if negative {
+                    value.checked_neg()
+                } else {
+                    Some(value)
+                }
+            });
+            return value.map_or(RootJSONValue::BigNumber(source), RootJSONValue::Integer);
+        }
+        if digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            // Parsing with the sign so that `i64::MIN` fits
+            let value = source.strip_prefix('+').unwrap_or(source).parse();
+            return value.map_or(RootJSONValue::BigNumber(source), RootJSONValue::Integer);
+        }
+        match source.parse::<f64>() {
+            Ok(value) if value.is_finite() || !digits.starts_with(char::is_numeric) => {
+                RootJSONValue::Float(value)
+            }
+            _ => RootJSONValue::BigNumber(source),
+        }
+    }
+
+    /// From the source of a [`ValueKind::NonFinite`]
+    fn non_finite(source: &str) -> Self {
+        RootJSONValue::NonFinite(if source.ends_with("NaN") {
+            f64::NAN
+        } else if source.starts_with('-') {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        })
+    }
+}
+
+/// How `NaN`, `Infinity` and `-Infinity` are written in JSON5, JavaScript and Python
+pub(crate) fn non_finite_literal(value: f64) -> &'static str {
+    if value.is_nan() {
+        "NaN"
+    } else if value.is_sign_negative() {
+        "-Infinity"
+    } else {
+        "Infinity"
+    }
+}
+
+/// Byte offsets into the source, `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The source text covered by this span
+    #[must_use]
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+}
+
+/// Emitted by [`parse_events`] and [`JsonReader::next_event`]
+#[derive(Debug, PartialEq)]
+pub enum JSONEvent<'a> {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Key(&'a str),
+    Value(RootJSONValue<'a>),
+    /// Only when [`ParseOptions::allow_comments`] is enabled. `text` excludes the `//`,
+    /// `#` or `/* */` and `span` includes them
+    Comment {
+        text: &'a str,
+        style: CommentStyle,
+        span: Span,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `// ...`
+    Line,
+    /// `/* ... */`
+    Block,
+    /// `# ...`
+    Hash,
+}
+
+/// For the `Expected*` reasons, `found` is the character found instead or `None` at the end of
+/// the input. More reasons may be added, so matches need a wildcard arm
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JSONParseErrorReason {
+    ExpectedColon {
+        found: Option<char>,
+    },
+    ExpectedEndOfValue {
+        found: Option<char>,
+    },
+    /// A closing bracket which does not match the open object or array
+    ExpectedBracket {
+        found: Option<char>,
+    },
+    ExpectedTrueFalseNull {
+        found: Option<char>,
+    },
+    ExpectedKey {
+        found: Option<char>,
+    },
+    ExpectedValue {
+        found: Option<char>,
+    },
+    /// An unterminated `/* */` comment
+    ExpectedEndOfMultilineComment,
+    /// No longer produced, replaced by [`JSONParseErrorReason::UnterminatedString`]. Kept so
+    /// that codes do not change
+    ExpectedQuote,
+    /// Only from byte based parsing
+    InvalidUTF8,
+    /// Number does not follow the JSON number grammar (for example `01`, `1.` or `-`)
+    InvalidNumber,
+    /// From [`try_unescape_string_content`]
+    InvalidEscape,
+    /// More nested objects and arrays than [`ParseOptions::max_depth`]
+    DepthLimitExceeded,
+    /// A string, number, boolean or null as the document with
+    /// [`ParseOptions::allow_scalar_root`] disabled
+    ExpectedObjectOrArray,
+    /// An unescaped character below `U+0020` in a string or key, with
+    /// [`ParseOptions::allow_control_characters`] disabled
+    ControlCharacterInString,
+    /// A string or key longer than [`Limits::max_string_len`]
+    StringTooLong,
+    /// An object with more keys than [`Limits::max_keys_per_object`]
+    TooManyKeys,
+    /// More values than [`Limits::max_total_values`]
+    TooManyValues,
+    /// A `//` or `/* */` comment when only the other style is allowed
+    DisallowedComment,
+    /// No longer produced, replaced by [`JSONParseErrorReason::UnexpectedTrailingContent`].
+    /// Kept so that codes do not change
+    ExpectedEndOfInput {
+        found: Option<char>,
+    },
+    /// The check of [`ParseOptions::cancellation`] returned `true`
+    Cancelled,
+    /// More input than [`Limits::max_bytes`]
+    InputTooLarge,
+    /// Still parsing at [`Limits::deadline`]
+    DeadlineExceeded,
+    /// A `\u` escape of half of a surrogate pair without the other half, with
+    /// [`ParseOptions::reject_lone_surrogates`]. At the backslash of the escape
+    LoneSurrogate,
+    /// The input ended inside a string or key. At the end of the input
+    UnterminatedString,
+    /// Content after the root value, with [`ParseOptions::require_end_of_input`]
+    UnexpectedTrailingContent {
+        found: char,
+    },
+    /// The input ended inside an object or array. At the end of the input
+    UnexpectedEndOfInput,
+}
+
+impl JSONParseErrorReason {
+    /// The character found in place of what was expected, `None` at the end of the input or
+    /// for reasons which do not have a `found` field
+    #[must_use]
+    pub fn found(&self) -> Option<char> {
+        match self {
+            Self::ExpectedColon { found }
+            | Self::ExpectedEndOfValue { found }
+            | Self::ExpectedBracket { found }
+            | Self::ExpectedTrueFalseNull { found }
+            | Self::ExpectedKey { found }
+            | Self::ExpectedValue { found }
+            | Self::ExpectedEndOfInput { found } => *found,
+            Self::UnexpectedTrailingContent { found } => Some(*found),
+            _ => None,
+        }
+    }
+
+    /// Number for the reason, starting from one. Numbers are stable: they are not reused or
+    /// changed when reasons are added. Also used as `SjpError::reason` by the C API
+    ///
+    /// ```
+    /// use simple_json_parser::{parse, JSONParseErrorReason};
+    ///
+    /// let err = parse("[1, 2}", |_, _| {}).unwrap_err();
+    /// assert_eq!(err.reason.code(), 3);
+    /// assert_eq!(err.reason.name(), "ExpectedBracket");
+    /// ```
+    #[must_use]
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::ExpectedColon { .. } => 1,
+            Self::ExpectedEndOfValue { .. } => 2,
+            Self::ExpectedBracket { .. } => 3,
+            Self::ExpectedTrueFalseNull { .. } => 4,
+            Self::ExpectedKey { .. } => 5,
+            Self::ExpectedValue { .. } => 6,
+            Self::ExpectedEndOfMultilineComment => 7,
+            Self::ExpectedQuote => 8,
+            Self::InvalidUTF8 => 9,
+            Self::InvalidNumber => 10,
+            Self::InvalidEscape => 11,
+            Self::DepthLimitExceeded => 12,
+            Self::ExpectedObjectOrArray => 13,
+            Self::ControlCharacterInString => 14,
+            Self::StringTooLong => 15,
+            Self::TooManyKeys => 16,
+            Self::TooManyValues => 17,
+            Self::DisallowedComment => 18,
+            Self::ExpectedEndOfInput { .. } => 19,
+            Self::Cancelled => 20,
+            Self::InputTooLarge => 21,
+            Self::DeadlineExceeded => 22,
+            Self::LoneSurrogate => 23,
+            Self::UnterminatedString => 24,
+            Self::UnexpectedTrailingContent { .. } => 25,
+            Self::UnexpectedEndOfInput => 26,
+        }
+    }
+
+    /// Name of the variant, which is also stable
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ExpectedColon { .. } => "ExpectedColon",
+            Self::ExpectedEndOfValue { .. } => "ExpectedEndOfValue",
+            Self::ExpectedBracket { .. } => "ExpectedBracket",
+            Self::ExpectedTrueFalseNull { .. } => "ExpectedTrueFalseNull",
+            Self::ExpectedKey { .. } => "ExpectedKey",
+            Self::ExpectedValue { .. } => "ExpectedValue",
+            Self::ExpectedEndOfMultilineComment => "ExpectedEndOfMultilineComment",
+            Self::ExpectedQuote => "ExpectedQuote",
+            Self::InvalidUTF8 => "InvalidUTF8",
+            Self::InvalidNumber => "InvalidNumber",
+            Self::InvalidEscape => "InvalidEscape",
+            Self::DepthLimitExceeded => "DepthLimitExceeded",
+            Self::ExpectedObjectOrArray => "ExpectedObjectOrArray",
+            Self::ControlCharacterInString => "ControlCharacterInString",
+            Self::StringTooLong => "StringTooLong",
+            Self::TooManyKeys => "TooManyKeys",
+            Self::TooManyValues => "TooManyValues",
+            Self::DisallowedComment => "DisallowedComment",
+            Self::ExpectedEndOfInput { .. } => "ExpectedEndOfInput",
+            Self::Cancelled => "Cancelled",
+            Self::InputTooLarge => "InputTooLarge",
+            Self::DeadlineExceeded => "DeadlineExceeded",
+            Self::LoneSurrogate => "LoneSurrogate",
+            Self::UnterminatedString => "UnterminatedString",
+            Self::UnexpectedTrailingContent { .. } => "UnexpectedTrailingContent",
+            Self::UnexpectedEndOfInput => "UnexpectedEndOfInput",
+        }
+    }
+
+    /// Short lowercase description, used by [`JSONParseError::display_with_source`]
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ExpectedColon { .. } => "expected ':'",
+            Self::ExpectedEndOfValue { .. } => "expected ',' or a closing bracket",
+            Self::ExpectedBracket { .. } => "expected a closing bracket",
+            Self::ExpectedTrueFalseNull { .. } => "expected true, false or null",
+            Self::ExpectedKey { .. } => "expected a key",
+            Self::ExpectedValue { .. } => "expected a value",
+            Self::ExpectedEndOfMultilineComment => "unclosed comment",
+            Self::InvalidUTF8 => "invalid UTF-8",
+            Self::InvalidNumber => "invalid number",
+            Self::InvalidEscape => "invalid escape",
+            Self::DepthLimitExceeded => "nested too deeply",
+            Self::ExpectedObjectOrArray => "expected an object or array",
+            Self::ControlCharacterInString => "control character in string",
+            Self::StringTooLong => "string is too long",
+            Self::TooManyKeys => "object has too many keys",
+            Self::TooManyValues => "too many values",
+            Self::DisallowedComment => "comment style is not allowed",
+            Self::ExpectedEndOfInput { .. } => "expected end of input",
+            Self::Cancelled => "parsing was cancelled",
+            Self::InputTooLarge => "input is too large",
+            Self::DeadlineExceeded => "parsing took too long",
+            Self::LoneSurrogate => "lone surrogate",
+            Self::ExpectedQuote | Self::UnterminatedString => "unclosed string",
+            Self::UnexpectedTrailingContent { .. } => "unexpected content after the value",
+            Self::UnexpectedEndOfInput => "unclosed object or array",
+        }
+    }
+}
+
+/// The name of the reason, followed by what was found for the reasons with a `found` field
+/// (`ExpectedColon, found '='`)
+impl core::fmt::Display for JSONParseErrorReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = self.name();
+        match self {
+            Self::ExpectedColon { found }
+            | Self::ExpectedEndOfValue { found }
+            | Self::ExpectedBracket { found }
+            | Self::ExpectedTrueFalseNull { found }
+            | Self::ExpectedKey { found }
+            | Self::ExpectedValue { found }
+            | Self::ExpectedEndOfInput { found } => match found {
+                Some(found) => write!(f, "{name}, found {found:?}"),
+                None => write!(f, "{name}, found end of input"),
+            },
+            Self::UnexpectedTrailingContent { found } => write!(f, "{name}, found {found:?}"),
+            _ => f.write_str(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JSONParseError {
+    pub at: usize,
+    pub reason: JSONParseErrorReason,
+    /// JSON Pointer of the key chain where the error occurred (`""` for the root), if it is
+    /// known. Displayed before the offset
+    pub path: Option<String>,
+}
+
+/// One based line and column (in characters) of a position in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl JSONParseError {
+    /// `source` must be the input that was parsed
+    #[must_use]
+    pub fn location(&self, source: &str) -> Location {
+        let before = &source[..self.line_start(source)];
+        let line = before.matches('\n').count() + 1;
+        let column = source[before.len()..self.at.min(source.len())]
+            .chars()
+            .count()
+            + 1;
+        Location { line, column }
+    }
+
+    /// Renders the line containing the error with a caret under the position and a short
+    /// label. `source` must be the input that was parsed
+    ///
+    /// ```
+    /// use simple_json_parser::parse;
+    ///
+    /// let source = "{\n  \"a\": 1,\n  \"b\" = 2\n}";
+    /// let err = parse(source, |_, _| {}).unwrap_err();
+    /// assert_eq!(
+    ///     err.display_with_source(source).to_string(),
+    ///     "error: ExpectedColon, found '=' at /b\n --> 3:7\n  |\n3 |   \"b\" = 2\n  |       ^ expected ':'"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn display_with_source<'a>(&'a self, source: &'a str) -> SourceSnippet<'a> {
+        SourceSnippet {
+            error: self,
+            source,
+        }
+    }
+
+    /// The text of the line containing the error (without the line ending)
+    #[must_use]
+    pub fn line<'a>(&self, source: &'a str) -> &'a str {
+        let rest = &source[self.line_start(source)..];
+        let line = rest.split('\n').next().unwrap_or_default();
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    /// Sets [`JSONParseError::path`] to the key chain where the error occurred
+    #[cold]
+    pub(crate) fn with_path(mut self, keys: &[JSONKey<'_>]) -> Self {
+        self.path = Some(pointer::to_pointer(keys));
+        self
+    }
+
+    fn line_start(&self, source: &str) -> usize {
+        source[..self.at.min(source.len())]
+            .rfind('\n')
+            .map_or(0, |idx| idx + 1)
+    }
+}
+
+impl core::error::Error for JSONParseError {}
+
+/// Returned by [`JSONParseError::display_with_source`]
+pub struct SourceSnippet<'a> {
+    error: &'a JSONParseError,
+    source: &'a str,
+}
+
+impl core::fmt::Display for SourceSnippet<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self { error, source } = self;
+        let Location { line, column } = error.location(source);
+        let text = error.line(source);
+        let gutter = line.to_string().len();
+
+        write!(f, "error: {}", error.reason)?;
+        if let Some(path) = error.path.as_deref().filter(|path| !path.is_empty()) {
+            write!(f, " at {path}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:gutter$}--> {line}:{column}", "")?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(f, "{:gutter$} | ", "")?;
+        // Keep tabs so the caret lines up
+        for chr in text.chars().take(column - 1) {
+            f.write_str(if chr == '\t' { "\t" } else { " " })?;
+        }
+        write!(f, "^ {}", error.reason.label())
+    }
+}
+
+impl core::fmt::Display for JSONParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self.path.as_deref() {
+            Some(path) if !path.is_empty() => f.write_fmt(format_args!(
+                "JSONParseError: {} at {path} (offset {})",
+                self.reason, self.at
+            )),
+            _ => f.write_fmt(format_args!(
+                "JSONParseError: {} at {:?}",
+                self.reason, self.at
+            )),
+        }
+    }
+}
+
+/// Options for parsing. Build with [`ParseOptions::builder`] or start from a preset:
+/// [`ParseOptions::DEFAULT`], [`ParseOptions::STRICT_JSON`], [`ParseOptions::JSONC`] or
+/// [`ParseOptions::JSON5`]. Fields can be changed directly, but
+/// as more may be added the struct can not be created with a literal outside of this crate
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Returns once the first (root) value has been parsed, without looking at the rest of
+    /// the input
+    pub exit_on_first_value: bool,
+    /// Errors with [`JSONParseErrorReason::UnexpectedTrailingContent`] if anything other than
+    /// whitespace and comments follows the root value, and with
+    /// [`JSONParseErrorReason::ExpectedValue`] if there is no root value (the input is empty
+    /// or only whitespace and comments). Takes precedence over
+    /// [`ParseOptions::exit_on_first_value`], so the whole input is read
+    pub require_end_of_input: bool,
+    /// Allows `//`, `/* */` and `#` comments. Styles can be turned off separately with
+    /// [`ParseOptions::allow_line_comments`], [`ParseOptions::allow_block_comments`] and
+    /// [`ParseOptions::allow_hash_comments`]
+    pub allow_comments: bool,
+    /// Maximum number of nested objects and arrays. Set this when parsing untrusted input,
+    /// as the key chain grows with nesting
+    pub max_depth: Option<usize>,
+    /// Allows a comma after the last item of an object or array (`[1, 2,]`)
+    pub allow_trailing_commas: bool,
+    /// Accepts [JSON5](https://json5.org/) syntax: unquoted keys, single quoted strings,
+    /// hexadecimal numbers, a leading `+` and `Infinity` and `NaN`. Like other numbers
+    /// `Infinity` and `NaN` are given as [`RootJSONValue::Number`] (or
+    /// [`RootJSONValue::Float`] with [`ParseOptions::typed_numbers`]), or as
+    /// [`RootJSONValue::NonFinite`] with [`ParseOptions::allow_non_finite_numbers`]. See
+    /// [`ParseOptions::json5`]
+    pub json5: bool,
+    /// Accepts hexadecimal numbers (`0xFF`), a leading `+`, a leading or trailing decimal
+    /// point (`.5`, `5.`) and `_` between digits (`1_000`), as written in hand edited
+    /// configuration. Numbers are given as their source, so with
+    /// [`ParseOptions::typed_numbers`] ones with `_` are [`RootJSONValue::BigNumber`]
+    pub lenient_numbers: bool,
+    /// Accepts `NaN`, `Infinity` and `-Infinity`, which Python's `json.dumps` writes, giving
+    /// them as [`RootJSONValue::NonFinite`]. Also changes how they are given with
+    /// [`ParseOptions::json5`]
+    pub allow_non_finite_numbers: bool,
+    /// Allows the document to be a string, number, boolean or null (which has an empty key
+    /// chain). Disable to require an object or array
+    pub allow_scalar_root: bool,
+    /// Gives numbers as [`RootJSONValue::Integer`], [`RootJSONValue::Float`] or
+    /// [`RootJSONValue::BigNumber`] rather than [`RootJSONValue::Number`]
+    pub typed_numbers: bool,
+    /// Passes objects and arrays with nothing in them to value callbacks, as
+    /// [`RootJSONValue::EmptyObject`] and [`RootJSONValue::EmptyArray`]. Otherwise they
+    /// do not produce any values
+    pub empty_containers: bool,
+    /// Called as parsing goes through the input, for example to show a progress bar for a
+    /// large file. `None` by default
+    pub progress: Option<Progress>,
+    /// Checked as parsing goes through the input, to stop it from elsewhere. `None` by
+    /// default
+    pub cancellation: Option<Cancellation>,
+    /// Allows unescaped control characters (below `U+0020`, such as tabs and new lines) in
+    /// strings and keys. [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259#section-7)
+    /// does not, so disable this for strict parsing
+    pub allow_control_characters: bool,
+    /// Skips a byte order mark (`U+FEFF`) at the start of the input, which some Windows
+    /// tools write
+    pub allow_bom: bool,
+    /// Allows `//` comments, if [`ParseOptions::allow_comments`] is enabled
+    pub allow_line_comments: bool,
+    /// Allows `/* */` comments, if [`ParseOptions::allow_comments`] is enabled
+    pub allow_block_comments: bool,
+    /// Allows `#` comments, if [`ParseOptions::allow_comments`] is enabled. Disable for
+    /// strict JSON5 or JSONC, which do not have them
+    pub allow_hash_comments: bool,
+    /// Errors with [`JSONParseErrorReason::LoneSurrogate`] for a `\uD800`-`\uDBFF` escape
+    /// not followed by a `\uDC00`-`\uDFFF` escape, or a `\uDC00`-`\uDFFF` escape on its
+    /// own. These can not be decoded to valid UTF-8
+    pub reject_lone_surrogates: bool,
+    /// Limits on the size of the input, beyond [`ParseOptions::max_depth`]. None are set by
+    /// default
+    pub limits: Limits,
+}
+
+/// Guards for parsing untrusted input, each is an error when exceeded
+///
+/// ```
+/// use simple_json_parser::{parse_with_exit_signal, JSONParseErrorReason, Limits, ParseOptions};
+///
+/// let options = ParseOptions::builder()
+///     .limits(Limits { max_keys_per_object: Some(2), ..Limits::default() })
+///     .build();
+/// let result = parse_with_exit_signal(r#"{ "a": 1, "b": 2, "c": 3 }"#, |_, _| false, &options);
+/// let err = result.unwrap_err();
+/// assert!(matches!(err.reason, JSONParseErrorReason::TooManyKeys));
+/// assert_eq!(err.at, 18);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum length in bytes of strings and keys, as in the source (so escapes are not
+    /// decoded)
+    pub max_string_len: Option<usize>,
+    pub max_keys_per_object: Option<usize>,
+    /// Maximum number of values in the input, including objects and arrays
+    pub max_total_values: Option<usize>,
+    /// Maximum number of bytes of input to read
+    pub max_bytes: Option<usize>,
+    /// Time to stop parsing by, checked every [`Limits::DEADLINE_EVERY`] bytes
+    #[cfg(feature = "std")]
+    pub deadline: Option<std::time::Instant>,
+}
+
+impl Limits {
+    /// Bytes between checks of [`Limits::deadline`]
+    pub const DEADLINE_EVERY: usize = 4096;
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ParseOptions {
+    /// Comments (of all styles), scalar roots, unescaped control characters and a leading
+    /// byte order mark are allowed. Everything else is off
+    pub const DEFAULT: Self = Self {
+        exit_on_first_value: false,
+        require_end_of_input: false,
+        allow_comments: true,
+        max_depth: None,
+        allow_trailing_commas: false,
+        json5: false,
+        lenient_numbers: false,
+        allow_non_finite_numbers: false,
+        allow_scalar_root: true,
+        typed_numbers: false,
+        empty_containers: false,
+        progress: None,
+        cancellation: None,
+        allow_control_characters: true,
+        allow_bom: true,
+        allow_line_comments: true,
+        allow_block_comments: true,
+        allow_hash_comments: true,
+        reject_lone_surrogates: false,
+        limits: Limits {
+            max_string_len: None,
+            max_keys_per_object: None,
+            max_total_values: None,
+            max_bytes: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+        },
+    };
+
+    /// [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259) JSON only: no comments,
+    /// trailing commas, unescaped control characters, byte order mark, lone surrogates or
+    /// content after the root value. Empty input is an error as there must be exactly one
+    /// value
+    pub const STRICT_JSON: Self = Self {
+        require_end_of_input: true,
+        allow_comments: false,
+        allow_control_characters: false,
+        allow_bom: false,
+        reject_lone_surrogates: true,
+        ..Self::DEFAULT
+    };
+
+    /// JSON with `//` and `/* */` comments and trailing commas, as used by `tsconfig.json`
+    /// and VS Code settings
+    pub const JSONC: Self = Self {
+        allow_trailing_commas: true,
+        allow_hash_comments: false,
+        ..Self::DEFAULT
+    };
+
+    /// [`ParseOptions::DEFAULT`] with [`ParseOptions::json5`] syntax and trailing commas
+    pub const JSON5: Self = Self {
+        allow_trailing_commas: true,
+        json5: true,
+        ..Self::DEFAULT
+    };
+
+    /// [`ParseOptions::exit_on_first_value`], unless the rest of the input is checked
+    fn exits_on_first_value(&self) -> bool {
+        self.exit_on_first_value && !self.require_end_of_input
+    }
+
+    /// Same as [`ParseOptions::STRICT_JSON`]
+    #[must_use]
+    pub fn strict_json() -> Self {
+        Self::STRICT_JSON
+    }
+
+    /// Same as [`ParseOptions::JSONC`]
+    #[must_use]
+    pub fn jsonc() -> Self {
+        Self::JSONC
+    }
+
+    /// Options for parsing JSON5, which also allows comments and trailing commas. Same as
+    /// [`ParseOptions::JSON5`]
+    #[must_use]
+    pub fn json5() -> Self {
+        Self::JSON5
+    }
+}
+
+/// If you want to return early (break on an exception in the callback) or
+/// more configuration use [`parse_with_exit_signal`]
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+) -> Result<usize, JSONParseError> {
+    parse_with_exit_signal(
+        on,
+        |k, v| {
+            cb(k, v);
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .map(|outcome| outcome.bytes_consumed)
+}
+
+/// Returns where and why parsing stopped.
+///
+/// ```
+/// use simple_json_parser::{parse_with_exit_signal, EndState, JSONKey, ParseOptions};
+///
+/// let source = r#"{ "name": "ezno", "version": "0.1.0", "license": "MIT" }"#;
+/// let outcome = parse_with_exit_signal(
+///     source,
+///     |keys, _value| matches!(keys, [JSONKey::Slice("version")]),
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(outcome.bytes_consumed, 36);
+/// assert_eq!(outcome.remaining, r#", "license": "MIT" }"#);
+/// assert_eq!(outcome.values_emitted, 2);
+/// assert!(outcome.exited_early);
+/// assert_eq!(outcome.end_state, EndState::Callback);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_exit_signal<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
+    options: &ParseOptions,
+) -> Result<ParseOutcome<'a>, JSONParseError> {
+    parse_with_outcome(on, |keys, value, _span| cb(keys, value), options)
+}
+
+/// Returned by [`parse_with_exit_signal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOutcome<'a> {
+    /// Offset parsing stopped at. The length of the input unless it exited early
+    pub bytes_consumed: usize,
+    /// The input after [`ParseOutcome::bytes_consumed`]. Empty unless it exited early
+    pub remaining: &'a str,
+    /// Number of times the callback was called
+    pub values_emitted: usize,
+    /// Whether parsing stopped before the end of the input
+    pub exited_early: bool,
+    pub end_state: EndState,
+}
+
+/// Why parsing stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndState {
+    /// All of the input was parsed
+    EndOfInput,
+    /// The callback returned `true`
+    Callback,
+    /// The root value ended, with [`ParseOptions::exit_on_first_value`]
+    FirstValue,
+}
+
+/// Like [`parse_with_exit_signal`] but the callback also receives the [`Span`] of the value
+/// in the source. For strings the span includes the quotes. Returns the number of bytes
+/// parsed.
+///
+/// Spans can be kept to slice values out later, without parsing again
+///
+/// ```
+/// use simple_json_parser::{parse_with_spans, JSONKey, ParseOptions};
+///
+/// let source = r#"{ "id": 7, "body": "long text", "tags": ["a"] }"#;
+/// let mut body = None;
+/// parse_with_spans(
+///     source,
+///     |keys, _value, span| {
+///         if let [JSONKey::Slice("body")] = keys {
+///             body = Some(span);
+///         }
+///         false
+///     },
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// let body = body.unwrap();
+/// assert_eq!(body.start, 19);
+/// assert_eq!(body.slice(source), "\"long text\"");
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_spans<'a>(
+    on: &'a str,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>, Span) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    parse_with_outcome(on, cb, options).map(|outcome| outcome.bytes_consumed)
+}
+
+fn parse_with_outcome<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>, Span) -> bool,
+    options: &ParseOptions,
+) -> Result<ParseOutcome<'a>, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    let mut values_emitted = 0;
+    let outcome = |bytes_consumed, values_emitted, end_state| ParseOutcome {
+        bytes_consumed,
+        remaining: &on[bytes_consumed..],
+        values_emitted,
+        exited_early: end_state != EndState::EndOfInput,
+        end_state,
+    };
+
+    while let Some(step) = reader.next_step()? {
+        match step {
+            Step::Value {
+                kind, start, end, ..
+            } => {
+                let value = reader.value(kind, start, end);
+                values_emitted += 1;
+                if cb(&reader.key_chain, value, Span { start, end }) {
+                    return Ok(outcome(end, values_emitted, EndState::Callback));
+                }
+                if options.exits_on_first_value() && reader.machine.is_at_root() {
+                    return Ok(outcome(end, values_emitted, EndState::FirstValue));
+                }
+            }
+            Step::EndObject { at } | Step::EndArray { at }
+                if options.exits_on_first_value() && reader.machine.is_at_root() =>
+            {
+                return Ok(outcome(at + 1, values_emitted, EndState::FirstValue));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(outcome(on.len(), values_emitted, EndState::EndOfInput))
+}
+
+/// Returned from the callback of [`parse_with_signal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Continue,
+    /// Skip the value of a key, the object or array just started or otherwise the rest of
+    /// the current object or array. See [`JsonReader::skip`]
+    SkipSubtree,
+    /// Stop parsing
+    Stop,
+}
+
+/// Like [`parse_events`] but the callback decides whether to continue, skip part of the
+/// input or stop. Returns the number of bytes parsed.
+///
+/// ```
+/// use simple_json_parser::{parse_with_signal, JSONEvent, ParseOptions, Signal};
+///
+/// let source = r#"{ "name": "x", "dependencies": { "a": "1.0" }, "version": "1.0" }"#;
+/// let mut events = 0;
+/// parse_with_signal(
+///     source,
+///     |_keys, event| {
+///         events += 1;
+///         match event {
+///             JSONEvent::Key("dependencies") => Signal::SkipSubtree,
+///             JSONEvent::Key("version") => Signal::Stop,
+///             _ => Signal::Continue,
+///         }
+///     },
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// // `{`, `name`, `"x"`, `dependencies` and `version`
+/// assert_eq!(events, 5);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_signal<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], JSONEvent<'a>) -> Signal,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    while let Some(event) = reader.next_event()? {
+        match cb(&reader.key_chain, event) {
+            Signal::Continue => {}
+            Signal::SkipSubtree => reader.skip_subtree()?,
+            Signal::Stop => return Ok(reader.span.end),
+        }
+    }
+    Ok(on.len())
+}
+
+/// Parses concatenated documents (`{"a":1} {"b":2} 3`) separated by whitespace (or
+/// comments). The callback receives the index of the document the value is in. Returns the
+/// [`Span`] of each document. [`ParseOptions::exit_on_first_value`] is not used here
+///
+/// ```
+/// use simple_json_parser::{parse_many, ParseOptions, Span};
+///
+/// let source = r#"{"a":1} {"b":2} 3"#;
+/// let mut values = Vec::new();
+/// let documents = parse_many(
+///     source,
+///     |document, _keys, value| values.push((document, value)),
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(documents.len(), 3);
+/// assert_eq!(documents[1].slice(source), r#"{"b":2}"#);
+/// assert_eq!(values.len(), 3);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_many<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(usize, &'b [JSONKey<'a>], RootJSONValue<'a>),
+    options: &ParseOptions,
+) -> Result<Vec<Span>, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    let mut documents = Vec::new();
+    let mut document_start = None;
+
+    while let Some(step) = reader.next_step()? {
+        if document_start.is_none() && !matches!(step, Step::Comment { .. }) {
+            document_start = step.span().map(|span| span.start);
+        }
+        if let Step::Value {
+            kind, start, end, ..
+        } = step
+        {
+            let value = reader.value(kind, start, end);
+            cb(documents.len(), &reader.key_chain, value);
+        }
+        let ends_value = matches!(
+            step,
+            Step::Value { .. } | Step::EndObject { .. } | Step::EndArray { .. }
+        );
+        if ends_value && reader.machine.is_at_root() {
+            if let Some(start) = document_start.take() {
+                documents.push(Span {
+                    start,
+                    end: reader.span.end,
+                });
+            }
+            // Allow the next document
+            reader.machine.state = State::ExpectingValue;
+        }
+    }
+
+    Ok(documents)
+}
+
+/// Like [`parse`] but also emits events for the start and end of objects and arrays
+/// and for keys. On [`JSONEvent::Key`] the key chain includes the key, on start and end
+/// events the key chain is the path to the container.
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_events<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], JSONEvent<'a>),
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    while let Some(event) = reader.next_event()? {
+        cb(&reader.key_chain, event);
+    }
+    Ok(on.len())
+}
+
+/// Calls `cb` with the key chain (ending with the key) for each key in an object, as the key
+/// is found so before its value is parsed. Values are not passed on, but are still validated.
+///
+/// ```
+/// use simple_json_parser::{parse_keys, JSONKey, ParseOptions};
+///
+/// let source = r#"{ "scripts": { "build": "tsc", "clean": {} }, "version": "1.0.0" }"#;
+/// let mut scripts = Vec::new();
+/// parse_keys(
+///     source,
+///     |keys| {
+///         if let [JSONKey::Slice("scripts"), JSONKey::Slice(name)] = keys {
+///             scripts.push(*name);
+///         }
+///     },
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(scripts, ["build", "clean"]);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_keys<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>]),
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    while let Some(step) = reader.next_step()? {
+        if let Step::Key { .. } = step {
+            cb(&reader.key_chain);
+        }
+    }
+    Ok(on.len())
+}
+
+/// Alternative to closures for [`parse_with_visitor`], all methods default to doing nothing.
+/// The `keys` arguments follow the same rules as [`parse_events`]
+pub trait JSONVisitor<'a> {
+    /// Called on the start of every object and array, with the key chain of the container.
+    /// By default calls [`JSONVisitor::enter_object`] or [`JSONVisitor::enter_array`]
+    fn enter(&mut self, keys: &[JSONKey<'a>], kind: Container) {
+        match kind {
+            Container::Object => self.enter_object(keys),
+            Container::Array => self.enter_array(keys),
+        }
+    }
+
+    /// Called once an object or array is finished, with the same key chain as
+    /// [`JSONVisitor::enter`]. `span` covers the container from its opening bracket to its
+    /// closing bracket. By default calls [`JSONVisitor::exit_object`] or
+    /// [`JSONVisitor::exit_array`]
+    fn exit(&mut self, keys: &[JSONKey<'a>], kind: Container, span: Span) {
+        let _ = span;
+        match kind {
+            Container::Object => self.exit_object(keys),
+            Container::Array => self.exit_array(keys),
+        }
+    }
+
+    fn value(&mut self, keys: &[JSONKey<'a>], value: RootJSONValue<'a>) {
+        let _ = (keys, value);
+    }
+
+    fn key(&mut self, keys: &[JSONKey<'a>], key: &'a str) {
+        let _ = (keys, key);
+    }
+
+    fn enter_object(&mut self, keys: &[JSONKey<'a>]) {
+        let _ = keys;
+    }
+
+    fn exit_object(&mut self, keys: &[JSONKey<'a>]) {
+        let _ = keys;
+    }
+
+    fn enter_array(&mut self, keys: &[JSONKey<'a>]) {
+        let _ = keys;
+    }
+
+    fn exit_array(&mut self, keys: &[JSONKey<'a>]) {
+        let _ = keys;
+    }
+
+    fn comment(&mut self, keys: &[JSONKey<'a>], text: &'a str, style: CommentStyle, span: Span) {
+        let _ = (keys, text, style, span);
+    }
+}
+
+/// [`parse_events`] but calling methods on a [`JSONVisitor`]
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_visitor<'a>(
+    on: &'a str,
+    visitor: &mut impl JSONVisitor<'a>,
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    // Start of each open container
+    let mut starts = Vec::new();
+    while let Some(event) = reader.next_event()? {
+        let keys = &reader.key_chain;
+        match event {
+            JSONEvent::StartObject | JSONEvent::StartArray => {
+                let kind = if let JSONEvent::StartObject = event {
+                    Container::Object
+                } else {
+                    Container::Array
+                };
+                starts.push(reader.span.start);
+                visitor.enter(keys, kind);
+            }
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                let kind = if let JSONEvent::EndObject = event {
+                    Container::Object
+                } else {
+                    Container::Array
+                };
+                let start = starts.pop().unwrap_or(reader.span.start);
+                let span = Span {
+                    start,
+                    end: reader.span.end,
+                };
+                visitor.exit(keys, kind, span);
+            }
+            JSONEvent::Key(key) => visitor.key(keys, key),
+            JSONEvent::Value(value) => visitor.value(keys, value),
+            JSONEvent::Comment { text, style, span } => visitor.comment(keys, text, style, span),
+        }
+    }
+    Ok(on.len())
+}
+
+/// Pull based alternative to [`parse`]. Yields each value along with its key chain
+///
+/// ```
+/// use simple_json_parser::{JsonReader, JSONKey, ParseOptions, RootJSONValue};
+///
+/// let mut reader = JsonReader::new(r#"{ "a": [1, true] }"#, &ParseOptions::default());
+/// let (keys, value) = reader.next().unwrap().unwrap();
+/// assert_eq!(keys, [JSONKey::Slice("a"), JSONKey::Index(0)]);
+/// assert_eq!(value, RootJSONValue::Number("1"));
+/// ```
+pub struct JsonReader<'a> {
+    on: &'a str,
+    /// Byte offset of the next character
+    idx: usize,
+    /// Character that ended a number and still needs to be processed
+    retry: Option<(usize, char)>,
+    machine: Machine,
+    key_chain: Vec<JSONKey<'a>>,
+    span: Span,
+    finished: bool,
+    typed_numbers: bool,
+    /// End of a container found by [`JsonReader::skip`], still to be returned
+    pending: Option<Step>,
+    progress: ProgressTracker,
+}
+
+impl<'a> JsonReader<'a> {
+    /// [`ParseOptions::exit_on_first_value`] is not used here, stop iterating instead
+    #[must_use]
+    pub fn new(on: &'a str, options: &ParseOptions) -> Self {
+        Self {
+            on,
+            idx: 0,
+            retry: None,
+            machine: Machine::new(options),
+            key_chain: Vec::new(),
+            span: Span { start: 0, end: 0 },
+            finished: false,
+            typed_numbers: options.typed_numbers,
+            pending: None,
+            progress: ProgressTracker::new(options),
+        }
+    }
+
+    /// Like [`Iterator::next`] but does not clone the key chain. The key chain of the
+    /// returned value is available through [`JsonReader::key_chain`]
+    ///
+    /// # Errors
+    /// Returns an error if it tries to parse invalid JSON input
+    pub fn next_value(&mut self) -> Result<Option<RootJSONValue<'a>>, JSONParseError> {
+        while let Some(step) = self.next_step()? {
+            if let Step::Value {
+                kind, start, end, ..
+            } = step
+            {
+                return Ok(Some(self.value(kind, start, end)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Pull based version of [`parse_events`]
+    ///
+    /// # Errors
+    /// Returns an error if it tries to parse invalid JSON input
+    pub fn next_event(&mut self) -> Result<Option<JSONEvent<'a>>, JSONParseError> {
+        while let Some(step) = self.next_step()? {
+            let event = match step {
+                Step::Continue => continue,
+                Step::Key { start, end, quoted } => {
+                    let (start, end) = key_content(start, end, quoted);
+                    JSONEvent::Key(&self.on[start..end])
+                }
+                Step::StartObject { .. } => JSONEvent::StartObject,
+                // The start event has already been returned for empty values
+                Step::EndObject { .. }
+                | Step::Value {
+                    kind: ValueKind::EmptyObject,
+                    ..
+                } => JSONEvent::EndObject,
+                Step::StartArray { .. } => JSONEvent::StartArray,
+                Step::EndArray { .. }
+                | Step::Value {
+                    kind: ValueKind::EmptyArray,
+                    ..
+                } => JSONEvent::EndArray,
+                Step::Value {
+                    kind, start, end, ..
+                } => JSONEvent::Value(self.value(kind, start, end)),
+                Step::Comment { start, end, style } => {
+                    let (content_start, content_end) = comment_content(start, end, style);
+                    JSONEvent::Comment {
+                        text: &self.on[content_start..content_end],
+                        style,
+                        span: Span { start, end },
+                    }
+                }
+            };
+            return Ok(Some(event));
+        }
+        Ok(None)
+    }
+
+    #[must_use]
+    pub fn key_chain(&self) -> &[JSONKey<'a>] {
+        &self.key_chain
+    }
+
+    /// Span of the last value or event. For keys and strings this includes the quotes,
+    /// for the start and end of objects and arrays it is the bracket
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    fn next_step(&mut self) -> Result<Option<Step>, JSONParseError> {
+        if let Some(step) = self.pending.take() {
+            return Ok(Some(step));
+        }
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+
+            let Some((idx, chr)) = self.retry.take().or_else(|| self.next_char()) else {
+                let result = self
+                    .machine
+                    .finish(self.on.len())
+                    .map_err(|err| err.with_path(&self.key_chain));
+                match result {
+                    Ok(Some(step)) => self.span = step.span().unwrap_or(self.span),
+                    _ => self.finished = true,
+                }
+                return result;
+            };
+            if self.progress.due(idx) {
+                if let Err(err) = self.progress.report(idx, &self.key_chain) {
+                    self.finished = true;
+                    return Err(err);
+                }
+            }
+
+            match self.machine.push_with_keys(idx, chr, &mut self.key_chain) {
+                Ok(Step::Continue) => {}
+                Ok(step @ Step::Key { start, end, quoted }) => {
+                    let (content_start, content_end) = key_content(start, end, quoted);
+                    self.key_chain
+                        .push(JSONKey::Slice(&self.on[content_start..content_end]));
+                    self.span = Span { start, end };
+                    return Ok(Some(step));
+                }
+                Ok(step) => {
+                    self.span = step.span().unwrap_or(self.span);
+                    if let Step::Value { retry: true, .. } = step {
+                        self.retry = Some((idx, chr));
+                    }
+                    return Ok(Some(step));
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Err(err.with_path(&self.key_chain));
+                }
+            }
+        }
+    }
+
+    /// Works on bytes, only decoding non ASCII characters where they are not skipped (so
+    /// content of strings is not decoded)
+    fn next_char(&mut self) -> Option<(usize, char)> {
+        let bytes = self.on.as_bytes();
+        self.idx += self.machine.skippable(self.idx, &bytes[self.idx..]);
+        let idx = self.idx;
+        let byte = *bytes.get(idx)?;
+        let chr = if byte.is_ascii() {
+            char::from(byte)
+        } else {
+            self.on[idx..].chars().next()?
+        };
+        self.idx += chr.len_utf8();
+        Some((idx, chr))
+    }
+
+    fn value(&self, kind: ValueKind, start: usize, end: usize) -> RootJSONValue<'a> {
+        match kind {
+            ValueKind::String => RootJSONValue::String(&self.on[(start + 1)..(end - 1)]),
+            ValueKind::Number => RootJSONValue::number(&self.on[start..end], self.typed_numbers),
+            ValueKind::NonFinite => RootJSONValue::non_finite(&self.on[start..end]),
+            ValueKind::Boolean(value) => RootJSONValue::Boolean(value),
+            ValueKind::Null => RootJSONValue::Null,
+            ValueKind::EmptyObject => RootJSONValue::EmptyObject,
+            ValueKind::EmptyArray => RootJSONValue::EmptyArray,
+        }
+    }
+}
+
+impl<'a> Iterator for JsonReader<'a> {
+    type Item = Result<(Vec<JSONKey<'a>>, RootJSONValue<'a>), JSONParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_value() {
+            Ok(Some(value)) => Some(Ok((self.key_chain.clone(), value))),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Part of a key chain. Allows [`Machine`] to work with key chains that own their keys
+trait Segment {
+    fn index(idx: usize) -> Self;
+
+    fn as_index(&mut self) -> Option<&mut usize>;
+}
+
+impl Segment for JSONKey<'_> {
+    fn index(idx: usize) -> Self {
+        JSONKey::Index(idx)
+    }
+
+    fn as_index(&mut self) -> Option<&mut usize> {
+        if let JSONKey::Index(idx) = self {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+}
+
+/// Kind of an object or array, passed to [`JSONVisitor::enter`] and [`JSONVisitor::exit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    InKey {
+        escaped: bool,
+        start: usize,
+        quote: char,
+    },
+    /// JSON5 unquoted key
+    InIdentifierKey {
+        start: usize,
+    },
+    Colon,
+    /// After `{`, expecting a key or `}`
+    ObjectStart,
+    /// After `,` in an object, expecting a key
+    InObject,
+    /// After `[`, expecting a value or `]`
+    ArrayStart,
+    Comment {
+        start: usize,
+        multiline: bool,
+        last_was_asterisk: bool,
+        hash: bool,
+    },
+    ExpectingValue,
+    StringValue {
+        start: usize,
+        escaped: bool,
+        quote: char,
+    },
+    NumberValue {
+        start: usize,
+        part: NumberPart,
+        /// After a `_`, with [`ParseOptions::lenient_numbers`]
+        separator: bool,
+    },
+    /// Also JSON5 `Infinity` and `NaN`
+    TrueFalseNull {
+        start: usize,
+        expected: &'static str,
+    },
+    EndOfValue,
+}
+
+/// Position in the number grammar `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+/// (and JSON5 `+` and `0x` hexadecimal, and a leading or trailing `.` with
+/// [`ParseOptions::lenient_numbers`])
+#[derive(Debug, Clone, Copy)]
+enum NumberPart {
+    Minus,
+    Plus,
+    Zero,
+    Integer,
+    /// `.` without digits before it
+    LeadingDot,
+    Dot,
+    Fraction,
+    Exponent,
+    ExponentSign,
+    ExponentDigits,
+    HexStart,
+    HexDigits,
+}
+
+impl NumberPart {
+    /// `sign` allows a leading `+` and `lenient` a leading `.`
+    fn start(chr: char, sign: bool, lenient: bool) -> Option<Self> {
+        match chr {
+            '-' => Some(NumberPart::Minus),
+            '+' if sign => Some(NumberPart::Plus),
+            '.' if lenient => Some(NumberPart::LeadingDot),
+            '0' => Some(NumberPart::Zero),
+            '1'..='9' => Some(NumberPart::Integer),
+            _ => None,
+        }
+    }
+
+    /// `hex` allows `0x` and `lenient` allows a leading or trailing `.`
+    fn next(self, chr: char, hex: bool, lenient: bool) -> Option<Self> {
+        match (self, chr) {
+            (NumberPart::Minus | NumberPart::Plus, '0') => Some(NumberPart::Zero),
+            (NumberPart::Minus | NumberPart::Plus | NumberPart::Integer, '0'..='9') => {
+                Some(NumberPart::Integer)
+            }
+            (NumberPart::Zero, 'x' | 'X') if hex => Some(NumberPart::HexStart),
+            (NumberPart::HexStart | NumberPart::HexDigits, chr) if chr.is_ascii_hexdigit() => {
+                Some(NumberPart::HexDigits)
+            }
+            (NumberPart::Minus | NumberPart::Plus, '.') if lenient => Some(NumberPart::LeadingDot),
+            (NumberPart::Zero | NumberPart::Integer, '.') => Some(NumberPart::Dot),
+            (NumberPart::LeadingDot | NumberPart::Dot | NumberPart::Fraction, '0'..='9') => {
+                Some(NumberPart::Fraction)
+            }
+            (NumberPart::Zero | NumberPart::Integer | NumberPart::Fraction, 'e' | 'E') => {
+                Some(NumberPart::Exponent)
+            }
+            (NumberPart::Dot, 'e' | 'E') if lenient => Some(NumberPart::Exponent),
+            (NumberPart::Exponent, '+' | '-') => Some(NumberPart::ExponentSign),
+            (
+                NumberPart::Exponent | NumberPart::ExponentSign | NumberPart::ExponentDigits,
+                '0'..='9',
+            ) => Some(NumberPart::ExponentDigits),
+            _ => None,
+        }
+    }
+
+    fn is_complete(self, lenient: bool) -> bool {
+        match self {
+            NumberPart::Zero
+            | NumberPart::Integer
+            | NumberPart::Fraction
+            | NumberPart::ExponentDigits
+            | NumberPart::HexDigits => true,
+            NumberPart::Dot => lenient,
+            _ => false,
+        }
+    }
+
+    /// Whether a `_` can follow, with [`ParseOptions::lenient_numbers`]. Only between
+    /// digits, so not after a leading zero
+    fn takes_separator(self) -> bool {
+        matches!(
+            self,
+            NumberPart::Integer
+                | NumberPart::Fraction
+                | NumberPart::ExponentDigits
+                | NumberPart::HexDigits
+        )
+    }
+}
+
+/// Position in `\u` escapes, for [`ParseOptions::reject_lone_surrogates`]. `at` is the
+/// backslash of the escape
+#[derive(Debug, Clone, Copy)]
+enum UnicodeEscape {
+    None,
+    /// `high` is the escape of the high surrogate before this one
+    Digits {
+        at: usize,
+        code: u32,
+        count: u8,
+        high: Option<usize>,
+    },
+    /// After the escape of a high surrogate, expecting `\`
+    AfterHigh {
+        at: usize,
+    },
+    /// Expecting `u`
+    AfterHighBackslash {
+        at: usize,
+    },
+}
+
+/// Kind of a [`Step::Value`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    String,
+    Number,
+    Boolean(bool),
+    Null,
+    /// `NaN`, `Infinity` or `-Infinity` with [`ParseOptions::allow_non_finite_numbers`]
+    NonFinite,
+    /// `{}` or `[]` with [`ParseOptions::empty_containers`], in place of the end step
+    EmptyObject,
+    EmptyArray,
+}
+
+/// JSON5 (ECMAScript) identifier names, without escapes
+fn is_identifier_start(chr: char) -> bool {
+    chr.is_alphabetic() || matches!(chr, '$' | '_')
+}
+
+fn is_identifier_part(chr: char) -> bool {
+    chr.is_alphanumeric() || matches!(chr, '$' | '_')
+}
+
+/// Output of [`Machine::push`]. Spans are byte offsets into the source (and include quotes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Nothing finished, such as whitespace or a character inside a string
+    Continue,
+    Key {
+        start: usize,
+        end: usize,
+        /// `false` for JSON5 unquoted keys
+        quoted: bool,
+    },
+    Value {
+        kind: ValueKind,
+        start: usize,
+        end: usize,
+        /// The character ended the value (a number) but has not been consumed
+        retry: bool,
+    },
+    StartObject {
+        at: usize,
+    },
+    EndObject {
+        at: usize,
+    },
+    StartArray {
+        at: usize,
+    },
+    EndArray {
+        at: usize,
+    },
+    /// Spans the delimiters (but not the line break after a line comment)
+    Comment {
+        start: usize,
+        end: usize,
+        style: CommentStyle,
+    },
+}
+
+impl Step {
+    /// `None` for [`Step::Continue`], otherwise what the step covers (the bracket for the
+    /// start and end of objects and arrays)
+    #[must_use]
+    pub fn span(self) -> Option<Span> {
+        match self {
+            Step::Continue => None,
+            Step::Key { start, end, .. }
+            | Step::Value { start, end, .. }
+            | Step::Comment { start, end, .. } => Some(Span { start, end }),
+            Step::StartObject { at }
+            | Step::EndObject { at }
+            | Step::StartArray { at }
+            | Step::EndArray { at } => Some(Span {
+                start: at,
+                end: at + 1,
+            }),
+        }
+    }
+}
+
+/// Bounds of a key from [`Step::Key`] without quotes
+fn key_content(start: usize, end: usize, quoted: bool) -> (usize, usize) {
+    if quoted {
+        (start + 1, end - 1)
+    } else {
+        (start, end)
+    }
+}
+
+/// Bounds of a comment from [`Step::Comment`] without delimiters
+fn comment_content(start: usize, end: usize, style: CommentStyle) -> (usize, usize) {
+    match style {
+        CommentStyle::Line => ((start + 2).min(end), end),
+        CommentStyle::Hash => (start + 1, end),
+        CommentStyle::Block => (start + 2, end - 2),
+    }
+}
+
+/// Kinds of the open objects and arrays, as bits. Only allocates when nested more than
+/// [`Containers::INLINE`] levels deep
+#[derive(Debug, Clone, Default)]
+struct Containers {
+    /// Set for arrays
+    inline: [u64; 4],
+    /// After the inline levels
+    spilled: Vec<Container>,
+    len: usize,
+}
+
+impl Containers {
+    const INLINE: usize = 256;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn get(&self, idx: usize) -> Option<Container> {
+        if idx >= self.len {
+            None
+        } else if idx < Self::INLINE {
+            let is_array = self.inline[idx / 64] & (1 << (idx % 64)) != 0;
+            Some(if is_array {
+                Container::Array
+            } else {
+                Container::Object
+            })
+        } else {
+            self.spilled.get(idx - Self::INLINE).copied()
+        }
+    }
+
+    fn last(&self) -> Option<Container> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    fn push(&mut self, container: Container) {
+        if self.len < Self::INLINE {
+            let (word, bit) = (self.len / 64, 1 << (self.len % 64));
+            if let Container::Array = container {
+                self.inline[word] |= bit;
+            } else {
+                self.inline[word] &= !bit;
+            }
+        } else {
+            self.spilled.push(container);
+        }
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<Container> {
+        let last = self.last()?;
+        self.truncate(self.len - 1);
+        Some(last)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.spilled.truncate(len.saturating_sub(Self::INLINE));
+            self.len = len;
+        }
+    }
+
+    fn iter(&self) -> impl DoubleEndedIterator<Item = Container> + ExactSizeIterator + '_ {
+        // Always in bounds
+        (0..self.len).map(|idx| self.get(idx).unwrap_or(Container::Object))
+    }
 }
 
-#[derive(Debug)]
-pub enum JSONParseErrorReason {
-    ExpectedColon,
-    ExpectedEndOfValue,
-    /// Doubles as both closing and ending
-    ExpectedBracket,
-    ExpectedTrueFalseNull,
-    ExpectedKey,
-    ExpectedValue,
-    ExpectedEndOfMultilineComment,
-    /// Both for string values and keys
-    ExpectedQuote,
+/// Where [`Machine`] keeps the key chain, so that it can be a `Vec` or a buffer from the
+/// caller
+trait KeyStack<K> {
+    fn push(&mut self, key: K);
+
+    fn pop(&mut self);
+
+    fn last_mut(&mut self) -> Option<&mut K>;
 }
 
-#[derive(Debug)]
-pub struct JSONParseError {
-    pub at: usize,
-    pub reason: JSONParseErrorReason,
+impl<K> KeyStack<K> for Vec<K> {
+    fn push(&mut self, key: K) {
+        Vec::push(self, key);
+    }
+
+    fn pop(&mut self) {
+        Vec::pop(self);
+    }
+
+    fn last_mut(&mut self) -> Option<&mut K> {
+        <[K]>::last_mut(self)
+    }
 }
 
-impl std::error::Error for JSONParseError {}
+/// Discards keys. Indexes are not tracked either
+struct NoKeys;
 
-impl std::fmt::Display for JSONParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        f.write_fmt(format_args!(
-            "JSONParseError: {:?} at {:?}",
-            self.reason, self.at
-        ))
+impl<'a> KeyStack<JSONKey<'a>> for NoKeys {
+    fn push(&mut self, _key: JSONKey<'a>) {}
+
+    fn pop(&mut self) {}
+
+    fn last_mut(&mut self) -> Option<&mut JSONKey<'a>> {
+        None
     }
 }
 
-/// If you want to return early (break on an exception in the callback) or
-/// more configuration use [`parse_with_exit_signal`]
+/// The character by character state machine behind all of the parsing functions, for
+/// driving parsing from sources the crate does not support (ropes, decoded streams). It does
+/// not hold the source or track key chains, so keys and values are given as byte offsets
+/// (from the `idx` passed to [`Machine::push`]).
 ///
-/// # Errors
-/// Returns an error if it tries to parse invalid JSON input
-pub fn parse<'a>(
-    on: &'a str,
-    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
-) -> Result<usize, JSONParseError> {
-    parse_with_exit_signal(
-        on,
-        |k, v| {
-            cb(k, v);
-            false
-        },
-        false,
-        true,
-    )
+/// ```
+/// use simple_json_parser::{Machine, ParseOptions, Step, ValueKind};
+///
+/// let source = r#"{ "a": [true, 12] }"#;
+/// let mut machine = Machine::new(&ParseOptions::default());
+/// let mut values = Vec::new();
+/// let mut steps = Vec::new();
+/// for (idx, chr) in source.char_indices() {
+///     loop {
+///         let step = machine.push(idx, chr).unwrap();
+///         if let Step::Value { kind, start, end, .. } = step {
+///             values.push((kind, &source[start..end]));
+///         }
+///         steps.push(step);
+///         // A number is only finished by the character after it, which needs pushing again
+///         if !matches!(step, Step::Value { retry: true, .. }) {
+///             break;
+///         }
+///     }
+/// }
+/// assert!(machine.finish(source.len()).unwrap().is_none());
+/// assert_eq!(values, [(ValueKind::Boolean(true), "true"), (ValueKind::Number, "12")]);
+/// assert!(matches!(steps[0], Step::StartObject { at: 0 }));
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+pub struct Machine {
+    state: State,
+    /// State to return to after a comment
+    resume: State,
+    containers: Containers,
+    line_comments: bool,
+    block_comments: bool,
+    hash_comments: bool,
+    max_depth: Option<usize>,
+    allow_trailing_commas: bool,
+    json5: bool,
+    lenient_numbers: bool,
+    non_finite_numbers: bool,
+    allow_scalar_root: bool,
+    empty_containers: bool,
+    allow_control_characters: bool,
+    allow_bom: bool,
+    require_end_of_input: bool,
+    limits: Limits,
+    /// Number of keys in each open object, only with [`Limits::max_keys_per_object`]
+    key_counts: Vec<usize>,
+    values: usize,
+    /// Position of the last `{` or `[`
+    opened: usize,
+    /// Offset to next check [`Limits::max_bytes`] and [`Limits::deadline`] at
+    next_budget_check: usize,
+    reject_lone_surrogates: bool,
+    unicode_escape: UnicodeEscape,
 }
 
-enum State {
-    InKey {
-        escaped: bool,
-        start: usize,
-    },
-    Colon,
-    InObject,
-    Comment {
-        start: usize,
-        multiline: bool,
-        last_was_asterisk: bool,
-        hash: bool,
-    },
-    ExpectingValue,
-    StringValue {
-        start: usize,
-        escaped: bool,
-    },
-    NumberValue {
-        start: usize,
-    },
-    TrueFalseNull {
-        start: usize,
-    },
-    EndOfValue,
-}
+impl Machine {
+    #[must_use]
+    pub fn new(options: &ParseOptions) -> Self {
+        Self {
+            state: State::ExpectingValue,
+            resume: State::ExpectingValue,
+            containers: Containers::default(),
+            line_comments: options.allow_comments && options.allow_line_comments,
+            block_comments: options.allow_comments && options.allow_block_comments,
+            hash_comments: options.allow_comments && options.allow_hash_comments,
+            max_depth: options.max_depth,
+            allow_trailing_commas: options.allow_trailing_commas,
+            json5: options.json5,
+            lenient_numbers: options.lenient_numbers,
+            non_finite_numbers: options.allow_non_finite_numbers,
+            allow_scalar_root: options.allow_scalar_root,
+            empty_containers: options.empty_containers,
+            allow_control_characters: options.allow_control_characters,
+            allow_bom: options.allow_bom,
+            require_end_of_input: options.require_end_of_input,
+            limits: options.limits,
+            key_counts: Vec::new(),
+            values: 0,
+            opened: 0,
+            // Set by the first check, at the start
+            next_budget_check: 0,
+            reject_lone_surrogates: options.reject_lone_surrogates,
+            unicode_escape: UnicodeEscape::None,
+        }
+    }
 
-// TODO always pops from key_chain **unless** we are in an array.
-// TODO there are complications using this in an iterator when we yielding numbers
-fn end_of_value(
-    idx: usize,
-    chr: char,
-    state: &mut State,
-    key_chain: &mut Vec<JSONKey<'_>>,
-    allow_comments: bool,
-) -> Result<(), JSONParseError> {
-    if chr == ',' {
-        if let Some(JSONKey::Index(i)) = key_chain.last_mut() {
-            *i += 1;
-            *state = State::ExpectingValue;
-        } else {
-            key_chain.pop();
-            *state = State::InObject;
-        }
-    } else if let ('}', Some(JSONKey::Slice(..))) = (chr, key_chain.last()) {
-        // TODO errors here if index
-        key_chain.pop();
-    } else if let (']', Some(JSONKey::Index(..))) = (chr, key_chain.last()) {
-        // TODO errors here if slice etc
-        key_chain.pop();
-    } else if let (true, c @ ('/' | '#')) = (allow_comments, chr) {
-        key_chain.pop();
-        *state = State::Comment {
-            last_was_asterisk: false,
-            start: idx,
-            multiline: false,
-            hash: c == '#',
+    /// Whether there are no open objects or arrays
+    #[must_use]
+    pub fn is_at_root(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    /// Number of open objects and arrays
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.containers.len()
+    }
+
+    /// Pushes the character `chr` at byte offset `idx`. Offsets should increase with each
+    /// character. On [`Step::Value`] with `retry` the same character must be pushed again
+    ///
+    /// # Errors
+    /// Returns an error if the character makes the input invalid
+    pub fn push(&mut self, idx: usize, chr: char) -> Result<Step, JSONParseError> {
+        self.push_with_keys(idx, chr, &mut NoKeys)
+    }
+
+    /// Inside a key, string or comment, where any character is valid
+    fn in_text(&self) -> bool {
+        matches!(
+            self.state,
+            State::InKey { .. } | State::StringValue { .. } | State::Comment { .. }
+        )
+    }
+
+    /// Number of bytes at the start of `rest` (starting at `idx`) which would not change
+    /// the state, so do not need to be pushed
+    fn skippable(&self, idx: usize, rest: &[u8]) -> usize {
+        // The digits of `\u` escapes are checked one by one
+        if !matches!(self.unicode_escape, UnicodeEscape::None) {
+            return 0;
+        }
+        let end = match self.state {
+            State::InKey {
+                escaped: false,
+                quote,
+                ..
+            }
+            | State::StringValue {
+                escaped: false,
+                quote,
+                ..
+            } => rest.iter().position(|&byte| {
+                char::from(byte) == quote
+                    || byte == b'\\'
+                    || (byte < b' ' && !self.allow_control_characters)
+            }),
+            // After the second character, which decides the style
+            State::Comment {
+                multiline,
+                last_was_asterisk: false,
+                start,
+                hash,
+            } if hash || idx > start + 1 => {
+                let end = if multiline { b'*' } else { b'\n' };
+                rest.iter().position(|&byte| byte == end)
+            }
+            _ => Some(0),
         };
-    } else if !chr.is_whitespace() {
-        return Err(JSONParseError {
-            at: idx,
-            reason: JSONParseErrorReason::ExpectedEndOfValue,
-        });
+        // Stop at the next check of the byte and time limits
+        end.unwrap_or(rest.len())
+            .min(self.next_budget_check.saturating_sub(idx))
     }
-    Ok(())
-}
 
-/// Returns the number of bytes parsed.
-/// `exit_on_first_value` returns once the first object has been parsed.
-///
-/// # Errors
-/// Returns an error if it tries to parse invalid JSON input
-#[allow(clippy::too_many_lines)]
-pub fn parse_with_exit_signal<'a>(
-    on: &'a str,
-    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
-    exit_on_first_value: bool,
-    allow_comments: bool,
-) -> Result<usize, JSONParseError> {
-    let chars = on.char_indices();
+    /// Start of the key or value currently being parsed
+    fn token_start(&self) -> Option<usize> {
+        match self.state {
+            State::InKey { start, .. }
+            | State::InIdentifierKey { start }
+            | State::StringValue { start, .. }
+            | State::NumberValue { start, .. }
+            | State::TrueFalseNull { start, .. } => Some(start),
+            _ => None,
+        }
+    }
+
+    /// Whether `chr` can start a comment
+    fn is_comment_start(&self, chr: char) -> bool {
+        match chr {
+            '/' => self.line_comments || self.block_comments,
+            '#' => self.hash_comments,
+            _ => false,
+        }
+    }
 
-    let mut key_chain = Vec::new();
-    let mut state = State::ExpectingValue;
+    fn start_comment(&mut self, idx: usize, chr: char) -> bool {
+        if let (true, c @ ('/' | '#')) = (self.is_comment_start(chr), chr) {
+            self.resume = self.state;
+            self.state = State::Comment {
+                last_was_asterisk: false,
+                start: idx,
+                multiline: false,
+                hash: c == '#',
+            };
+            true
+        } else {
+            false
+        }
+    }
 
-    for (idx, chr) in chars {
-        match state {
+    /// [`Machine::push`], keeping `key_chain` up to date (apart from keys, which the caller
+    /// pushes on [`Step::Key`])
+    #[allow(clippy::too_many_lines)]
+    fn push_with_keys<K: Segment>(
+        &mut self,
+        idx: usize,
+        chr: char,
+        key_chain: &mut impl KeyStack<K>,
+    ) -> Result<Step, JSONParseError> {
+        if idx >= self.next_budget_check {
+            self.check_budget(idx)?;
+        }
+        if let (true, State::InKey { escaped, .. } | State::StringValue { escaped, .. }) =
+            (self.reject_lone_surrogates, self.state)
+        {
+            self.check_surrogate(idx, chr, escaped)?;
+        }
+        match self.state {
             State::InKey {
                 start,
                 ref mut escaped,
+                quote,
             } => {
-                if !*escaped && chr == '"' {
-                    key_chain.push(JSONKey::Slice(&on[start..idx]));
-                    state = State::Colon;
-                } else {
-                    *escaped = chr == '\\';
+                if !*escaped && chr == quote {
+                    self.check_string_len(start, idx - start - 1)?;
+                    self.state = State::Colon;
+                    return Ok(Step::Key {
+                        start,
+                        end: idx + 1,
+                        quoted: true,
+                    });
+                }
+                if chr < ' ' && !self.allow_control_characters {
+                    return Err(JSONParseError {
+                        at: idx,
+                        reason: JSONParseErrorReason::ControlCharacterInString,
+                        path: None,
+                    });
+                }
+                *escaped = !*escaped && chr == '\\';
+            }
+            State::InIdentifierKey { start } => {
+                if !is_identifier_part(chr) {
+                    self.check_string_len(start, idx - start)?;
+                    self.state = State::Colon;
+                    if chr == ':' {
+                        self.state = State::ExpectingValue;
+                    } else if !chr.is_whitespace() && !self.start_comment(idx, chr) {
+                        return Err(JSONParseError {
+                            at: idx,
+                            reason: JSONParseErrorReason::ExpectedColon { found: Some(chr) },
+                            path: None,
+                        });
+                    }
+                    return Ok(Step::Key {
+                        start,
+                        end: idx,
+                        quoted: false,
+                    });
                 }
             }
             State::StringValue {
                 start,
                 ref mut escaped,
+                quote,
             } => {
-                if !*escaped && chr == '"' {
-                    state = State::EndOfValue;
-                    let res = cb(&key_chain, RootJSONValue::String(&on[start..idx]));
-                    if res {
-                        return Ok(idx + chr.len_utf8());
-                    }
-                } else {
-                    *escaped = chr == '\\';
+                if !*escaped && chr == quote {
+                    self.check_string_len(start, idx - start - 1)?;
+                    self.state = State::EndOfValue;
+                    return Ok(Step::Value {
+                        kind: ValueKind::String,
+                        start,
+                        end: idx + 1,
+                        retry: false,
+                    });
+                }
+                if chr < ' ' && !self.allow_control_characters {
+                    return Err(JSONParseError {
+                        at: idx,
+                        reason: JSONParseErrorReason::ControlCharacterInString,
+                        path: None,
+                    });
                 }
+                *escaped = !*escaped && chr == '\\';
             }
             State::Colon => {
                 if chr == ':' {
-                    state = State::ExpectingValue;
-                } else if !chr.is_whitespace() {
+                    self.state = State::ExpectingValue;
+                } else if !chr.is_whitespace() && !self.start_comment(idx, chr) {
                     return Err(JSONParseError {
                         at: idx,
-                        reason: JSONParseErrorReason::ExpectedColon,
+                        reason: JSONParseErrorReason::ExpectedColon { found: Some(chr) },
+                        path: None,
                     });
                 }
             }
-            State::EndOfValue => {
-                end_of_value(idx, chr, &mut state, &mut key_chain, allow_comments)?;
-
-                if exit_on_first_value && key_chain.is_empty() && chr != ',' {
-                    return Ok(idx + chr.len_utf8());
-                }
-            }
+            State::EndOfValue => return self.end_of_value(idx, chr, key_chain),
             State::Comment {
                 ref mut last_was_asterisk,
                 ref mut multiline,
@@ -198,180 +2168,578 @@ pub fn parse_with_exit_signal<'a>(
                 start,
             } => {
                 if chr == '\n' && !*multiline {
-                    if let Some(JSONKey::Index(..)) = key_chain.last() {
-                        state = State::ExpectingValue;
+                    self.state = self.resume;
+                    return Ok(Step::Comment {
+                        start,
+                        end: idx,
+                        style: if hash {
+                            CommentStyle::Hash
+                        } else {
+                            CommentStyle::Line
+                        },
+                    });
+                } else if start + 1 == idx && !hash {
+                    // The second character decides the style
+                    let block = chr == '*';
+                    if !(if block {
+                        self.block_comments
                     } else {
-                        state = State::InObject;
+                        self.line_comments
+                    }) {
+                        return Err(JSONParseError {
+                            at: start,
+                            reason: JSONParseErrorReason::DisallowedComment,
+                            path: None,
+                        });
                     }
-                } else if chr == '*' && start + 1 == idx && !hash {
-                    *multiline = true;
+                    *multiline = block;
                 } else if *multiline {
                     if *last_was_asterisk && chr == '/' {
-                        if let Some(JSONKey::Index(..)) = key_chain.last() {
-                            state = State::ExpectingValue;
-                        } else {
-                            state = State::InObject;
-                        }
-                    } else {
-                        *last_was_asterisk = chr == '*';
+                        self.state = self.resume;
+                        return Ok(Step::Comment {
+                            start,
+                            end: idx + 1,
+                            style: CommentStyle::Block,
+                        });
                     }
+                    *last_was_asterisk = chr == '*';
                 }
             }
             State::ExpectingValue => {
-                state = match chr {
-                    '{' => State::InObject,
-                    '[' => {
-                        key_chain.push(JSONKey::Index(0));
-                        State::ExpectingValue
-                    }
-                    '"' => State::StringValue {
-                        start: idx + '"'.len_utf8(),
-                        escaped: false,
-                    },
-                    c @ ('/' | '#') if allow_comments => State::Comment {
-                        last_was_asterisk: false,
-                        start: idx,
-                        multiline: false,
-                        hash: c == '#',
-                    },
-                    '0'..='9' | '-' => State::NumberValue { start: idx },
-                    't' | 'f' | 'n' => State::TrueFalseNull { start: idx },
-                    chr if chr.is_whitespace() => state,
-                    _ => {
-                        return Err(JSONParseError {
-                            at: idx,
-                            reason: JSONParseErrorReason::ExpectedValue,
-                        })
-                    }
+                // After a comma in an array
+                if let (']', true, Some(Container::Array)) =
+                    (chr, self.allow_trailing_commas, self.containers.last())
+                {
+                    key_chain.pop();
+                    self.close_container();
+                    self.state = State::EndOfValue;
+                    return Ok(Step::EndArray { at: idx });
+                }
+                return self.expecting_value(idx, chr);
+            }
+            State::ArrayStart => {
+                if chr == ']' {
+                    self.close_container();
+                    self.state = State::EndOfValue;
+                    return Ok(self.end_empty(idx, ValueKind::EmptyArray));
+                } else if !chr.is_whitespace() && !self.start_comment(idx, chr) {
+                    key_chain.push(K::index(0));
+                    return self.expecting_value(idx, chr);
                 }
             }
-            State::InObject => {
-                if chr == '"' {
-                    state = State::InKey {
+            State::ObjectStart | State::InObject => {
+                if chr == '"' || (self.json5 && chr == '\'') {
+                    self.count_key(idx)?;
+                    self.state = State::InKey {
                         escaped: false,
-                        start: idx + '"'.len_utf8(),
-                    };
-                } else if chr == '}' {
-                    if let Some(JSONKey::Index(..)) = key_chain.last() {
-                        state = State::ExpectingValue;
-                    } else {
-                        state = State::InObject;
-                    }
-                } else if let (true, c @ ('/' | '#')) = (allow_comments, chr) {
-                    state = State::Comment {
-                        last_was_asterisk: false,
                         start: idx,
-                        multiline: false,
-                        hash: c == '#',
+                        quote: chr,
                     };
-                } else if !chr.is_whitespace() {
+                } else if self.json5 && is_identifier_start(chr) {
+                    self.count_key(idx)?;
+                    self.state = State::InIdentifierKey { start: idx };
+                } else if chr == '}'
+                    && (matches!(self.state, State::ObjectStart) || self.allow_trailing_commas)
+                {
+                    let empty = matches!(self.state, State::ObjectStart);
+                    self.close_container();
+                    self.state = State::EndOfValue;
+                    if empty {
+                        return Ok(self.end_empty(idx, ValueKind::EmptyObject));
+                    }
+                    return Ok(Step::EndObject { at: idx });
+                } else if !chr.is_whitespace() && !self.start_comment(idx, chr) {
                     return Err(JSONParseError {
                         at: idx,
-                        reason: JSONParseErrorReason::ExpectedKey,
+                        reason: JSONParseErrorReason::ExpectedKey { found: Some(chr) },
+                        path: None,
                     });
                 }
             }
-            State::NumberValue { start } => {
-                // TODO actual number handing
-                if chr.is_whitespace() || matches!(chr, '}' | ',' | ']') {
-                    let res = cb(&key_chain, RootJSONValue::Number(&on[start..idx]));
-                    if res {
-                        return Ok(idx);
+            State::NumberValue {
+                start,
+                ref mut part,
+                ref mut separator,
+            } => {
+                if chr.is_whitespace()
+                    || matches!(chr, '}' | ',' | ']')
+                    || (chr == '/' && (self.line_comments || self.block_comments))
+                    || (chr == '#' && self.hash_comments)
+                {
+                    if *separator || !part.is_complete(self.lenient_numbers) {
+                        return Err(JSONParseError {
+                            at: idx,
+                            reason: JSONParseErrorReason::InvalidNumber,
+                            path: None,
+                        });
                     }
-                    state = State::EndOfValue;
-                    end_of_value(idx, chr, &mut state, &mut key_chain, allow_comments)?;
+                    self.state = State::EndOfValue;
+                    return Ok(Step::Value {
+                        kind: ValueKind::Number,
+                        start,
+                        end: idx,
+                        retry: true,
+                    });
                 }
-            }
-            State::TrueFalseNull { start } => {
-                let diff = idx - start + 1;
-                if diff < 4 {
-                    // ...
-                } else if diff == 4 {
-                    match &on[start..=idx] {
-                        "true" => {
-                            let res = cb(&key_chain, RootJSONValue::Boolean(true));
-                            if res {
-                                return Ok(idx + chr.len_utf8());
-                            }
-                            state = State::EndOfValue;
-                        }
-                        "null" => {
-                            let res = cb(&key_chain, RootJSONValue::Null);
-                            if res {
-                                return Ok(idx + chr.len_utf8());
-                            }
-                            state = State::EndOfValue;
-                        }
-                        "fals" => {}
-                        _ => {
-                            return Err(JSONParseError {
-                                at: idx,
-                                reason: JSONParseErrorReason::ExpectedTrueFalseNull,
-                            })
-                        }
-                    }
-                } else if let "false" = &on[start..=idx] {
-                    let res = cb(&key_chain, RootJSONValue::Boolean(false));
-                    if res {
-                        return Ok(idx + chr.len_utf8());
-                    }
-                    state = State::EndOfValue;
+                if let (true, NumberPart::Minus | NumberPart::Plus, 'I' | 'N') =
+                    (self.json5 || self.non_finite_numbers, *part, chr)
+                {
+                    // The sign is not checked again
+                    self.state = State::TrueFalseNull {
+                        start,
+                        expected: if chr == 'I' { "-Infinity" } else { "-NaN" },
+                    };
+                } else if chr == '_'
+                    && self.lenient_numbers
+                    && !*separator
+                    && part.takes_separator()
+                {
+                    *separator = true;
+                } else if let Some(next) = part
+                    .next(
+                        chr,
+                        self.json5 || self.lenient_numbers,
+                        self.lenient_numbers,
+                    )
+                    .filter(|next| !*separator || next.takes_separator())
+                {
+                    *part = next;
+                    *separator = false;
                 } else {
                     return Err(JSONParseError {
                         at: idx,
-                        reason: JSONParseErrorReason::ExpectedTrueFalseNull,
+                        reason: JSONParseErrorReason::InvalidNumber,
+                        path: None,
+                    });
+                }
+            }
+            State::TrueFalseNull { start, expected } => {
+                let offset = idx - start;
+                if !expected[offset..].starts_with(chr) {
+                    return Err(JSONParseError {
+                        at: idx,
+                        reason: JSONParseErrorReason::ExpectedTrueFalseNull { found: Some(chr) },
+                        path: None,
+                    });
+                }
+                if offset + 1 == expected.len() {
+                    let kind = match expected {
+                        "true" => ValueKind::Boolean(true),
+                        "false" => ValueKind::Boolean(false),
+                        "null" => ValueKind::Null,
+                        _ if self.non_finite_numbers => ValueKind::NonFinite,
+                        _ => ValueKind::Number,
+                    };
+                    self.state = State::EndOfValue;
+                    return Ok(Step::Value {
+                        kind,
+                        start,
+                        end: idx + 1,
+                        retry: false,
                     });
                 }
             }
         }
+        Ok(Step::Continue)
+    }
+
+    /// End of an object or array with nothing in it, as a value with
+    /// [`ParseOptions::empty_containers`]
+    fn end_empty(&self, idx: usize, kind: ValueKind) -> Step {
+        match kind {
+            _ if self.empty_containers => Step::Value {
+                kind,
+                start: self.opened,
+                end: idx + 1,
+                retry: false,
+            },
+            ValueKind::EmptyObject => Step::EndObject { at: idx },
+            _ => Step::EndArray { at: idx },
+        }
     }
 
-    match state {
-        State::InKey { .. } | State::StringValue { .. } => {
+    fn expecting_value(&mut self, idx: usize, chr: char) -> Result<Step, JSONParseError> {
+        if matches!(chr, '{' | '[')
+            && self
+                .max_depth
+                .is_some_and(|max_depth| self.containers.len() >= max_depth)
+        {
             return Err(JSONParseError {
-                at: on.len(),
-                reason: JSONParseErrorReason::ExpectedQuote,
-            })
+                at: idx,
+                reason: JSONParseErrorReason::DepthLimitExceeded,
+                path: None,
+            });
+        }
+        if let Some(part) = NumberPart::start(
+            chr,
+            self.json5 || self.lenient_numbers,
+            self.lenient_numbers,
+        ) {
+            self.state = State::NumberValue {
+                start: idx,
+                part,
+                separator: false,
+            };
+            return self.started_scalar(idx);
+        }
+        self.state = match chr {
+            '{' => {
+                self.count_value(idx)?;
+                if self.limits.max_keys_per_object.is_some() {
+                    self.key_counts.push(0);
+                }
+                self.opened = idx;
+                self.containers.push(Container::Object);
+                self.state = State::ObjectStart;
+                return Ok(Step::StartObject { at: idx });
+            }
+            '[' => {
+                self.count_value(idx)?;
+                self.opened = idx;
+                self.containers.push(Container::Array);
+                self.state = State::ArrayStart;
+                return Ok(Step::StartArray { at: idx });
+            }
+            '"' => State::StringValue {
+                start: idx,
+                escaped: false,
+                quote: '"',
+            },
+            '\'' if self.json5 => State::StringValue {
+                start: idx,
+                escaped: false,
+                quote: '\'',
+            },
+            'I' if self.json5 || self.non_finite_numbers => State::TrueFalseNull {
+                start: idx,
+                expected: "Infinity",
+            },
+            'N' if self.json5 || self.non_finite_numbers => State::TrueFalseNull {
+                start: idx,
+                expected: "NaN",
+            },
+            't' => State::TrueFalseNull {
+                start: idx,
+                expected: "true",
+            },
+            'f' => State::TrueFalseNull {
+                start: idx,
+                expected: "false",
+            },
+            'n' => State::TrueFalseNull {
+                start: idx,
+                expected: "null",
+            },
+            '\u{FEFF}' if idx == 0 && self.allow_bom => return Ok(Step::Continue),
+            chr if chr.is_whitespace() || self.start_comment(idx, chr) => {
+                return Ok(Step::Continue)
+            }
+            _ => {
+                return Err(JSONParseError {
+                    at: idx,
+                    reason: JSONParseErrorReason::ExpectedValue { found: Some(chr) },
+                    path: None,
+                })
+            }
+        };
+        self.started_scalar(idx)
+    }
+
+    /// After starting a string, number or literal
+    fn started_scalar(&mut self, idx: usize) -> Result<Step, JSONParseError> {
+        if !self.allow_scalar_root && self.containers.is_empty() {
+            return Err(JSONParseError {
+                at: idx,
+                reason: JSONParseErrorReason::ExpectedObjectOrArray,
+                path: None,
+            });
         }
-        State::Colon => {
+        self.count_value(idx)?;
+        Ok(Step::Continue)
+    }
+
+    fn close_container(&mut self) {
+        let container = self.containers.pop();
+        if let (Some(Container::Object), Some(_)) = (container, self.limits.max_keys_per_object) {
+            self.key_counts.pop();
+        }
+    }
+
+    /// Follows `\u` escapes in strings and keys, with
+    /// [`ParseOptions::reject_lone_surrogates`]. `escaped` is whether `chr` follows a `\`
+    fn check_surrogate(
+        &mut self,
+        idx: usize,
+        chr: char,
+        escaped: bool,
+    ) -> Result<(), JSONParseError> {
+        let lone = |at| JSONParseError {
+            at,
+            reason: JSONParseErrorReason::LoneSurrogate,
+            path: None,
+        };
+        self.unicode_escape = match self.unicode_escape {
+            UnicodeEscape::None if escaped && chr == 'u' => UnicodeEscape::Digits {
+                at: idx - 1,
+                code: 0,
+                count: 0,
+                high: None,
+            },
+            UnicodeEscape::None => UnicodeEscape::None,
+            UnicodeEscape::Digits {
+                at,
+                code,
+                count,
+                high,
+            } => match (chr.to_digit(16), high) {
+                (Some(digit), _) if count < 3 => UnicodeEscape::Digits {
+                    at,
+                    code: code * 16 + digit,
+                    count: count + 1,
+                    high,
+                },
+                (Some(digit), high) => {
+                    let code = code * 16 + digit;
+                    match (high, code) {
+                        (None, 0xD800..=0xDBFF) => UnicodeEscape::AfterHigh { at },
+                        (None, 0xDC00..=0xDFFF) => return Err(lone(at)),
+                        (Some(_), 0xDC00..=0xDFFF) | (None, _) => UnicodeEscape::None,
+                        (Some(high), _) => return Err(lone(high)),
+                    }
+                }
+                // Not four hex digits, which is not checked here
+                (None, None) => UnicodeEscape::None,
+                (None, Some(high)) => return Err(lone(high)),
+            },
+            UnicodeEscape::AfterHigh { at } if !escaped && chr == '\\' => {
+                UnicodeEscape::AfterHighBackslash { at }
+            }
+            UnicodeEscape::AfterHighBackslash { at } if chr == 'u' => UnicodeEscape::Digits {
+                at: idx - 1,
+                code: 0,
+                count: 0,
+                high: Some(at),
+            },
+            UnicodeEscape::AfterHigh { at } | UnicodeEscape::AfterHighBackslash { at } => {
+                return Err(lone(at));
+            }
+        };
+        Ok(())
+    }
+
+    /// Checks [`Limits::max_bytes`] and [`Limits::deadline`], and when to check them next
+    #[cold]
+    fn check_budget(&mut self, idx: usize) -> Result<(), JSONParseError> {
+        let max_bytes = self.limits.max_bytes.unwrap_or(usize::MAX);
+        if idx >= max_bytes {
             return Err(JSONParseError {
-                at: on.len(),
-                reason: JSONParseErrorReason::ExpectedColon,
+                at: idx,
+                reason: JSONParseErrorReason::InputTooLarge,
+                path: None,
             });
         }
-        State::Comment { multiline, .. } => {
-            if multiline {
+        #[allow(unused_mut)]
+        let mut next = max_bytes;
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.limits.deadline {
+            if std::time::Instant::now() >= deadline {
+                return Err(JSONParseError {
+                    at: idx,
+                    reason: JSONParseErrorReason::DeadlineExceeded,
+                    path: None,
+                });
+            }
+            next = next.min((idx / Limits::DEADLINE_EVERY + 1) * Limits::DEADLINE_EVERY);
+        }
+        self.next_budget_check = next;
+        Ok(())
+    }
+
+    fn count_value(&mut self, idx: usize) -> Result<(), JSONParseError> {
+        if let Some(max) = self.limits.max_total_values {
+            self.values += 1;
+            if self.values > max {
                 return Err(JSONParseError {
-                    at: on.len(),
-                    reason: JSONParseErrorReason::ExpectedEndOfMultilineComment,
+                    at: idx,
+                    reason: JSONParseErrorReason::TooManyValues,
+                    path: None,
                 });
             }
         }
-        State::EndOfValue | State::ExpectingValue => {
-            if !key_chain.is_empty() {
+        Ok(())
+    }
+
+    fn count_key(&mut self, idx: usize) -> Result<(), JSONParseError> {
+        if let (Some(max), Some(count)) =
+            (self.limits.max_keys_per_object, self.key_counts.last_mut())
+        {
+            *count += 1;
+            if *count > max {
                 return Err(JSONParseError {
-                    at: on.len(),
-                    reason: JSONParseErrorReason::ExpectedBracket,
+                    at: idx,
+                    reason: JSONParseErrorReason::TooManyKeys,
+                    path: None,
                 });
             }
         }
-        State::InObject => {
+        Ok(())
+    }
+
+    fn check_string_len(&self, start: usize, length: usize) -> Result<(), JSONParseError> {
+        if self.limits.max_string_len.is_some_and(|max| length > max) {
             return Err(JSONParseError {
-                at: on.len(),
-                reason: JSONParseErrorReason::ExpectedBracket,
+                at: start,
+                reason: JSONParseErrorReason::StringTooLong,
+                path: None,
             });
         }
-        State::NumberValue { start } => {
-            // TODO actual number handing
-            let _result = cb(&key_chain, RootJSONValue::Number(&on[start..]));
-        }
-        State::TrueFalseNull { start: _ } => {
+        Ok(())
+    }
+
+    fn end_of_value<K: Segment>(
+        &mut self,
+        idx: usize,
+        chr: char,
+        key_chain: &mut impl KeyStack<K>,
+    ) -> Result<Step, JSONParseError> {
+        if self.require_end_of_input && self.containers.is_empty() {
+            if chr.is_whitespace() || self.start_comment(idx, chr) {
+                return Ok(Step::Continue);
+            }
             return Err(JSONParseError {
-                at: on.len(),
-                reason: JSONParseErrorReason::ExpectedTrueFalseNull,
-            })
+                at: idx,
+                reason: JSONParseErrorReason::UnexpectedTrailingContent { found: chr },
+                path: None,
+            });
+        }
+        match (chr, self.containers.last()) {
+            (',', Some(Container::Array)) => {
+                if let Some(i) = key_chain.last_mut().and_then(K::as_index) {
+                    *i += 1;
+                }
+                self.state = State::ExpectingValue;
+            }
+            (',', Some(Container::Object)) => {
+                key_chain.pop();
+                self.state = State::InObject;
+            }
+            ('}', Some(Container::Object)) => {
+                key_chain.pop();
+                self.close_container();
+                return Ok(Step::EndObject { at: idx });
+            }
+            (']', Some(Container::Array)) => {
+                key_chain.pop();
+                self.close_container();
+                return Ok(Step::EndArray { at: idx });
+            }
+            ('}' | ']', _) => {
+                return Err(JSONParseError {
+                    at: idx,
+                    reason: JSONParseErrorReason::ExpectedBracket { found: Some(chr) },
+                    path: None,
+                });
+            }
+            (chr, _) => {
+                if !chr.is_whitespace() && !self.start_comment(idx, chr) {
+                    return Err(JSONParseError {
+                        at: idx,
+                        reason: JSONParseErrorReason::ExpectedEndOfValue { found: Some(chr) },
+                        path: None,
+                    });
+                }
+            }
         }
+        Ok(Step::Continue)
     }
 
-    Ok(on.len())
+    /// Call once the input has ended, with its length. Returns a value for a number that ran
+    /// to the end of the input, in that case call again
+    ///
+    /// # Errors
+    /// Returns an error if the input ended early
+    pub fn finish(&mut self, len: usize) -> Result<Option<Step>, JSONParseError> {
+        match self.state {
+            State::InKey { .. } | State::StringValue { .. } => Err(JSONParseError {
+                at: len,
+                reason: JSONParseErrorReason::UnterminatedString,
+                path: None,
+            }),
+            State::Colon | State::InIdentifierKey { .. } => Err(JSONParseError {
+                at: len,
+                reason: JSONParseErrorReason::ExpectedColon { found: None },
+                path: None,
+            }),
+            State::Comment {
+                multiline,
+                hash,
+                start,
+                ..
+            } => {
+                if multiline {
+                    Err(JSONParseError {
+                        at: len,
+                        reason: JSONParseErrorReason::ExpectedEndOfMultilineComment,
+                        path: None,
+                    })
+                } else {
+                    self.state = self.resume;
+                    Ok(Some(Step::Comment {
+                        start,
+                        end: len,
+                        style: if hash {
+                            CommentStyle::Hash
+                        } else {
+                            CommentStyle::Line
+                        },
+                    }))
+                }
+            }
+            State::ExpectingValue if self.containers.is_empty() && self.require_end_of_input => {
+                // No root value
+                Err(JSONParseError {
+                    at: len,
+                    reason: JSONParseErrorReason::ExpectedValue { found: None },
+                    path: None,
+                })
+            }
+            State::EndOfValue | State::ExpectingValue => {
+                if self.containers.is_empty() {
+                    Ok(None)
+                } else {
+                    Err(JSONParseError {
+                        at: len,
+                        reason: JSONParseErrorReason::UnexpectedEndOfInput,
+                        path: None,
+                    })
+                }
+            }
+            State::ObjectStart | State::InObject | State::ArrayStart => Err(JSONParseError {
+                at: len,
+                reason: JSONParseErrorReason::UnexpectedEndOfInput,
+                path: None,
+            }),
+            State::NumberValue {
+                part, separator, ..
+            } if separator || !part.is_complete(self.lenient_numbers) => Err(JSONParseError {
+                at: len,
+                reason: JSONParseErrorReason::InvalidNumber,
+                path: None,
+            }),
+            State::NumberValue { start, .. } => {
+                self.state = State::EndOfValue;
+                Ok(Some(Step::Value {
+                    kind: ValueKind::Number,
+                    start,
+                    end: len,
+                    retry: false,
+                }))
+            }
+            State::TrueFalseNull { .. } => Err(JSONParseError {
+                at: len,
+                reason: JSONParseErrorReason::ExpectedTrueFalseNull { found: None },
+                path: None,
+            }),
+        }
+    }
 }
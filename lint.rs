@@ -0,0 +1,157 @@
+//! Style checks for JSONC and JSON5 configuration files
+
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    is_valid, tokenize, try_unescape_string_content, unescape_string_content, JSONParseError,
+    ParseOptions, Span, TokenKind,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// A key which has already appeared in the same object. Parsing gives the last value
+    DuplicateKey,
+    /// A comma after the last member or item
+    TrailingComma,
+    /// A JSON5 or [`ParseOptions::lenient_numbers`] number (hexadecimal, a leading `+`, a
+    /// leading or trailing `.`, `_`, `Infinity` or `NaN`) which plain JSON does not allow
+    NonStandardNumber,
+    /// A key which sorts before the key before it in the same object
+    UnsortedKey,
+    /// An escape which JSON does not have (such as `\x41` or `\'`) or a lone surrogate
+    SuspiciousEscape,
+}
+
+/// Found by [`lint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub span: Span,
+}
+
+/// Which checks [`lint`] does. All apart from [`LintOptions::unsorted_keys`] are on by default
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct LintOptions {
+    pub duplicate_keys: bool,
+    pub trailing_commas: bool,
+    pub non_standard_numbers: bool,
+    pub unsorted_keys: bool,
+    pub suspicious_escapes: bool,
+    /// Options for parsing the source. [`ParseOptions::JSON5`] with
+    /// [`ParseOptions::lenient_numbers`] by default, so that the extensions are reported
+    /// rather than being errors
+    pub parse: ParseOptions,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            duplicate_keys: true,
+            trailing_commas: true,
+            non_standard_numbers: true,
+            unsorted_keys: false,
+            suspicious_escapes: true,
+            parse: ParseOptions {
+                lenient_numbers: true,
+                ..ParseOptions::JSON5
+            },
+        }
+    }
+}
+
+/// Checks `source` for likely mistakes and non standard syntax. Lints are in source order
+///
+/// ```
+/// use simple_json_parser::{lint, LintKind, LintOptions};
+///
+/// let source = r#"{ "a": 1, "b": 0x10, "a": 2, }"#;
+/// let lints = lint(source, &LintOptions::default()).unwrap();
+/// let kinds = lints.iter().map(|lint| lint.kind).collect::<Vec<_>>();
+/// assert_eq!(
+///     kinds,
+///     [LintKind::NonStandardNumber, LintKind::DuplicateKey, LintKind::TrailingComma]
+/// );
+/// assert_eq!(lints[1].span.slice(source), "\"a\"");
+/// ```
+///
+/// # Errors
+/// Returns an error if the source is not valid with [`LintOptions::parse`]
+pub fn lint(source: &str, options: &LintOptions) -> Result<Vec<Lint>, JSONParseError> {
+    let mut lints = Vec::new();
+    // Keys so far in each open object (or `None` for arrays)
+    let mut containers: Vec<Option<Vec<String>>> = Vec::new();
+    let mut comma: Option<Span> = None;
+
+    for token in tokenize(source, &options.parse) {
+        let token = token?;
+        let mut push = |kind, span| lints.push(Lint { kind, span });
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::Comment(_) => continue,
+            TokenKind::EndObject | TokenKind::EndArray => {
+                if let Some(span) = comma.filter(|_| options.trailing_commas) {
+                    push(LintKind::TrailingComma, span);
+                }
+                containers.pop();
+            }
+            TokenKind::StartObject => containers.push(Some(Vec::new())),
+            TokenKind::StartArray => containers.push(None),
+            TokenKind::Key => {
+                let content = string_content(token.text);
+                if options.suspicious_escapes {
+                    check_escapes(content, token.span.start + 1, &mut push);
+                }
+                let key = unescape_string_content(content).into_owned();
+                if let Some(Some(keys)) = containers.last_mut() {
+                    if options.duplicate_keys && keys.contains(&key) {
+                        push(LintKind::DuplicateKey, token.span);
+                    } else if options.unsorted_keys && keys.last().is_some_and(|last| key < *last) {
+                        push(LintKind::UnsortedKey, token.span);
+                    }
+                    keys.push(key);
+                }
+            }
+            TokenKind::String if options.suspicious_escapes => {
+                check_escapes(string_content(token.text), token.span.start + 1, &mut push);
+            }
+            TokenKind::Number
+                if options.non_standard_numbers
+                    && is_valid(token.text, &ParseOptions::STRICT_JSON).is_err() =>
+            {
+                push(LintKind::NonStandardNumber, token.span);
+            }
+            _ => {}
+        }
+        comma = (token.kind == TokenKind::Comma).then_some(token.span);
+    }
+    Ok(lints)
+}
+
+/// Without the quotes, if it has them (JSON5 keys may not)
+fn string_content(text: &str) -> &str {
+    match text.chars().next() {
+        Some('"' | '\'') => &text[1..text.len() - 1],
+        _ => text,
+    }
+}
+
+/// `offset` is the position of `content` in the source
+fn check_escapes(content: &str, offset: usize, push: &mut impl FnMut(LintKind, Span)) {
+    let mut from = 0;
+    while let Err(err) = try_unescape_string_content(&content[from..]) {
+        let start = from + err.at;
+        // The backslash and the character after it
+        let end = content[start + 1..]
+            .chars()
+            .next()
+            .map_or(start + 1, |chr| start + 1 + chr.len_utf8());
+        push(
+            LintKind::SuspiciousEscape,
+            Span {
+                start: offset + start,
+                end: offset + end,
+            },
+        );
+        from = end;
+    }
+}
@@ -0,0 +1,114 @@
+//! Deep merging documents, for layering configuration
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::{
+    minify,
+    patch::{children, write_key},
+    unescape_string_content, Container, JSONFormatError, JSONKey, JSONParseError,
+};
+
+/// How [`merge`] combines arrays which are in both documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The array from the overlay replaces the base array
+    #[default]
+    Replace,
+    /// The items of the overlay array are added after the items of the base array
+    Concat,
+}
+
+/// Merges `overlay` on top of `base`, without whitespace or comments. Objects in both are
+/// merged recursively, keeping the order of `base` with new keys from `overlay` after. For
+/// anything else the value from `overlay` wins (including `null`), apart from arrays with
+/// [`MergeStrategy::Concat`]. Only the members of the objects being merged are collected,
+/// values are copied from the sources.
+///
+/// ```
+/// use simple_json_parser::{merge, MergeStrategy};
+///
+/// let defaults = r#"{ "port": 80, "log": { "level": "info" }, "plugins": ["a"] }"#;
+/// let user = r#"{
+///     // local overrides
+///     "log": { "level": "debug" },
+///     "plugins": ["b"]
+/// }"#;
+/// assert_eq!(
+///     merge(defaults, user, MergeStrategy::Replace).unwrap(),
+///     r#"{"port":80,"log":{"level":"debug"},"plugins":["b"]}"#
+/// );
+/// assert_eq!(
+///     merge(defaults, user, MergeStrategy::Concat).unwrap(),
+///     r#"{"port":80,"log":{"level":"debug"},"plugins":["a","b"]}"#
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if either input is not valid JSON
+pub fn merge(base: &str, overlay: &str, strategy: MergeStrategy) -> Result<String, JSONParseError> {
+    let mut out = String::new();
+    match merge_values(base, overlay, strategy, &mut out) {
+        Ok(()) => Ok(out),
+        Err(JSONFormatError::Parse(err)) => Err(err),
+        Err(JSONFormatError::Format(_)) => unreachable!("writing to a `String` does not fail"),
+    }
+}
+
+fn merge_values(
+    base: &str,
+    overlay: &str,
+    strategy: MergeStrategy,
+    out: &mut impl Write,
+) -> Result<(), JSONFormatError> {
+    match (children(base)?, children(overlay)?) {
+        (Some((Container::Object, base)), Some((Container::Object, overlay))) => {
+            let same_key = |a: &JSONKey, b: &JSONKey| match (a, b) {
+                (JSONKey::Slice(a), JSONKey::Slice(b)) => {
+                    unescape_string_content(a) == unescape_string_content(b)
+                }
+                _ => false,
+            };
+            out.write_char('{')?;
+            let mut first = true;
+            for (key, value) in &base {
+                let JSONKey::Slice(key_source) = key else {
+                    continue;
+                };
+                write_key(out, key_source, &mut first)?;
+                match overlay
+                    .iter()
+                    .find(|(overlay_key, _)| same_key(key, overlay_key))
+                {
+                    Some((_, overlay_value)) => merge_values(value, overlay_value, strategy, out)?,
+                    None => minify(value, out)?,
+                }
+            }
+            for (key, value) in &overlay {
+                let JSONKey::Slice(key_source) = key else {
+                    continue;
+                };
+                if !base.iter().any(|(base_key, _)| same_key(key, base_key)) {
+                    write_key(out, key_source, &mut first)?;
+                    minify(value, out)?;
+                }
+            }
+            out.write_char('}')?;
+            Ok(())
+        }
+        (Some((Container::Array, base)), Some((Container::Array, overlay)))
+            if strategy == MergeStrategy::Concat =>
+        {
+            out.write_char('[')?;
+            for (idx, (_, value)) in base.iter().chain(&overlay).enumerate() {
+                if idx > 0 {
+                    out.write_char(',')?;
+                }
+                minify(value, out)?;
+            }
+            out.write_char(']')?;
+            Ok(())
+        }
+        _ => minify(overlay, out),
+    }
+}
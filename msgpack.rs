@@ -0,0 +1,207 @@
+//! Converting JSON to [MessagePack](https://github.com/msgpack/msgpack/blob/master/spec.md)
+
+use std::io::{Error, ErrorKind, Write};
+
+use super::{
+    transcode::{big_integer, transcode, Encoder},
+    unescape_string_content, Container, JSONReadError, ParseOptions, RootJSONValue,
+};
+
+const NIL: u8 = 0xC0;
+const FALSE: u8 = 0xC2;
+const TRUE: u8 = 0xC3;
+const FLOAT64: u8 = 0xCB;
+const UINT8: u8 = 0xCC;
+const UINT16: u8 = 0xCD;
+const UINT32: u8 = 0xCE;
+const UINT64: u8 = 0xCF;
+const INT8: u8 = 0xD0;
+const INT16: u8 = 0xD1;
+const INT32: u8 = 0xD2;
+const INT64: u8 = 0xD3;
+
+/// The `fix*`, 8 (strings only), 16 and 32 bit length markers
+struct LengthMarkers {
+    fixed: u8,
+    fixed_max: u8,
+    with_u8: Option<u8>,
+    with_u16: u8,
+    with_u32: u8,
+}
+
+const STRING: LengthMarkers = LengthMarkers {
+    fixed: 0xA0,
+    fixed_max: 31,
+    with_u8: Some(0xD9),
+    with_u16: 0xDA,
+    with_u32: 0xDB,
+};
+const ARRAY: LengthMarkers = LengthMarkers {
+    fixed: 0x90,
+    fixed_max: 15,
+    with_u8: None,
+    with_u16: 0xDC,
+    with_u32: 0xDD,
+};
+const MAP: LengthMarkers = LengthMarkers {
+    fixed: 0x80,
+    fixed_max: 15,
+    with_u8: None,
+    with_u16: 0xDE,
+    with_u32: 0xDF,
+};
+
+/// Parses `source` and writes it to `out` as [MessagePack](https://msgpack.org), one event at
+/// a time, so no tree is built. The format needs the length of maps and arrays before their contents, so this
+/// first parses `source` to count them (keeping one `usize` per object and array). Keys and
+/// strings are unescaped. Integers (including those beyond `i64` but within `u64`) are
+/// written as integers, in the smallest form, and other numbers as 64 bit floats. Comments
+/// are skipped
+///
+/// ```
+/// use simple_json_parser::{json_to_msgpack, ParseOptions};
+///
+/// let mut out = Vec::new();
+/// json_to_msgpack(r#"{ "a": [1, -2, true] }"#, &mut out, &ParseOptions::default()).unwrap();
+/// assert_eq!(out, [0x81, 0xA1, b'a', 0x93, 0x01, 0xFE, 0xC3]);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or it tries to parse invalid JSON input. Strings, objects
+/// or arrays longer than `u32::MAX` give an [`ErrorKind::InvalidData`] error
+pub fn json_to_msgpack(
+    source: &str,
+    out: &mut impl Write,
+    options: &ParseOptions,
+) -> Result<(), JSONReadError> {
+    transcode::<MessagePack>(source, out, options)
+}
+
+struct MessagePack;
+
+impl Encoder for MessagePack {
+    const NEEDS_LENGTHS: bool = true;
+
+    fn start(
+        out: &mut impl Write,
+        container: Container,
+        length: Option<usize>,
+    ) -> std::io::Result<()> {
+        let length = length.expect("lengths are counted");
+        match container {
+            Container::Object => write_length(out, &MAP, length),
+            Container::Array => write_length(out, &ARRAY, length),
+        }
+    }
+
+    fn end(_out: &mut impl Write, _container: Container) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn key(out: &mut impl Write, key: &str) -> std::io::Result<()> {
+        write_str(out, key)
+    }
+
+    fn value(out: &mut impl Write, value: &RootJSONValue) -> std::io::Result<()> {
+        match *value {
+            RootJSONValue::String(value) => write_str(out, &unescape_string_content(value)),
+            RootJSONValue::Integer(value) => write_integer(out, value),
+            RootJSONValue::Float(value) | RootJSONValue::NonFinite(value) => {
+                write_float(out, value)
+            }
+            RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                let integer = big_integer(value).and_then(|(negative, magnitude)| {
+                    if negative {
+                        0i64.checked_sub_unsigned(magnitude)
+                            .map(|value| write_integer(out, value))
+                    } else {
+                        Some(write_unsigned(out, magnitude))
+                    }
+                });
+                integer.unwrap_or_else(|| write_float(out, value.parse().unwrap_or(f64::NAN)))
+            }
+            RootJSONValue::Boolean(value) => out.write_all(&[if value { TRUE } else { FALSE }]),
+            RootJSONValue::Null => out.write_all(&[NIL]),
+            RootJSONValue::EmptyObject => write_length(out, &MAP, 0),
+            RootJSONValue::EmptyArray => write_length(out, &ARRAY, 0),
+        }
+    }
+}
+
+fn write_integer(out: &mut impl Write, value: i64) -> std::io::Result<()> {
+    if let Ok(value) = u64::try_from(value) {
+        write_unsigned(out, value)
+    } else if let Ok(value) = i8::try_from(value) {
+        if value >= -32 {
+            // Negative fixint
+            out.write_all(&value.to_be_bytes())
+        } else {
+            out.write_all(&[INT8])?;
+            out.write_all(&value.to_be_bytes())
+        }
+    } else if let Ok(value) = i16::try_from(value) {
+        out.write_all(&[INT16])?;
+        out.write_all(&value.to_be_bytes())
+    } else if let Ok(value) = i32::try_from(value) {
+        out.write_all(&[INT32])?;
+        out.write_all(&value.to_be_bytes())
+    } else {
+        out.write_all(&[INT64])?;
+        out.write_all(&value.to_be_bytes())
+    }
+}
+
+fn write_unsigned(out: &mut impl Write, value: u64) -> std::io::Result<()> {
+    if let Ok(value) = u8::try_from(value) {
+        if value <= 0x7F {
+            // Positive fixint
+            out.write_all(&[value])
+        } else {
+            out.write_all(&[UINT8, value])
+        }
+    } else if let Ok(value) = u16::try_from(value) {
+        out.write_all(&[UINT16])?;
+        out.write_all(&value.to_be_bytes())
+    } else if let Ok(value) = u32::try_from(value) {
+        out.write_all(&[UINT32])?;
+        out.write_all(&value.to_be_bytes())
+    } else {
+        out.write_all(&[UINT64])?;
+        out.write_all(&value.to_be_bytes())
+    }
+}
+
+fn write_float(out: &mut impl Write, value: f64) -> std::io::Result<()> {
+    out.write_all(&[FLOAT64])?;
+    out.write_all(&value.to_be_bytes())
+}
+
+fn write_str(out: &mut impl Write, value: &str) -> std::io::Result<()> {
+    write_length(out, &STRING, value.len())?;
+    out.write_all(value.as_bytes())
+}
+
+fn write_length(
+    out: &mut impl Write,
+    markers: &LengthMarkers,
+    length: usize,
+) -> std::io::Result<()> {
+    let small = u8::try_from(length).ok();
+    if let Some(length) = small.filter(|length| *length <= markers.fixed_max) {
+        // Fits in the low bits of the marker
+        out.write_all(&[markers.fixed | length])
+    } else if let (Some(marker), Some(length)) = (markers.with_u8, small) {
+        out.write_all(&[marker, length])
+    } else if let Ok(length) = u16::try_from(length) {
+        out.write_all(&[markers.with_u16])?;
+        out.write_all(&length.to_be_bytes())
+    } else if let Ok(length) = u32::try_from(length) {
+        out.write_all(&[markers.with_u32])?;
+        out.write_all(&length.to_be_bytes())
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "too long for MessagePack",
+        ))
+    }
+}
@@ -0,0 +1,99 @@
+//! Inputs with several top-level values, such as concatenated JSON or a `<script>` tag
+//! followed by more JSON
+
+use alloc::vec::Vec;
+
+use super::{JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue, Span, Step};
+
+/// Parses top-level values one after another until the end of the input. The callback
+/// receives the index of the document along with the keys and value as in
+/// [`crate::parse`]. Documents do not need anything between them (`{}{}` is two
+/// documents). [`ParseOptions::exit_on_first_value`] and
+/// [`ParseOptions::require_end_of_input`] are not used.
+///
+/// Returns the span of each document.
+///
+/// ```
+/// use simple_json_parser::{parse_all, ParseOptions, RootJSONValue};
+///
+/// let source = "{\"id\": 1}\n{\"id\": 2} [3]";
+/// let mut ids = Vec::new();
+/// let documents = parse_all(
+///     source,
+///     |document, _keys, value| {
+///         if let RootJSONValue::Number(id) = value {
+///             ids.push((document, id));
+///         }
+///     },
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(ids, [(0, "1"), (1, "2"), (2, "3")]);
+/// assert_eq!(documents[1].slice(source), "{\"id\": 2}");
+/// ```
+///
+/// # Errors
+/// Returns an error if a document is not valid JSON, after the callback has received the
+/// values before it
+pub fn parse_all<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(usize, &'b [JSONKey<'a>], RootJSONValue<'a>),
+    options: &ParseOptions,
+) -> Result<Vec<Span>, JSONParseError> {
+    // Otherwise the whitespace after the last document is an error
+    let options = &ParseOptions {
+        require_end_of_input: false,
+        ..options.clone()
+    };
+    let mut documents = Vec::new();
+    let mut offset = 0;
+
+    loop {
+        let index = documents.len();
+        let shift = |err: JSONParseError| JSONParseError {
+            at: offset + err.at,
+            ..err
+        };
+        let mut reader = JsonReader::new(&on[offset..], options);
+        let mut start = None;
+        let end = loop {
+            // Only whitespace and comments left
+            let Some(step) = reader.next_step().map_err(shift)? else {
+                return Ok(documents);
+            };
+            match step {
+                Step::Continue | Step::Comment { .. } | Step::Key { .. } => {}
+                Step::StartObject { at } | Step::StartArray { at } => {
+                    start.get_or_insert(at);
+                }
+                Step::EndObject { at } | Step::EndArray { at } => {
+                    if reader.machine.is_at_root() {
+                        break at + 1;
+                    }
+                }
+                Step::Value {
+                    kind,
+                    start: value_start,
+                    end,
+                    ..
+                } => {
+                    start.get_or_insert(value_start);
+                    cb(
+                        index,
+                        &reader.key_chain,
+                        reader.value(kind, value_start, end),
+                    );
+                    if reader.machine.is_at_root() {
+                        break end;
+                    }
+                }
+            }
+        };
+
+        documents.push(Span {
+            start: offset + start.unwrap_or(0),
+            end: offset + end,
+        });
+        offset += end;
+    }
+}
@@ -0,0 +1,243 @@
+//! Validating the items of a large top level array on multiple threads
+
+use alloc::{string::String, vec, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use rayon::prelude::*;
+
+use super::{
+    parse_with_recovery,
+    skip::{comment_end, skip_whitespace_and_comments, string_end, CommentStyles},
+    JSONEvent, JSONParseError, JSONParseErrorReason, JsonReader, Limits, ParseOptions, Span,
+};
+
+/// Checks a document which is a top level array (for example a large export), validating
+/// its items in parallel. Returns all errors found, sorted by position, with at most one error
+/// for each item. Items are found with a quick scan which only looks at brackets, strings and
+/// comments, then each one is parsed on its own. If the document is not an array (or its
+/// brackets do not match up) it falls back to [`crate::parse_with_recovery`].
+/// [`ParseOptions::progress`] is only used in that case.
+///
+/// [`Limits::max_bytes`] and [`Limits::max_total_values`] apply to the whole document.
+/// [`ParseOptions::cancellation`] is checked before items which pass a multiple of its
+/// interval, and inside items as they are parsed. Once cancelled only the
+/// [`JSONParseErrorReason::Cancelled`] error is returned
+///
+/// ```
+/// use simple_json_parser::{validate_array_parallel, ParseOptions};
+///
+/// let errors = validate_array_parallel(r#"[{"a": 1}, {"a": }, [2, 3], tru]"#, &ParseOptions::default());
+/// assert_eq!(errors.iter().map(|err| err.at).collect::<Vec<_>>(), [17, 31]);
+/// ```
+#[must_use]
+pub fn validate_array_parallel(on: &str, options: &ParseOptions) -> Vec<JSONParseError> {
+    if let Some(max_bytes) = options.limits.max_bytes.filter(|max| on.len() > *max) {
+        return vec![JSONParseError {
+            at: max_bytes,
+            reason: JSONParseErrorReason::InputTooLarge,
+            path: None,
+        }];
+    }
+    let Some((open, close, items)) = split_items(on, options) else {
+        return parse_with_recovery(on, |_, _| {}, options);
+    };
+
+    let item_options = ParseOptions {
+        exit_on_first_value: false,
+        allow_scalar_root: true,
+        max_depth: options
+            .max_depth
+            .map(|max_depth| max_depth.saturating_sub(1)),
+        progress: None,
+        // Counted across items below
+        limits: Limits {
+            max_bytes: None,
+            max_total_values: None,
+            ..options.limits
+        },
+        ..options.clone()
+    };
+    let cancelled = AtomicBool::new(false);
+    let last = items.len().saturating_sub(1);
+    let results: Vec<(usize, Option<JSONParseError>)> = items
+        .par_iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            if cancelled.load(Ordering::Relaxed) {
+                return (0, None);
+            }
+            if let Some(cancellation) = &options.cancellation {
+                let passes_check = item.start / cancellation.every != item.end / cancellation.every;
+                if passes_check && (cancellation.is_cancelled)() {
+                    cancelled.store(true, Ordering::Relaxed);
+                    let err = JSONParseError {
+                        at: item.start,
+                        reason: JSONParseErrorReason::Cancelled,
+                        path: None,
+                    };
+                    return (0, Some(err));
+                }
+            }
+            match validate_item(item.slice(on), &item_options) {
+                Ok(0) if idx == last && (idx == 0 || options.allow_trailing_commas) => (0, None),
+                Ok(0) => {
+                    let err = JSONParseError {
+                        at: item.end,
+                        reason: JSONParseErrorReason::ExpectedValue { found: None },
+                        path: None,
+                    };
+                    (0, Some(err))
+                }
+                Ok(values) => (values, None),
+                Err(err) => {
+                    if matches!(err.reason, JSONParseErrorReason::Cancelled) {
+                        cancelled.store(true, Ordering::Relaxed);
+                    }
+                    let err = JSONParseError {
+                        at: item.start + err.at,
+                        ..err
+                    };
+                    (0, Some(err))
+                }
+            }
+        })
+        .collect();
+
+    let mut errors: Vec<JSONParseError> =
+        results.iter().filter_map(|(_, err)| err.clone()).collect();
+    if cancelled.into_inner() {
+        errors.retain(|err| matches!(err.reason, JSONParseErrorReason::Cancelled));
+        errors.sort_by_key(|err| err.at);
+        errors.truncate(1);
+        return errors;
+    }
+
+    if let Some(max_values) = options.limits.max_total_values {
+        // The array itself is a value
+        let mut remaining = max_values.saturating_sub(1);
+        for (item, (values, _)) in items.iter().zip(&results) {
+            if *values > remaining {
+                errors.push(too_many_values(on, *item, remaining, &item_options));
+                break;
+            }
+            remaining -= values;
+        }
+    }
+
+    errors.extend(validate_frame(on, open, close, options));
+    errors.sort_by_key(|err| err.at);
+    errors
+}
+
+/// Checks the whitespace and comments around the brackets, and anything after
+fn validate_frame(
+    on: &str,
+    open: usize,
+    close: usize,
+    options: &ParseOptions,
+) -> Option<JSONParseError> {
+    let mut frame = String::with_capacity(open + 1 + on.len() - close);
+    frame.push_str(&on[..=open]);
+    frame.push_str(&on[close..]);
+    let frame_options = ParseOptions {
+        max_depth: None,
+        progress: None,
+        ..options.clone()
+    };
+    let err = validate_item(&frame, &frame_options).err()?;
+    let at = if err.at > open {
+        err.at - (open + 1) + close
+    } else {
+        err.at
+    };
+    Some(JSONParseError { at, ..err })
+}
+
+/// Where the item goes over the values left, by parsing it again with that limit
+fn too_many_values(
+    on: &str,
+    item: Span,
+    remaining: usize,
+    item_options: &ParseOptions,
+) -> JSONParseError {
+    let options = ParseOptions {
+        limits: Limits {
+            max_total_values: Some(remaining),
+            ..item_options.limits
+        },
+        ..item_options.clone()
+    };
+    let at = validate_item(item.slice(on), &options)
+        .err()
+        .map_or(item.start, |err| item.start + err.at);
+    JSONParseError {
+        at,
+        reason: JSONParseErrorReason::TooManyValues,
+        path: None,
+    }
+}
+
+/// Number of values, including objects and arrays
+fn validate_item(item: &str, options: &ParseOptions) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(item, options);
+    let mut values = 0;
+    while let Some(event) = reader.next_event()? {
+        if matches!(
+            event,
+            JSONEvent::Value(_) | JSONEvent::StartObject | JSONEvent::StartArray
+        ) {
+            values += 1;
+        }
+    }
+    Ok(values)
+}
+
+/// Positions of the opening and closing brackets and the source between the commas. `None`
+/// if the document does not look like an array
+fn split_items(on: &str, options: &ParseOptions) -> Option<(usize, usize, Vec<Span>)> {
+    let bytes = on.as_bytes();
+    let comments = CommentStyles::new(options);
+    let mut idx = skip_whitespace_and_comments(bytes, 0, comments);
+    if bytes.get(idx) != Some(&b'[') {
+        return None;
+    }
+    let open = idx;
+    idx += 1;
+    let mut depth = 0usize;
+    let mut items = Vec::new();
+    let mut item_start = idx;
+
+    loop {
+        let byte = *bytes.get(idx)?;
+        match byte {
+            b'"' | b'\'' if byte == b'"' || options.json5 => {
+                idx = string_end(bytes, idx)?;
+                continue;
+            }
+            b'/' | b'#' if options.allow_comments => {
+                let end = comment_end(bytes, idx, comments)?;
+                idx = end.max(idx + 1) - 1;
+            }
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' if depth > 0 => depth -= 1,
+            b']' => {
+                items.push(Span {
+                    start: item_start,
+                    end: idx,
+                });
+                return Some((open, idx, items));
+            }
+            b',' if depth == 0 => {
+                items.push(Span {
+                    start: item_start,
+                    end: idx,
+                });
+                item_start = idx + 1;
+            }
+            // Mismatched
+            b'}' => return None,
+            _ => {}
+        }
+        idx += 1;
+    }
+}
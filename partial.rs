@@ -0,0 +1,126 @@
+//! Parsing documents which have been cut short
+
+use alloc::vec::Vec;
+
+use super::{
+    Container, JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue, State, Step,
+    ValueKind,
+};
+
+/// Needed to complete a document, from [`parse_partial`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Missing {
+    /// The rest of a key (or its closing quote), then a colon and value
+    Key,
+    /// A colon, then a value
+    Colon,
+    Value,
+    /// The rest of a string (or its closing quote)
+    String,
+    /// A number at the end inside an object or array. It is not passed to the callback
+    /// as it may have been cut short
+    Number,
+    /// The rest of `true`, `false` or `null`
+    Literal,
+    /// `*/`
+    EndOfComment,
+    EndOfObject,
+    EndOfArray,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Completeness {
+    Complete,
+    /// Innermost first, so `{"a": ["b` is missing
+    /// `[Missing::String, Missing::EndOfArray, Missing::EndOfObject]`
+    Incomplete {
+        missing: Vec<Missing>,
+    },
+}
+
+/// Like [`crate::parse`] but if the input ends early, returns what is missing rather than an
+/// error. All complete values before the end are passed to the callback.
+///
+/// ```
+/// use simple_json_parser::{parse_partial, Completeness, Missing, ParseOptions};
+///
+/// let mut values = 0;
+/// let result = parse_partial(r#"{"a": 1, "b": [true, "tr"#, |_, _| values += 1, &ParseOptions::default());
+/// assert_eq!(result.unwrap(), Completeness::Incomplete {
+///     missing: vec![Missing::String, Missing::EndOfArray, Missing::EndOfObject]
+/// });
+/// assert_eq!(values, 2);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input (before the end)
+pub fn parse_partial<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+    options: &ParseOptions,
+) -> Result<Completeness, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+
+    loop {
+        match reader.next_step() {
+            Ok(Some(Step::Value {
+                kind, start, end, ..
+            })) => {
+                if let (ValueKind::Number, true) =
+                    (kind, end == on.len() && !reader.machine.is_at_root())
+                {
+                    let mut missing = missing(&reader);
+                    missing.insert(0, Missing::Number);
+                    return Ok(Completeness::Incomplete { missing });
+                }
+                let value = reader.value(kind, start, end);
+                cb(&reader.key_chain, value);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(Completeness::Complete),
+            Err(err) if err.at == on.len() => {
+                let missing = missing(&reader);
+                return Ok(Completeness::Incomplete { missing });
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn missing(reader: &JsonReader<'_>) -> Vec<Missing> {
+    let machine = &reader.machine;
+    let mut missing = Vec::new();
+
+    let state = if let State::Comment { multiline, .. } = machine.state {
+        if multiline {
+            missing.push(Missing::EndOfComment);
+        }
+        machine.resume
+    } else {
+        machine.state
+    };
+    match state {
+        State::InKey { .. } | State::InIdentifierKey { .. } | State::InObject => {
+            missing.push(Missing::Key);
+        }
+        State::Colon => missing.push(Missing::Colon),
+        State::ExpectingValue if !machine.is_at_root() => missing.push(Missing::Value),
+        State::StringValue { .. } => missing.push(Missing::String),
+        State::NumberValue { .. } => missing.push(Missing::Number),
+        State::TrueFalseNull { .. } => missing.push(Missing::Literal),
+        _ => {}
+    }
+
+    missing.extend(
+        machine
+            .containers
+            .iter()
+            .rev()
+            .map(|container| match container {
+                Container::Object => Missing::EndOfObject,
+                Container::Array => Missing::EndOfArray,
+            }),
+    );
+
+    missing
+}
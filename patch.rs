@@ -0,0 +1,543 @@
+//! [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch, applied by editing the
+//! source text, and [RFC 7386](https://www.rfc-editor.org/rfc/rfc7386) JSON Merge Patch
+
+use alloc::{borrow::Cow, borrow::ToOwned, format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{
+    json_equals, minify,
+    pointer::{pointer_segments, segment_matches},
+    unescape_string_content, Container, EqualityOptions, JSONEvent, JSONFormatError, JSONKey,
+    JSONParseError, JsonReader, ParseOptions, RootJSONValue, Span,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JSONPatchError {
+    /// The source or patch is not valid JSON
+    Parse(JSONParseError),
+    /// The patch is not an array of operations, or the operation at this index is missing
+    /// a member or has an unknown `op`
+    InvalidOperation { index: usize },
+    /// The `path` (or `from`) of the operation at this index does not exist
+    NotFound { index: usize },
+    /// A `test` operation at this index did not match
+    TestFailed { index: usize },
+}
+
+impl core::error::Error for JSONPatchError {}
+
+impl core::fmt::Display for JSONPatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            JSONPatchError::Parse(err) => core::fmt::Display::fmt(err, f),
+            JSONPatchError::InvalidOperation { index } => {
+                f.write_fmt(format_args!("JSONPatchError: invalid operation {index}"))
+            }
+            JSONPatchError::NotFound { index } => f.write_fmt(format_args!(
+                "JSONPatchError: path of operation {index} not found"
+            )),
+            JSONPatchError::TestFailed { index } => f.write_fmt(format_args!(
+                "JSONPatchError: test operation {index} failed"
+            )),
+        }
+    }
+}
+
+impl From<JSONParseError> for JSONPatchError {
+    fn from(err: JSONParseError) -> Self {
+        JSONPatchError::Parse(err)
+    }
+}
+
+/// Applies the operations of `patch` to `source` in order. Only the parts of the source
+/// which are changed are rewritten, so formatting and comments elsewhere are kept. Added
+/// values are written as they are in the patch.
+///
+/// ```
+/// use simple_json_parser::apply_patch;
+///
+/// let source = r#"{ "name": "ezno", "tags": ["a"] }"#;
+/// let patch = r#"[
+///     { "op": "replace", "path": "/name", "value": "simple" },
+///     { "op": "add", "path": "/tags/-", "value": "b" },
+///     { "op": "add", "path": "/private", "value": false }
+/// ]"#;
+/// assert_eq!(
+///     apply_patch(source, patch).unwrap(),
+///     r#"{ "name": "simple", "tags": ["a","b"],"private":false }"#
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if either input is not valid JSON or an operation fails, in which case
+/// none of the operations are applied
+pub fn apply_patch(source: &str, patch: &str) -> Result<String, JSONPatchError> {
+    let mut result = source.to_owned();
+    for (index, operation) in operations(patch)?.into_iter().enumerate() {
+        result = operation.apply(&result, index)?;
+    }
+    Ok(result)
+}
+
+#[derive(Default)]
+struct Operation<'a> {
+    op: Option<String>,
+    path: Option<String>,
+    from: Option<String>,
+    /// Source of the value in the patch
+    value: Option<&'a str>,
+}
+
+fn operations(patch: &str) -> Result<Vec<Operation<'_>>, JSONPatchError> {
+    let mut operations: Vec<Operation<'_>> = Vec::new();
+    let mut reader = JsonReader::new(patch, &ParseOptions::default());
+    // Start of the object or array being the `value`
+    let mut value_start = None;
+
+    while let Some(event) = reader.next_event()? {
+        let span = reader.span();
+        let (index, key) = match reader.key_chain() {
+            [] => {
+                if !matches!(event, JSONEvent::StartArray | JSONEvent::EndArray) {
+                    return Err(JSONPatchError::InvalidOperation { index: 0 });
+                }
+                continue;
+            }
+            [JSONKey::Index(index)] => {
+                match event {
+                    JSONEvent::StartObject => operations.push(Operation::default()),
+                    JSONEvent::EndObject | JSONEvent::Comment { .. } => {}
+                    _ => return Err(JSONPatchError::InvalidOperation { index: *index }),
+                }
+                continue;
+            }
+            [JSONKey::Index(index), JSONKey::Slice(key), ..] => (*index, *key),
+            _ => continue,
+        };
+        let Some(operation) = operations.get_mut(index) else {
+            continue;
+        };
+        let key = unescape_string_content(key);
+        let direct = reader.key_chain().len() == 2;
+
+        match (key.as_ref(), event) {
+            ("value", JSONEvent::Value(_)) if direct => {
+                operation.value = Some(span.slice(patch));
+            }
+            ("value", JSONEvent::StartObject | JSONEvent::StartArray) if direct => {
+                value_start = Some(span.start);
+            }
+            ("value", JSONEvent::EndObject | JSONEvent::EndArray) if direct => {
+                operation.value = value_start.take().map(|start| &patch[start..span.end]);
+            }
+            (member @ ("op" | "path" | "from"), JSONEvent::Value(value)) if direct => {
+                let RootJSONValue::String(value) = value else {
+                    return Err(JSONPatchError::InvalidOperation { index });
+                };
+                let value = Some(unescape_string_content(value).into_owned());
+                match member {
+                    "op" => operation.op = value,
+                    "path" => operation.path = value,
+                    _ => operation.from = value,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(operations)
+}
+
+impl Operation<'_> {
+    fn apply(&self, source: &str, index: usize) -> Result<String, JSONPatchError> {
+        let invalid = || JSONPatchError::InvalidOperation { index };
+        let not_found = || JSONPatchError::NotFound { index };
+        let path = self.path.as_deref().ok_or_else(invalid)?;
+        let path = pointer_segments(path).ok_or(JSONPatchError::NotFound { index })?;
+        let from = || -> Result<Vec<Cow<'_, str>>, JSONPatchError> {
+            let from = self
+                .from
+                .as_deref()
+                .ok_or(JSONPatchError::InvalidOperation { index })?;
+            pointer_segments(from).ok_or(JSONPatchError::NotFound { index })
+        };
+        let value = || self.value.ok_or(JSONPatchError::InvalidOperation { index });
+
+        match self.op.as_deref() {
+            Some("add") => add(source, &path, value()?).ok_or_else(not_found),
+            Some("remove") => remove(source, &path)?.ok_or_else(not_found),
+            Some("replace") => {
+                let target = locate(source, &path)?;
+                let span = target.value.ok_or_else(not_found)?;
+                Ok(splice(source, span.start, span.end, value()?))
+            }
+            Some("move") => {
+                let from = from()?;
+                if from == path {
+                    return Ok(source.to_owned());
+                }
+                // Cannot move into itself
+                if path.len() > from.len() && path.starts_with(&from) {
+                    return Err(JSONPatchError::InvalidOperation { index });
+                }
+                let span = locate(source, &from)?.value.ok_or_else(not_found)?;
+                let value = span.slice(source).to_owned();
+                let removed = remove(source, &from)?.ok_or(JSONPatchError::NotFound { index })?;
+                add(&removed, &path, &value).ok_or(JSONPatchError::NotFound { index })
+            }
+            Some("copy") => {
+                let span = locate(source, &from()?)?.value.ok_or_else(not_found)?;
+                add(source, &path, span.slice(source)).ok_or(JSONPatchError::NotFound { index })
+            }
+            Some("test") => {
+                let span = locate(source, &path)?.value.ok_or_else(not_found)?;
+                let options = EqualityOptions {
+                    normalize_numbers: true,
+                };
+                if json_equals(span.slice(source), value()?, options)? {
+                    Ok(source.to_owned())
+                } else {
+                    Err(JSONPatchError::TestFailed { index })
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Where a path is in the source
+#[derive(Default)]
+pub(crate) struct Target {
+    /// Of the value at the path, if it exists
+    pub(crate) value: Option<Span>,
+    /// The key for object members, otherwise the start of the value
+    pub(crate) member_start: usize,
+    pub(crate) parent: Option<Parent>,
+    /// End of the value before the target in the parent
+    pub(crate) previous_end: Option<usize>,
+}
+
+pub(crate) struct Parent {
+    pub(crate) is_object: bool,
+    /// Position after the `{` or `[`
+    pub(crate) inside: usize,
+    /// Position of the `}` or `]`
+    pub(crate) close: usize,
+    pub(crate) children: usize,
+    /// The key for object members, otherwise the start of the value
+    pub(crate) last_child_start: Option<usize>,
+    pub(crate) last_child_end: Option<usize>,
+}
+
+fn locate(source: &str, path: &[Cow<'_, str>]) -> Result<Target, JSONParseError> {
+    locate_with_options(source, path, &ParseOptions::default())
+}
+
+pub(crate) fn locate_with_options(
+    source: &str,
+    path: &[Cow<'_, str>],
+    options: &ParseOptions,
+) -> Result<Target, JSONParseError> {
+    let mut target = Target::default();
+    let mut reader = JsonReader::new(source, options);
+    let Some((last, parent_path)) = path.split_last() else {
+        // The root
+        let mut start = None;
+        while let Some(event) = reader.next_event()? {
+            if !matches!(event, JSONEvent::Comment { .. }) {
+                let span = reader.span();
+                let start = *start.get_or_insert(span.start);
+                target.value = Some(Span {
+                    start,
+                    end: span.end,
+                });
+            }
+        }
+        target.member_start = target.value.map_or(0, |value| value.start);
+        return Ok(target);
+    };
+
+    let matches = |keys: &[JSONKey<'_>], path: &[Cow<'_, str>]| {
+        keys.len() == path.len()
+            && keys
+                .iter()
+                .zip(path)
+                .all(|(key, segment)| segment_matches(key, segment))
+    };
+    let mut value_start = None;
+
+    while let Some(event) = reader.next_event()? {
+        let span = reader.span();
+        let keys = reader.key_chain();
+        if let JSONEvent::Comment { .. } = event {
+            continue;
+        }
+        if matches(keys, parent_path) {
+            match event {
+                JSONEvent::StartObject | JSONEvent::StartArray => {
+                    target.parent = Some(Parent {
+                        is_object: matches!(event, JSONEvent::StartObject),
+                        inside: span.end,
+                        close: span.end,
+                        children: 0,
+                        last_child_start: None,
+                        last_child_end: None,
+                    });
+                }
+                JSONEvent::EndObject | JSONEvent::EndArray => {
+                    if let Some(parent) = target.parent.as_mut() {
+                        parent.close = span.start;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let is_child = keys.len() == path.len() && matches(&keys[..parent_path.len()], parent_path);
+        if !is_child {
+            continue;
+        }
+        let Some(parent) = target.parent.as_mut() else {
+            continue;
+        };
+        let is_target = segment_matches(&keys[parent_path.len()], last);
+        match event {
+            JSONEvent::Key(_) => {
+                parent.last_child_start = Some(span.start);
+                if is_target {
+                    target.member_start = span.start;
+                }
+            }
+            JSONEvent::Value(_) | JSONEvent::StartObject | JSONEvent::StartArray => {
+                parent.children += 1;
+                if !parent.is_object {
+                    parent.last_child_start = Some(span.start);
+                }
+                if is_target {
+                    target.previous_end = parent.last_child_end;
+                    if !parent.is_object {
+                        target.member_start = span.start;
+                    }
+                    if let JSONEvent::Value(_) = event {
+                        target.value = Some(span);
+                    } else {
+                        value_start = Some(span.start);
+                    }
+                }
+                if let JSONEvent::Value(_) = event {
+                    parent.last_child_end = Some(span.end);
+                }
+            }
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                parent.last_child_end = Some(span.end);
+                if let (true, Some(start)) = (is_target, value_start.take()) {
+                    target.value = Some(Span {
+                        start,
+                        end: span.end,
+                    });
+                }
+            }
+            JSONEvent::Comment { .. } => {}
+        }
+    }
+
+    Ok(target)
+}
+
+fn splice(source: &str, start: usize, end: usize, with: &str) -> String {
+    let mut result = String::with_capacity(source.len() + with.len());
+    result.push_str(&source[..start]);
+    result.push_str(with);
+    result.push_str(&source[end..]);
+    result
+}
+
+/// `None` if the parent does not exist or the index is out of bounds
+fn add(source: &str, path: &[Cow<'_, str>], value: &str) -> Option<String> {
+    let target = locate(source, path).ok()?;
+    let Some(parent) = target.parent else {
+        // Replacing the root
+        let span = target.value?;
+        return Some(splice(source, span.start, span.end, value));
+    };
+    let last = path.last()?;
+
+    if parent.is_object {
+        if let Some(span) = target.value {
+            return Some(splice(source, span.start, span.end, value));
+        }
+        let mut member = String::new();
+        crate::JSONWriter::new(&mut member).string(last).ok()?;
+        member.push(':');
+        member.push_str(value);
+        return Some(append(source, &parent, &member));
+    }
+
+    if last == "-" {
+        return Some(append(source, &parent, value));
+    }
+    let index: usize = last.parse().ok()?;
+    if let Some(span) = target.value {
+        Some(splice(source, span.start, span.start, &format!("{value},")))
+    } else if index == parent.children {
+        Some(append(source, &parent, value))
+    } else {
+        None
+    }
+}
+
+/// Adds an item to the end of an object or array
+fn append(source: &str, parent: &Parent, item: &str) -> String {
+    match parent.last_child_end {
+        Some(end) => splice(source, end, end, &format!(",{item}")),
+        None => splice(source, parent.inside, parent.inside, item),
+    }
+}
+
+/// `None` if there is no value at the path
+fn remove(source: &str, path: &[Cow<'_, str>]) -> Result<Option<String>, JSONParseError> {
+    let target = locate(source, path)?;
+    let (Some(span), Some(_)) = (target.value, target.parent) else {
+        return Ok(None);
+    };
+
+    let after = &source[span.end..];
+    let after = after.trim_start();
+    let result = if let Some(rest) = after.strip_prefix(',') {
+        // Also remove the comma and whitespace after it
+        let end = source.len() - rest.trim_start().len();
+        splice(source, target.member_start, end, "")
+    } else {
+        let before = source[..target.member_start].trim_end();
+        let start = before
+            .strip_suffix(',')
+            .map_or(target.member_start, str::len);
+        splice(source, start, span.end, "")
+    };
+    Ok(Some(result))
+}
+
+/// Writes `target` with `patch` merged into it, without whitespace or comments. Members of
+/// `patch` which are objects are merged recursively, `null` members remove the key and
+/// anything else replaces the value. Only the members of the objects being merged are
+/// collected, values are copied from the source.
+///
+/// ```
+/// use simple_json_parser::merge_patch;
+///
+/// let base = r#"{ "port": 80, "log": { "level": "info", "file": "out.log" } }"#;
+/// let local = r#"{ "log": { "level": "debug", "file": null }, "debug": true }"#;
+/// let mut out = String::new();
+/// merge_patch(base, local, &mut out).unwrap();
+/// assert_eq!(out, r#"{"port":80,"log":{"level":"debug"},"debug":true}"#);
+/// ```
+///
+/// # Errors
+/// Returns an error if writing fails or either input is not valid JSON
+pub fn merge_patch(target: &str, patch: &str, out: &mut impl Write) -> Result<(), JSONFormatError> {
+    merge(Some(target), patch, out)
+}
+
+/// `target` is `None` for new keys
+fn merge(target: Option<&str>, patch: &str, out: &mut impl Write) -> Result<(), JSONFormatError> {
+    let Some(patch_members) = members(patch)? else {
+        return minify(patch, out);
+    };
+    let target_members = match target {
+        Some(target) => members(target)?.unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    out.write_char('{')?;
+    let mut first = true;
+
+    for (key, value) in &target_members {
+        let patched = patch_members.iter().find(|(patch_key, _)| {
+            unescape_string_content(patch_key) == unescape_string_content(key)
+        });
+        match patched {
+            Some((_, "null")) => {}
+            Some((_, patched)) => {
+                write_key(out, key, &mut first)?;
+                merge(Some(value), patched, out)?;
+            }
+            None => {
+                write_key(out, key, &mut first)?;
+                minify(value, out)?;
+            }
+        }
+    }
+    for (key, value) in &patch_members {
+        let in_target = target_members.iter().any(|(target_key, _)| {
+            unescape_string_content(target_key) == unescape_string_content(key)
+        });
+        if !in_target && *value != "null" {
+            write_key(out, key, &mut first)?;
+            merge(None, value, out)?;
+        }
+    }
+
+    out.write_char('}')?;
+    Ok(())
+}
+
+/// Keys (as in the source) and the source of their values, or `None` if `source` is not an
+/// object
+fn members(source: &str) -> Result<Option<Vec<(&str, &str)>>, JSONParseError> {
+    Ok(match children(source)? {
+        Some((Container::Object, children)) => Some(
+            children
+                .into_iter()
+                .filter_map(|(key, value)| match key {
+                    JSONKey::Slice(key) => Some((key, value)),
+                    JSONKey::Index(_) => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    })
+}
+
+/// Keys or indexes of the members or items of an object or array, and the source of
+/// their values
+pub(crate) type Children<'a> = Vec<(JSONKey<'a>, &'a str)>;
+
+/// The children of `source`, or `None` if `source` is not an object or array
+pub(crate) fn children(source: &str) -> Result<Option<(Container, Children<'_>)>, JSONParseError> {
+    let mut reader = JsonReader::new(source, &ParseOptions::default());
+    let mut container = None;
+    let mut children = Vec::new();
+    // Start of the object or array being the value
+    let mut value_start = None;
+
+    while let Some(event) = reader.next_event()? {
+        let span = reader.span();
+        match (reader.key_chain(), event) {
+            ([], JSONEvent::StartObject) => container = Some(Container::Object),
+            ([], JSONEvent::StartArray) => container = Some(Container::Array),
+            (_, JSONEvent::Comment { .. }) | ([], JSONEvent::EndObject | JSONEvent::EndArray) => {}
+            ([], _) => return Ok(None),
+            ([key], JSONEvent::Value(_)) => {
+                children.push((*key, span.slice(source)));
+            }
+            ([_], JSONEvent::StartObject | JSONEvent::StartArray) => {
+                value_start = Some(span.start);
+            }
+            ([key], JSONEvent::EndObject | JSONEvent::EndArray) => {
+                if let Some(start) = value_start.take() {
+                    children.push((*key, &source[start..span.end]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(container.map(|container| (container, children)))
+}
+
+pub(crate) fn write_key(out: &mut impl Write, key: &str, first: &mut bool) -> core::fmt::Result {
+    if !core::mem::take(first) {
+        out.write_char(',')?;
+    }
+    out.write_fmt(format_args!("\"{key}\":"))
+}
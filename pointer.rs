@@ -0,0 +1,153 @@
+//! [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointers
+
+use alloc::{borrow::Cow, string::String, string::ToString, vec::Vec};
+
+use super::{
+    parse_with_exit_signal, unescape_string_content, JSONKey, JSONParseError, ParseOptions,
+    RootJSONValue,
+};
+
+/// Finds the value at `pointer` (for example `/build/entries/1/input`), only parsing up to
+/// it. Returns `None` if there is no value there, the pointer is to an object or array or
+/// the pointer is invalid (does not start with `/`).
+///
+/// ```
+/// use simple_json_parser::{get_at_pointer, RootJSONValue};
+///
+/// let source = r#"{ "a/b": [{ "c": "found" }] }"#;
+/// assert_eq!(get_at_pointer(source, "/a~1b/0/c").unwrap(), Some(RootJSONValue::String("found")));
+/// assert_eq!(get_at_pointer(source, "/a~1b/1").unwrap(), None);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn get_at_pointer<'a>(
+    source: &'a str,
+    pointer: &str,
+) -> Result<Option<RootJSONValue<'a>>, JSONParseError> {
+    let Some(segments) = pointer_segments(pointer) else {
+        return Ok(None);
+    };
+
+    let mut found = None;
+    parse_with_exit_signal(
+        source,
+        |keys, value| {
+            let matches = keys.len() == segments.len()
+                && keys
+                    .iter()
+                    .zip(&segments)
+                    .all(|(key, segment)| segment_matches(key, segment));
+            if matches {
+                found = Some(value);
+            }
+            matches
+        },
+        &ParseOptions::default(),
+    )?;
+    Ok(found)
+}
+
+/// The string at `pointer`, unescaped. `None` if there is no string there
+///
+/// ```
+/// use simple_json_parser::get_string_at;
+///
+/// let package = r#"{ "name": "simple-json-parser", "version": "0.0.5" }"#;
+/// assert_eq!(get_string_at(package, "/version").unwrap().as_deref(), Some("0.0.5"));
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn get_string_at<'a>(
+    source: &'a str,
+    pointer: &str,
+) -> Result<Option<Cow<'a, str>>, JSONParseError> {
+    Ok(match get_at_pointer(source, pointer)? {
+        Some(RootJSONValue::String(value)) => Some(unescape_string_content(value)),
+        _ => None,
+    })
+}
+
+/// The integer at `pointer`. `None` if there is no number there or it is not an integer
+/// which fits in an `i64`
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn get_i64_at(source: &str, pointer: &str) -> Result<Option<i64>, JSONParseError> {
+    Ok(match get_at_pointer(source, pointer)? {
+        Some(RootJSONValue::Number(value)) => value.parse().ok(),
+        _ => None,
+    })
+}
+
+/// The number at `pointer`. `None` if there is no number there
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn get_f64_at(source: &str, pointer: &str) -> Result<Option<f64>, JSONParseError> {
+    Ok(match get_at_pointer(source, pointer)? {
+        Some(RootJSONValue::Number(value)) => value.parse().ok(),
+        _ => None,
+    })
+}
+
+/// The boolean at `pointer`. `None` if there is no boolean there
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn get_bool_at(source: &str, pointer: &str) -> Result<Option<bool>, JSONParseError> {
+    Ok(match get_at_pointer(source, pointer)? {
+        Some(RootJSONValue::Boolean(value)) => Some(value),
+        _ => None,
+    })
+}
+
+/// Splits and unescapes (`~1` to `/` and `~0` to `~`) a pointer. `None` if it is not a
+/// valid pointer
+pub(crate) fn pointer_segments(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    let segments = pointer.strip_prefix('/')?.split('/').map(|segment| {
+        if segment.contains('~') {
+            Cow::Owned(segment.replace("~1", "/").replace("~0", "~"))
+        } else {
+            Cow::Borrowed(segment)
+        }
+    });
+    Some(segments.collect())
+}
+
+pub(crate) fn segment_matches(key: &JSONKey<'_>, segment: &str) -> bool {
+    match key {
+        JSONKey::Slice(key) => unescape_string_content(key) == segment,
+        JSONKey::Index(idx) => {
+            (segment == "0" || !segment.starts_with('0'))
+                && segment
+                    .parse::<usize>()
+                    .is_ok_and(|segment| segment == *idx)
+        }
+    }
+}
+
+/// The pointer for a key chain, the inverse of [`pointer_segments`]
+pub(crate) fn to_pointer(keys: &[JSONKey<'_>]) -> String {
+    let mut pointer = String::new();
+    write_pointer(&mut pointer, keys);
+    pointer
+}
+
+/// [`to_pointer`] appending to `pointer`
+pub(crate) fn write_pointer(pointer: &mut String, keys: &[JSONKey<'_>]) {
+    for key in keys {
+        pointer.push('/');
+        match key {
+            JSONKey::Slice(key) => {
+                let key = unescape_string_content(key);
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            JSONKey::Index(idx) => pointer.push_str(&idx.to_string()),
+        }
+    }
+}
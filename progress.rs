@@ -0,0 +1,109 @@
+//! Reporting how far through the input parsing is
+
+use alloc::sync::Arc;
+
+use super::{Cancellation, JSONKey, JSONParseError, JSONParseErrorReason, ParseOptions};
+
+/// Set as [`ParseOptions::progress`] to be called with the byte offset and key chain as
+/// parsing passes every `every` bytes. For streaming sources it is called once for each
+/// chunk that passes a multiple of `every`
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use simple_json_parser::{parse_with_exit_signal, ParseOptions, Progress};
+///
+/// let source = format!("[{}1]", "1, ".repeat(1000));
+/// let calls = Arc::new(AtomicUsize::new(0));
+/// let counter = calls.clone();
+/// let options = ParseOptions::builder()
+///     .progress(Progress::new(1000, move |_offset, _keys| {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///     }))
+///     .build();
+/// parse_with_exit_signal(&source, |_, _| false, &options).unwrap();
+/// assert_eq!(calls.load(Ordering::Relaxed), 3);
+/// ```
+#[derive(Clone)]
+pub struct Progress {
+    every: usize,
+    hook: Arc<Hook>,
+}
+
+type Hook = dyn Fn(usize, &[JSONKey<'_>]) + Send + Sync;
+
+impl Progress {
+    /// `every` of zero is treated as one
+    pub fn new(every: usize, hook: impl Fn(usize, &[JSONKey<'_>]) + Send + Sync + 'static) -> Self {
+        Self {
+            every: every.max(1),
+            hook: Arc::new(hook),
+        }
+    }
+}
+
+impl core::fmt::Debug for Progress {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Progress")
+            .field("every", &self.every)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Calls [`ParseOptions::progress`] and checks [`ParseOptions::cancellation`]. When neither
+/// is set `due` is always `false`
+pub(crate) struct ProgressTracker {
+    progress: Option<Progress>,
+    cancellation: Option<Cancellation>,
+    next_progress: usize,
+    next_check: usize,
+}
+
+impl ProgressTracker {
+    pub(crate) fn new(options: &ParseOptions) -> Self {
+        let progress = options.progress.clone();
+        let cancellation = options.cancellation.clone();
+        let next_progress = progress
+            .as_ref()
+            .map_or(usize::MAX, |progress| progress.every);
+        let next_check = cancellation
+            .as_ref()
+            .map_or(usize::MAX, |cancellation| cancellation.every);
+        Self {
+            progress,
+            cancellation,
+            next_progress,
+            next_check,
+        }
+    }
+
+    pub(crate) fn due(&self, offset: usize) -> bool {
+        offset >= self.next_progress.min(self.next_check)
+    }
+
+    /// # Errors
+    /// With [`JSONParseErrorReason::Cancelled`] if the cancellation check is true
+    #[cold]
+    pub(crate) fn report(
+        &mut self,
+        offset: usize,
+        keys: &[JSONKey<'_>],
+    ) -> Result<(), JSONParseError> {
+        if let (Some(progress), true) = (&self.progress, offset >= self.next_progress) {
+            (progress.hook)(offset, keys);
+            self.next_progress = (offset / progress.every + 1) * progress.every;
+        }
+        if let (Some(cancellation), true) = (&self.cancellation, offset >= self.next_check) {
+            if (cancellation.is_cancelled)() {
+                return Err(JSONParseError {
+                    at: offset,
+                    reason: JSONParseErrorReason::Cancelled,
+                    path: None,
+                }
+                .with_path(keys));
+            }
+            self.next_check = (offset / cancellation.every + 1) * cancellation.every;
+        }
+        Ok(())
+    }
+}
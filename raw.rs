@@ -0,0 +1,121 @@
+//! Getting the source of values rather than parsing them
+
+use super::{
+    key_chain_matches,
+    pointer::{pointer_segments, segment_matches},
+    JSONKey, JSONParseError, JsonReader, KeyPattern, ParseOptions, RootJSONValue, Step,
+};
+
+/// Passed to the callback of [`parse_with_raw_subtrees`]
+#[derive(Debug, PartialEq)]
+pub enum CapturedValue<'a> {
+    Value(RootJSONValue<'a>),
+    /// The source of an object or array, including the brackets
+    Raw(&'a str),
+}
+
+/// Like [`crate::parse`], but objects and arrays at a key chain matching one of `raw` are
+/// passed whole as [`CapturedValue::Raw`] rather than as the values in them. Returns the
+/// number of bytes parsed.
+///
+/// Captured objects and arrays are skipped over with [`JsonReader::skip_subtree`], so their
+/// content is not fully validated
+///
+/// ```
+/// use simple_json_parser::{parse_with_raw_subtrees, CapturedValue, KeyPattern, ParseOptions};
+///
+/// let source = r#"{ "name": "pkg", "exports": { ".": { "import": "./index.mjs" } } }"#;
+/// let mut values = Vec::new();
+/// parse_with_raw_subtrees(
+///     source,
+///     &[&[KeyPattern::Key("exports")]],
+///     |keys, value| values.push((keys.len(), value)),
+///     &ParseOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(values[1], (1, CapturedValue::Raw(r#"{ ".": { "import": "./index.mjs" } }"#)));
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_raw_subtrees<'a>(
+    on: &'a str,
+    raw: &[&[KeyPattern<'_>]],
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], CapturedValue<'a>),
+    options: &ParseOptions,
+) -> Result<usize, JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    while let Some(step) = reader.next_step()? {
+        match step {
+            Step::Value {
+                kind, start, end, ..
+            } => {
+                let value = reader.value(kind, start, end);
+                cb(&reader.key_chain, CapturedValue::Value(value));
+            }
+            Step::StartObject { at } | Step::StartArray { at }
+                if raw
+                    .iter()
+                    .any(|pattern| key_chain_matches(&reader.key_chain, pattern)) =>
+            {
+                reader.skip_subtree()?;
+                // Returns the end of the object or array
+                if let Some(Step::EndObject { at: end } | Step::EndArray { at: end }) =
+                    reader.next_step()?
+                {
+                    cb(&reader.key_chain, CapturedValue::Raw(&on[at..=end]));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(on.len())
+}
+
+/// The source of the value at `pointer` (for example `/build/entries/1`), as it is in
+/// `source`. Strings include their quotes and are not unescaped. Returns `None` if there is
+/// no value there or the pointer is invalid. Objects and arrays not on the way to the value
+/// are skipped with [`JsonReader::skip_subtree`].
+///
+/// ```
+/// use simple_json_parser::raw_slice_at;
+///
+/// let source = r#"{ "user": { "name": "Ben", "roles": ["admin", "dev"] }, "id": 1 }"#;
+/// assert_eq!(raw_slice_at(source, "/user/roles").unwrap(), Some(r#"["admin", "dev"]"#));
+/// assert_eq!(raw_slice_at(source, "/user/name").unwrap(), Some(r#""Ben""#));
+/// assert_eq!(raw_slice_at(source, "/user/age").unwrap(), None);
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input before finding the value
+pub fn raw_slice_at<'a>(source: &'a str, pointer: &str) -> Result<Option<&'a str>, JSONParseError> {
+    let Some(segments) = pointer_segments(pointer) else {
+        return Ok(None);
+    };
+    let matches = |keys: &[JSONKey<'_>]| {
+        keys.len() <= segments.len()
+            && keys
+                .iter()
+                .zip(&segments)
+                .all(|(key, segment)| segment_matches(key, segment))
+    };
+
+    let mut reader = JsonReader::new(source, &ParseOptions::default());
+    while let Some(step) = reader.next_step()? {
+        let found = reader.key_chain.len() == segments.len() && matches(&reader.key_chain);
+        match step {
+            Step::Value { start, end, .. } if found => return Ok(Some(&source[start..end])),
+            Step::StartObject { at } | Step::StartArray { at } if found => {
+                reader.skip_subtree()?;
+                if let Some(Step::EndObject { at: end } | Step::EndArray { at: end }) =
+                    reader.next_step()?
+                {
+                    return Ok(Some(&source[at..=end]));
+                }
+            }
+            Step::Key { .. } if !matches(&reader.key_chain) => reader.skip_subtree()?,
+            _ => {}
+        }
+    }
+    Ok(None)
+}
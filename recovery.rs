@@ -0,0 +1,117 @@
+//! Continuing after errors, for reporting every problem in a document
+
+use alloc::vec::Vec;
+
+use super::{
+    Container, JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue, State, Step,
+};
+
+/// Like [`crate::parse`] but on an error skips to the next `,`, `}` or `]` and continues.
+/// Returns every error found (empty if the input is valid). Values are still passed to
+/// the callback, but those around an error may be missing or have the wrong key chain.
+///
+/// ```
+/// use simple_json_parser::{parse_with_recovery, ParseOptions};
+///
+/// let source = r#"{ "a": 1, "b": tru, "c": [01, 2], "d" 4, "e": 5 }"#;
+/// let mut keys = Vec::new();
+/// let errors = parse_with_recovery(
+///     source,
+///     |chain, _value| keys.push(format!("{chain:?}")),
+///     &ParseOptions::default(),
+/// );
+/// assert_eq!(errors.len(), 3);
+/// assert_eq!(keys, [r#"[Slice("a")]"#, r#"[Slice("c"), Index(1)]"#, r#"[Slice("e")]"#]);
+/// ```
+pub fn parse_with_recovery<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+    options: &ParseOptions,
+) -> Vec<JSONParseError> {
+    let mut reader = JsonReader::new(on, options);
+    let mut errors = Vec::new();
+
+    loop {
+        match reader.next_step() {
+            Ok(Some(Step::Value {
+                kind, start, end, ..
+            })) => {
+                let value = reader.value(kind, start, end);
+                cb(&reader.key_chain, value);
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => break,
+            Err(err) => {
+                let at = err.at;
+                errors.push(err);
+                if !reader.recover(at) {
+                    break;
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+impl JsonReader<'_> {
+    /// Skips to the next `,`, `}` or `]` (from the character at `at` which caused the
+    /// error), then puts the state to after a value so the delimiter can be processed.
+    /// Returns `false` if there is nothing to continue from
+    fn recover(&mut self, at: usize) -> bool {
+        if self.machine.is_at_root() {
+            // Nothing to synchronise with
+            return false;
+        }
+
+        let failed = self.on[at..].chars().next().map(|chr| (at, chr));
+        let from = self.idx;
+        let rest = self.on[from..]
+            .char_indices()
+            .map(|(idx, chr)| (from + idx, chr));
+        let mut next = failed.into_iter().chain(rest);
+
+        let found = loop {
+            let Some((idx, chr)) = next.next() else {
+                return false;
+            };
+            let matching = match chr {
+                ',' => self.machine.containers.last(),
+                '}' => Some(Container::Object),
+                ']' => Some(Container::Array),
+                _ => continue,
+            };
+            let Some(matching) = matching else {
+                continue;
+            };
+            let Some(depth) = self
+                .machine
+                .containers
+                .iter()
+                .rposition(|container| container == matching)
+            else {
+                continue;
+            };
+            // Close any containers (missing their end) inside the matching one
+            self.machine.containers.truncate(depth + 1);
+            break (idx, chr);
+        };
+
+        // After a value there is a key or index for each container
+        let depth = self.machine.containers.len();
+        self.key_chain.truncate(depth);
+        if self.key_chain.len() < depth {
+            let placeholder = match self.machine.containers.last() {
+                Some(Container::Array) => JSONKey::Index(0),
+                _ => JSONKey::Slice(""),
+            };
+            self.key_chain.push(placeholder);
+        }
+
+        self.machine.state = State::EndOfValue;
+        self.idx = self.idx.max(found.0 + found.1.len_utf8());
+        self.retry = Some(found);
+        self.finished = false;
+        true
+    }
+}
@@ -0,0 +1,76 @@
+//! Replacing values at given key chains, keeping the rest of the source
+
+use alloc::string::String;
+
+use super::{
+    key_chain_matches, writer::write_string, JSONParseError, JsonReader, KeyPattern, ParseOptions,
+    Step,
+};
+
+/// A copy of `source` with each value whose key chain matches one of `patterns` replaced by
+/// the string `replacement`. Matching objects and arrays are replaced as a whole. Everything
+/// else, including formatting and comments, is kept as it is
+///
+/// ```
+/// use simple_json_parser::{redact, KeyPattern, ParseOptions};
+///
+/// let source = r#"{
+///     "user": "ben",
+///     "auth": { "token": "abc", "password": "hunter2" },
+///     "sessions": [{ "id": 1, "secret": "x" }]
+/// }"#;
+/// let patterns: [&[KeyPattern]; 2] = [
+///     &[KeyPattern::Key("auth"), KeyPattern::AnyKey],
+///     &[KeyPattern::Key("sessions"), KeyPattern::AnyIndex, KeyPattern::Key("secret")],
+/// ];
+/// let redacted = redact(source, &patterns, "***", &ParseOptions::default()).unwrap();
+/// assert_eq!(
+///     redacted,
+///     r#"{
+///     "user": "ben",
+///     "auth": { "token": "***", "password": "***" },
+///     "sessions": [{ "id": 1, "secret": "***" }]
+/// }"#
+/// );
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn redact(
+    source: &str,
+    patterns: &[&[KeyPattern<'_>]],
+    replacement: &str,
+    options: &ParseOptions,
+) -> Result<String, JSONParseError> {
+    let mut quoted = String::new();
+    // Writing to a `String` does not fail
+    let _ = write_string(&mut quoted, replacement);
+
+    let mut result = String::with_capacity(source.len());
+    // End of the source copied to `result`
+    let mut copied = 0;
+    let mut reader = JsonReader::new(source, options);
+    while let Some(step) = reader.next_step()? {
+        let matches = || {
+            patterns
+                .iter()
+                .any(|pattern| key_chain_matches(&reader.key_chain, pattern))
+        };
+        let span = match step {
+            Step::Value { start, end, .. } if matches() => start..end,
+            Step::StartObject { at } | Step::StartArray { at } if matches() => {
+                reader.skip_subtree()?;
+                match reader.next_step()? {
+                    Some(Step::EndObject { at: end } | Step::EndArray { at: end }) => at..end + 1,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        result.push_str(&source[copied..span.start]);
+        result.push_str(&quoted);
+        copied = span.end;
+    }
+    result.push_str(&source[copied..]);
+    Ok(result)
+}
@@ -0,0 +1,533 @@
+//! Validating documents against a subset of [JSON Schema](https://json-schema.org) while
+//! parsing
+
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec, vec::Vec};
+
+use super::{
+    parse_to_value,
+    pointer::to_pointer,
+    unescape_string_content,
+    value::{values_equal, EqualityOptions, Value},
+    JSONEvent, JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue, Span,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The schema is not valid JSON
+    Parse(JSONParseError),
+    /// A supported keyword has a value of the wrong type
+    InvalidKeyword {
+        /// JSON pointer to the schema containing the keyword
+        path: String,
+        keyword: String,
+    },
+}
+
+impl core::error::Error for SchemaError {}
+
+impl core::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            SchemaError::Parse(err) => core::fmt::Display::fmt(err, f),
+            SchemaError::InvalidKeyword { path, keyword } => {
+                f.write_fmt(format_args!("SchemaError: invalid {keyword:?} at {path:?}"))
+            }
+        }
+    }
+}
+
+impl From<JSONParseError> for SchemaError {
+    fn from(err: JSONParseError) -> Self {
+        SchemaError::Parse(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    Object,
+    Array,
+    String,
+    /// Also matches integers
+    Number,
+    /// A number without a fractional part (so `1.0` is an integer)
+    Integer,
+    Boolean,
+    Null,
+}
+
+impl SchemaType {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "object" => SchemaType::Object,
+            "array" => SchemaType::Array,
+            "string" => SchemaType::String,
+            "number" => SchemaType::Number,
+            "integer" => SchemaType::Integer,
+            "boolean" => SchemaType::Boolean,
+            "null" => SchemaType::Null,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    Type {
+        expected: Vec<SchemaType>,
+    },
+    /// A property in `required` is not in the object
+    MissingProperty(String),
+    /// Not one of `enum` (or not `const`)
+    Enum,
+    Minimum(f64),
+    Maximum(f64),
+    ExclusiveMinimum(f64),
+    ExclusiveMaximum(f64),
+    MinLength(usize),
+    MaxLength(usize),
+    MinItems(usize),
+    MaxItems(usize),
+    /// The schema for the value is `false` (or it is a property not allowed by
+    /// `"additionalProperties": false`)
+    NotAllowed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the value
+    pub path: String,
+    /// Of the value
+    pub span: Span,
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Node {
+    /// From the `false` schema
+    never: bool,
+    types: Option<Vec<SchemaType>>,
+    required: Vec<String>,
+    properties: Vec<(String, Node)>,
+    additional_properties: Option<Box<Node>>,
+    items: Option<Box<Node>>,
+    enum_values: Option<Vec<Value>>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+}
+
+/// A compiled schema. Supports the `type`, `required`, `properties`,
+/// `additionalProperties`, `items`, `enum`, `const`, `minimum`, `maximum`,
+/// `exclusiveMinimum`, `exclusiveMaximum`, `minLength`, `maxLength`, `minItems` and
+/// `maxItems` keywords and `true` and `false` schemas. Other keywords are ignored.
+///
+/// ```
+/// use simple_json_parser::{ParseOptions, Schema, ViolationKind};
+///
+/// let schema = Schema::compile(r#"{
+///     "type": "object",
+///     "required": ["name"],
+///     "properties": { "version": { "type": "integer", "minimum": 1 } }
+/// }"#).unwrap();
+///
+/// let violations = schema.validate(r#"{ "version": 0 }"#, &ParseOptions::default()).unwrap();
+/// assert_eq!(violations[0].path, "/version");
+/// assert_eq!(violations[0].kind, ViolationKind::Minimum(1.0));
+/// assert_eq!(violations[1].kind, ViolationKind::MissingProperty("name".to_owned()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    root: Node,
+}
+
+impl Schema {
+    /// # Errors
+    /// Returns an error if the schema is not valid JSON or a keyword has an invalid value
+    pub fn compile(source: &str) -> Result<Self, SchemaError> {
+        let value = parse_to_value(source)?;
+        let mut path = String::new();
+        Ok(Self {
+            root: compile_node(&value, &mut path)?,
+        })
+    }
+
+    /// Checks `on` against the schema in a single pass, only keeping the schemas for the
+    /// open objects and arrays. Violations inside an object or array come before those of
+    /// the object or array.
+    ///
+    /// # Errors
+    /// Returns an error if it tries to parse invalid JSON input
+    pub fn validate(
+        &self,
+        on: &str,
+        options: &ParseOptions,
+    ) -> Result<Vec<SchemaViolation>, JSONParseError> {
+        let mut reader = JsonReader::new(on, options);
+        let mut violations = Vec::new();
+        // Open objects and arrays
+        let mut frames: Vec<Frame<'_>> = Vec::new();
+
+        while let Some(event) = reader.next_event()? {
+            let span = reader.span();
+            let keys = reader.key_chain();
+            match event {
+                JSONEvent::Key(key) => {
+                    if let Some(frame) = frames.last_mut() {
+                        frame.found_key(key);
+                    }
+                }
+                JSONEvent::Value(value) => {
+                    let node = self.node_for(&frames, keys, span, &mut violations);
+                    if let Some(frame) = frames.last_mut() {
+                        frame.items += 1;
+                    }
+                    if let Some(node) = node {
+                        check_value(node, value, keys, span, &mut violations);
+                    }
+                }
+                JSONEvent::StartObject | JSONEvent::StartArray => {
+                    let node = self.node_for(&frames, keys, span, &mut violations);
+                    if let Some(frame) = frames.last_mut() {
+                        frame.items += 1;
+                    }
+                    frames.push(Frame {
+                        node,
+                        start: span.start,
+                        items: 0,
+                        required_found: vec![false; node.map_or(0, |node| node.required.len())],
+                    });
+                }
+                JSONEvent::EndObject | JSONEvent::EndArray => {
+                    let Some(frame) = frames.pop() else {
+                        continue;
+                    };
+                    if let Some(node) = frame.node {
+                        let span = Span {
+                            start: frame.start,
+                            end: span.end,
+                        };
+                        let is_object = matches!(event, JSONEvent::EndObject);
+                        check_container(node, &frame, is_object, on, keys, span, &mut violations);
+                    }
+                }
+                JSONEvent::Comment { .. } => {}
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// The schema for the value at `keys`, `None` if it is not constrained
+    fn node_for<'a>(
+        &'a self,
+        frames: &[Frame<'a>],
+        keys: &[JSONKey<'_>],
+        span: Span,
+        violations: &mut Vec<SchemaViolation>,
+    ) -> Option<&'a Node> {
+        let Some(parent) = frames.last() else {
+            return Some(&self.root);
+        };
+        let parent = parent.node?;
+        let node = match keys.last()? {
+            JSONKey::Slice(key) => {
+                let key = unescape_string_content(key);
+                parent
+                    .properties
+                    .iter()
+                    .find_map(|(name, node)| (*name == key).then_some(node))
+                    .or(parent.additional_properties.as_deref())?
+            }
+            JSONKey::Index(_) => parent.items.as_deref()?,
+        };
+        if node.never {
+            violations.push(SchemaViolation {
+                path: to_pointer(keys),
+                span,
+                kind: ViolationKind::NotAllowed,
+            });
+            None
+        } else {
+            Some(node)
+        }
+    }
+}
+
+struct Frame<'a> {
+    node: Option<&'a Node>,
+    start: usize,
+    /// Number of members or items
+    items: usize,
+    /// Whether each of the `required` properties of the node has been found, empty if
+    /// there are none
+    required_found: Vec<bool>,
+}
+
+impl Frame<'_> {
+    fn found_key(&mut self, key: &str) {
+        let Some(node) = self.node.filter(|node| !node.required.is_empty()) else {
+            return;
+        };
+        let key = unescape_string_content(key);
+        for (name, found) in node.required.iter().zip(&mut self.required_found) {
+            *found |= *name == key;
+        }
+    }
+}
+
+fn compile_node(value: &Value, path: &mut String) -> Result<Node, SchemaError> {
+    let members = match value {
+        Value::Boolean(allowed) => {
+            return Ok(Node {
+                never: !allowed,
+                ..Node::default()
+            })
+        }
+        Value::Object(members) => members,
+        _ => {
+            return Err(SchemaError::InvalidKeyword {
+                path: path.clone(),
+                keyword: String::new(),
+            })
+        }
+    };
+
+    let mut node = Node::default();
+    for (keyword, value) in members {
+        let invalid = || SchemaError::InvalidKeyword {
+            path: path.clone(),
+            keyword: keyword.clone(),
+        };
+        let number = || number_value(value).ok_or_else(invalid);
+        let count = || count_value(value).ok_or_else(invalid);
+
+        match keyword.as_str() {
+            "type" => {
+                let names = match value {
+                    Value::String(name) => vec![name.as_str()],
+                    Value::Array(names) => names
+                        .iter()
+                        .map(|name| name.as_str().ok_or_else(invalid))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => return Err(invalid()),
+                };
+                let types = names
+                    .into_iter()
+                    .map(|name| SchemaType::from_name(name).ok_or_else(invalid))
+                    .collect::<Result<_, _>>()?;
+                node.types = Some(types);
+            }
+            "required" => {
+                let Value::Array(names) = value else {
+                    return Err(invalid());
+                };
+                node.required = names
+                    .iter()
+                    .map(|name| name.as_str().map(str::to_owned).ok_or_else(invalid))
+                    .collect::<Result<_, _>>()?;
+            }
+            "properties" => {
+                let Value::Object(properties) = value else {
+                    return Err(invalid());
+                };
+                for (name, value) in properties {
+                    let length = path.len();
+                    path.push_str("/properties/");
+                    path.push_str(&name.replace('~', "~0").replace('/', "~1"));
+                    node.properties
+                        .push((name.clone(), compile_node(value, path)?));
+                    path.truncate(length);
+                }
+            }
+            "additionalProperties" | "items" => {
+                let length = path.len();
+                path.push('/');
+                path.push_str(keyword);
+                let child = Some(Box::new(compile_node(value, path)?));
+                path.truncate(length);
+                if keyword == "items" {
+                    node.items = child;
+                } else {
+                    node.additional_properties = child;
+                }
+            }
+            "enum" => {
+                let Value::Array(values) = value else {
+                    return Err(invalid());
+                };
+                node.enum_values = Some(values.clone());
+            }
+            "const" => node.enum_values = Some(vec![value.clone()]),
+            "minimum" => node.minimum = Some(number()?),
+            "maximum" => node.maximum = Some(number()?),
+            "exclusiveMinimum" => node.exclusive_minimum = Some(number()?),
+            "exclusiveMaximum" => node.exclusive_maximum = Some(number()?),
+            "minLength" => node.min_length = Some(count()?),
+            "maxLength" => node.max_length = Some(count()?),
+            "minItems" => node.min_items = Some(count()?),
+            "maxItems" => node.max_items = Some(count()?),
+            _ => {}
+        }
+    }
+
+    Ok(node)
+}
+
+fn number_value(value: &Value) -> Option<f64> {
+    if let Value::Number(number) = value {
+        number.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn count_value(value: &Value) -> Option<usize> {
+    if let Value::Number(number) = value {
+        number.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn check_value(
+    node: &Node,
+    value: RootJSONValue<'_>,
+    keys: &[JSONKey<'_>],
+    span: Span,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let mut violation = |kind| {
+        violations.push(SchemaViolation {
+            path: to_pointer(keys),
+            span,
+            kind,
+        });
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let number = match &value {
+        RootJSONValue::Number(number) | RootJSONValue::BigNumber(number) => number.parse().ok(),
+        RootJSONValue::Integer(number) => Some(*number as f64),
+        RootJSONValue::Float(number) | RootJSONValue::NonFinite(number) => Some(*number),
+        _ => None,
+    };
+    if let Some(types) = &node.types {
+        let matches = types.iter().any(|ty| match (ty, &value) {
+            (SchemaType::String, RootJSONValue::String(_))
+            | (SchemaType::Boolean, RootJSONValue::Boolean(_))
+            | (SchemaType::Null, RootJSONValue::Null) => true,
+            (SchemaType::Number, _) => number.is_some(),
+            (SchemaType::Integer, _) => {
+                number.is_some_and(|number| number.is_finite() && number % 1.0 == 0.0)
+            }
+            _ => false,
+        });
+        if !matches {
+            violation(ViolationKind::Type {
+                expected: types.clone(),
+            });
+        }
+    }
+
+    if let Some(number) = number {
+        if let Some(minimum) = node.minimum.filter(|minimum| number < *minimum) {
+            violation(ViolationKind::Minimum(minimum));
+        }
+        if let Some(maximum) = node.maximum.filter(|maximum| number > *maximum) {
+            violation(ViolationKind::Maximum(maximum));
+        }
+        if let Some(minimum) = node.exclusive_minimum.filter(|minimum| number <= *minimum) {
+            violation(ViolationKind::ExclusiveMinimum(minimum));
+        }
+        if let Some(maximum) = node.exclusive_maximum.filter(|maximum| number >= *maximum) {
+            violation(ViolationKind::ExclusiveMaximum(maximum));
+        }
+    }
+
+    if let RootJSONValue::String(value) = &value {
+        let length = unescape_string_content(value).chars().count();
+        if let Some(min_length) = node.min_length.filter(|min| length < *min) {
+            violation(ViolationKind::MinLength(min_length));
+        }
+        if let Some(max_length) = node.max_length.filter(|max| length > *max) {
+            violation(ViolationKind::MaxLength(max_length));
+        }
+    }
+
+    if let Some(values) = &node.enum_values {
+        let value = Value::from(value);
+        if !values
+            .iter()
+            .any(|item| values_equal(item, &value, NUMBERS))
+        {
+            violation(ViolationKind::Enum);
+        }
+    }
+}
+
+const NUMBERS: EqualityOptions = EqualityOptions {
+    normalize_numbers: true,
+};
+
+fn check_container(
+    node: &Node,
+    frame: &Frame<'_>,
+    is_object: bool,
+    on: &str,
+    keys: &[JSONKey<'_>],
+    span: Span,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let mut violation = |kind| {
+        violations.push(SchemaViolation {
+            path: to_pointer(keys),
+            span,
+            kind,
+        });
+    };
+
+    if let Some(types) = &node.types {
+        let expected = if is_object {
+            SchemaType::Object
+        } else {
+            SchemaType::Array
+        };
+        if !types.contains(&expected) {
+            violation(ViolationKind::Type {
+                expected: types.clone(),
+            });
+        }
+    }
+
+    if is_object {
+        for (name, found) in node.required.iter().zip(&frame.required_found) {
+            if !found {
+                violation(ViolationKind::MissingProperty(name.clone()));
+            }
+        }
+    } else {
+        if let Some(min_items) = node.min_items.filter(|min| frame.items < *min) {
+            violation(ViolationKind::MinItems(min_items));
+        }
+        if let Some(max_items) = node.max_items.filter(|max| frame.items > *max) {
+            violation(ViolationKind::MaxItems(max_items));
+        }
+    }
+
+    if let Some(values) = &node.enum_values {
+        // Only the source of this object or array is parsed
+        let matches = parse_to_value(span.slice(on)).is_ok_and(|value| {
+            values
+                .iter()
+                .any(|item| values_equal(item, &value, NUMBERS))
+        });
+        if !matches {
+            violation(ViolationKind::Enum);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Skipping subtrees without parsing them
+
+use super::{Container, JSONParseError, JsonReader, Machine, Span, State, Step};
+
+impl JsonReader<'_> {
+    /// Skips part of the input based on the last event returned. After a key skips its value,
+    /// otherwise skips the rest of the object or array the last event was in (for
+    /// [`crate::JSONEvent::StartObject`] and [`crate::JSONEvent::StartArray`] that is the new
+    /// one). The end of the container is still returned, so start and end events stay
+    /// balanced. At the root, after a value, this does nothing.
+    ///
+    /// Skipped content is found with a quick scan which only looks at brackets, strings and
+    /// comments, so it is not fully validated (`[tru, 01]` is skipped over without an error)
+    ///
+    /// ```
+    /// use simple_json_parser::{JsonReader, JSONEvent, ParseOptions, RootJSONValue};
+    ///
+    /// let mut reader = JsonReader::new(r#"{ "a": [1, 2], "b": 3 }"#, &ParseOptions::default());
+    /// assert_eq!(reader.next_event().unwrap(), Some(JSONEvent::StartObject));
+    /// assert_eq!(reader.next_event().unwrap(), Some(JSONEvent::Key("a")));
+    /// reader.skip_subtree().unwrap();
+    /// assert_eq!(reader.next_event().unwrap(), Some(JSONEvent::Key("b")));
+    /// reader.skip_subtree().unwrap();
+    /// assert_eq!(reader.next_event().unwrap(), Some(JSONEvent::EndObject));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if it tries to parse invalid JSON input
+    pub fn skip_subtree(&mut self) -> Result<(), JSONParseError> {
+        // A comment can end a JSON5 key
+        let state = match self.machine.state {
+            State::Comment { .. } => self.machine.resume,
+            state => state,
+        };
+        let after_key = match state {
+            State::Colon => true,
+            State::ExpectingValue => self.machine.containers.last() != Some(Container::Array),
+            _ => false,
+        };
+        if self.skip_scanning(after_key) {
+            return Ok(());
+        }
+
+        // Invalid input (or at the root), run the parser to find the error
+        let depth = self.machine.containers.len();
+        if after_key {
+            while let Some(step) = self.next_step()? {
+                let ends_value = matches!(
+                    step,
+                    Step::Value { .. } | Step::EndObject { .. } | Step::EndArray { .. }
+                );
+                if ends_value && self.machine.containers.len() == depth {
+                    break;
+                }
+            }
+        } else if depth > 0 {
+            while let Some(step) = self.next_step()? {
+                if self.machine.containers.len() < depth {
+                    self.pending = Some(step);
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `false` if the input could not be skipped this way
+    fn skip_scanning(&mut self, after_key: bool) -> bool {
+        let depth = self.machine.containers.len();
+        if depth == 0
+            || self.pending.is_some()
+            || matches!(self.machine.state, State::Comment { .. })
+        {
+            return false;
+        }
+        let bytes = self.on.as_bytes();
+        let (comments, json5) = (CommentStyles::of(&self.machine), self.machine.json5);
+        let from = self.retry.map_or(self.idx, |(idx, _)| idx);
+
+        if after_key {
+            let mut idx = skip_whitespace_and_comments(bytes, from, comments);
+            if let State::Colon = self.machine.state {
+                if bytes.get(idx) != Some(&b':') {
+                    return false;
+                }
+                idx = skip_whitespace_and_comments(bytes, idx + 1, comments);
+            }
+            let Some(end) = value_end(bytes, idx, comments, json5) else {
+                return false;
+            };
+            self.machine.state = State::EndOfValue;
+            self.idx = end;
+        } else {
+            let Some(close) = closing_bracket(bytes, from, comments, json5) else {
+                return false;
+            };
+            let (container, step) = if bytes[close] == b'}' {
+                (Container::Object, Step::EndObject { at: close })
+            } else {
+                (Container::Array, Step::EndArray { at: close })
+            };
+            if self.machine.containers.last() != Some(container) {
+                return false;
+            }
+            self.machine.close_container();
+            // Each outer container has a key or index
+            self.key_chain.truncate(depth - 1);
+            self.machine.state = State::EndOfValue;
+            self.idx = close + 1;
+            self.span = Span {
+                start: close,
+                end: close + 1,
+            };
+            self.pending = Some(step);
+        }
+        self.retry = None;
+        true
+    }
+}
+
+/// End of the value starting at `idx`. For scalars this is the next delimiter
+fn value_end(bytes: &[u8], idx: usize, comments: CommentStyles, json5: bool) -> Option<usize> {
+    match *bytes.get(idx)? {
+        open @ (b'{' | b'[') => {
+            let close = closing_bracket(bytes, idx + 1, comments, json5)?;
+            // `{` and `[` are two before `}` and `]`
+            (bytes[close] == open + 2).then_some(close + 1)
+        }
+        b'"' => string_end(bytes, idx),
+        b'\'' if json5 => string_end(bytes, idx),
+        _ => {
+            let rest = &bytes[idx..];
+            let length = rest
+                .iter()
+                .position(|&byte| {
+                    byte.is_ascii_whitespace()
+                        || matches!(byte, b',' | b'}' | b']')
+                        || (comments.any() && matches!(byte, b'/' | b'#'))
+                })
+                .unwrap_or(rest.len());
+            (length > 0).then_some(idx + length)
+        }
+    }
+}
+
+/// Position of the bracket closing the object or array that `idx` is in
+fn closing_bracket(
+    bytes: &[u8],
+    mut idx: usize,
+    comments: CommentStyles,
+    json5: bool,
+) -> Option<usize> {
+    let mut depth = 0usize;
+    loop {
+        match *bytes.get(idx)? {
+            b'"' => {
+                idx = string_end(bytes, idx)?;
+                continue;
+            }
+            b'\'' if json5 => {
+                idx = string_end(bytes, idx)?;
+                continue;
+            }
+            b'/' | b'#' if comments.any() => {
+                let end = comment_end(bytes, idx, comments)?;
+                if end > idx {
+                    idx = end;
+                    continue;
+                }
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return Some(idx),
+            b'}' | b']' => depth -= 1,
+            _ => {}
+        }
+        idx += 1;
+    }
+}
+
+/// End (after the quote) of the string with its opening quote at `idx`. `None` if it is not
+/// closed
+pub(crate) fn string_end(bytes: &[u8], idx: usize) -> Option<usize> {
+    let quote = bytes[idx];
+    let mut idx = idx + 1;
+    loop {
+        idx += bytes
+            .get(idx..)?
+            .iter()
+            .position(|&byte| byte == quote || byte == b'\\')?;
+        if bytes[idx] == quote {
+            return Some(idx + 1);
+        }
+        idx += 2;
+    }
+}
+
+pub(crate) fn skip_whitespace_and_comments(
+    bytes: &[u8],
+    mut idx: usize,
+    comments: CommentStyles,
+) -> usize {
+    loop {
+        match bytes.get(idx) {
+            Some(byte) if byte.is_ascii_whitespace() => idx += 1,
+            Some(b'/' | b'#') if comments.any() => match comment_end(bytes, idx, comments) {
+                Some(end) if end > idx => idx = end,
+                _ => return idx,
+            },
+            _ => return idx,
+        }
+    }
+}
+
+/// The comment styles which are allowed, as in [`Machine`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CommentStyles {
+    line: bool,
+    block: bool,
+    hash: bool,
+}
+
+impl CommentStyles {
+    #[cfg(feature = "rayon")]
+    pub(crate) fn new(options: &super::ParseOptions) -> Self {
+        Self {
+            line: options.allow_comments && options.allow_line_comments,
+            block: options.allow_comments && options.allow_block_comments,
+            hash: options.allow_comments && options.allow_hash_comments,
+        }
+    }
+
+    fn of(machine: &Machine) -> Self {
+        Self {
+            line: machine.line_comments,
+            block: machine.block_comments,
+            hash: machine.hash_comments,
+        }
+    }
+
+    fn any(self) -> bool {
+        self.line || self.block || self.hash
+    }
+}
+
+/// End of a comment starting at `idx`. `Some(idx)` if it is not a comment, `None` if a block
+/// comment is not closed or the style of the comment is not allowed (so the parser can report
+/// the error)
+pub(crate) fn comment_end(bytes: &[u8], idx: usize, comments: CommentStyles) -> Option<usize> {
+    let rest = &bytes[idx..];
+    let line_end = || {
+        let length = rest.iter().position(|&byte| byte == b'\n');
+        Some(idx + length.unwrap_or(rest.len()))
+    };
+    if rest.starts_with(b"#") {
+        comments.hash.then(line_end)?
+    } else if rest.starts_with(b"//") {
+        comments.line.then(line_end)?
+    } else if rest.starts_with(b"/*") && comments.block {
+        let length = rest[2..].windows(2).position(|window| window == b"*/")?;
+        Some(idx + 2 + length + 2)
+    } else if rest.starts_with(b"/*") {
+        None
+    } else {
+        Some(idx)
+    }
+}
@@ -0,0 +1,423 @@
+//! Parsing sources which are not available as a single `&str`
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use super::{
+    key_content, JSONKey, JSONParseError, Machine, ParseOptions, ProgressTracker, RootJSONValue,
+    Segment, Step, ValueKind,
+};
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum JSONReadError {
+    IO(std::io::Error),
+    Parse(JSONParseError),
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for JSONReadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            JSONReadError::IO(err) => Some(err),
+            JSONReadError::Parse(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for JSONReadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            JSONReadError::IO(err) => f.write_fmt(format_args!("JSONReadError: {err}")),
+            JSONReadError::Parse(err) => core::fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for JSONReadError {
+    fn from(err: std::io::Error) -> Self {
+        JSONReadError::IO(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<JSONParseError> for JSONReadError {
+    fn from(err: JSONParseError) -> Self {
+        JSONReadError::Parse(err)
+    }
+}
+
+#[cfg(feature = "std")]
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Like [`crate::parse_with_exit_signal`] but reads from a [`Read`] source in chunks, so
+/// the whole document does not have to be in memory. Keys and values are only borrowed
+/// for the duration of the callback. Returns the number of bytes parsed.
+///
+/// # Errors
+/// Returns an error if reading fails, the input is not UTF-8 or it tries to parse invalid JSON input
+#[cfg(feature = "std")]
+pub fn parse_reader<R: Read>(
+    mut reader: R,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONReadError> {
+    let mut chunked = Chunked::new(options);
+    let mut buffer = ReadBuffer::new();
+
+    loop {
+        let read = match reader.read(buffer.unfilled()) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        if let Some(end) = buffer.push_read(read, &mut chunked, &mut cb)? {
+            return Ok(end);
+        }
+    }
+
+    buffer.finish(&mut chunked, &mut cb)
+}
+
+/// Async version of [`parse_reader`]. The callback is still synchronous
+///
+/// # Errors
+/// Returns an error if reading fails, the input is not UTF-8 or it tries to parse invalid JSON input
+#[cfg(feature = "tokio")]
+pub async fn parse_async<R: tokio::io::AsyncRead + Unpin>(
+    mut reader: R,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    options: &ParseOptions,
+) -> Result<usize, JSONReadError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut chunked = Chunked::new(options);
+    let mut buffer = ReadBuffer::new();
+
+    loop {
+        let read = reader.read(buffer.unfilled()).await?;
+        if read == 0 {
+            break;
+        }
+        if let Some(end) = buffer.push_read(read, &mut chunked, &mut cb)? {
+            return Ok(end);
+        }
+    }
+
+    buffer.finish(&mut chunked, &mut cb)
+}
+
+/// Returned by [`PushParser::feed`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedResult {
+    /// The input so far is valid, feed the next chunk (or call [`PushParser::finish`])
+    NeedMoreData,
+    /// Parsing stopped at this byte offset (from the callback or
+    /// [`ParseOptions::exit_on_first_value`]). Further chunks are ignored
+    Exited(usize),
+}
+
+/// For when the source arrives in chunks (sockets, chunked transfer encoding). Values can
+/// be split across chunks.
+///
+/// ```
+/// use simple_json_parser::{FeedResult, JSONKey, ParseOptions, PushParser};
+///
+/// let mut names = Vec::new();
+/// let mut parser = PushParser::new(&ParseOptions::default(), |keys, value| {
+///     if let [JSONKey::Slice("name")] = keys {
+///         names.push(format!("{value:?}"));
+///     }
+///     false
+/// });
+/// assert_eq!(parser.feed(r#"{ "na"#).unwrap(), FeedResult::NeedMoreData);
+/// assert_eq!(parser.feed(r#"me": "Ben" }"#).unwrap(), FeedResult::NeedMoreData);
+/// parser.finish().unwrap();
+/// assert_eq!(names, [r#"String("Ben")"#]);
+/// ```
+pub struct PushParser<F> {
+    chunked: Chunked,
+    cb: F,
+    exited: Option<usize>,
+}
+
+impl<F> PushParser<F>
+where
+    F: for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+{
+    pub fn new(options: &ParseOptions, cb: F) -> Self {
+        Self {
+            chunked: Chunked::new(options),
+            cb,
+            exited: None,
+        }
+    }
+
+    /// # Errors
+    /// Returns an error if the input so far is not valid JSON
+    pub fn feed(&mut self, chunk: &str) -> Result<FeedResult, JSONParseError> {
+        if self.exited.is_none() {
+            self.exited = self.chunked.push_str(chunk, &mut self.cb)?;
+        }
+        Ok(self
+            .exited
+            .map_or(FeedResult::NeedMoreData, FeedResult::Exited))
+    }
+
+    /// Call once there are no more chunks. Returns the number of bytes parsed
+    ///
+    /// # Errors
+    /// Returns an error if the input is incomplete (for example an unclosed object)
+    pub fn finish(mut self) -> Result<usize, JSONParseError> {
+        match self.exited {
+            Some(end) => Ok(end),
+            None => self.chunked.finish(&mut self.cb),
+        }
+    }
+}
+
+/// Bytes read from a source. A character split across reads is kept until it is complete
+#[cfg(feature = "std")]
+struct ReadBuffer {
+    bytes: Vec<u8>,
+    filled: usize,
+}
+
+#[cfg(feature = "std")]
+impl ReadBuffer {
+    fn new() -> Self {
+        Self {
+            bytes: alloc::vec![0; READ_BUFFER_SIZE],
+            filled: 0,
+        }
+    }
+
+    fn unfilled(&mut self) -> &mut [u8] {
+        &mut self.bytes[self.filled..]
+    }
+
+    /// Returns `Some` with the end offset if parsing should stop
+    fn push_read(
+        &mut self,
+        read: usize,
+        chunked: &mut Chunked,
+        cb: &mut impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    ) -> Result<Option<usize>, JSONReadError> {
+        self.filled += read;
+        let text = valid_utf8_prefix(&self.bytes[..self.filled])?;
+        let valid = text.len();
+        if let Some(end) = chunked.push_str(text, cb)? {
+            return Ok(Some(end));
+        }
+        self.bytes.copy_within(valid..self.filled, 0);
+        self.filled -= valid;
+        Ok(None)
+    }
+
+    fn finish(
+        self,
+        chunked: &mut Chunked,
+        cb: &mut impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    ) -> Result<usize, JSONReadError> {
+        if self.filled != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+            .into());
+        }
+        Ok(chunked.finish(cb)?)
+    }
+}
+
+/// Leaves characters which have been split at the end
+#[cfg(feature = "std")]
+fn valid_utf8_prefix(bytes: &[u8]) -> Result<&str, std::io::Error> {
+    let invalid = |err| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+    match core::str::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(err) if err.error_len().is_none() => {
+            core::str::from_utf8(&bytes[..err.valid_up_to()]).map_err(invalid)
+        }
+        Err(err) => Err(invalid(err)),
+    }
+}
+
+/// Key chain segment for when the source does not outlive the key chain
+enum OwnedSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl Segment for OwnedSegment {
+    fn index(idx: usize) -> Self {
+        OwnedSegment::Index(idx)
+    }
+
+    fn as_index(&mut self) -> Option<&mut usize> {
+        if let OwnedSegment::Index(idx) = self {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+}
+
+fn borrow_segment(segment: &OwnedSegment) -> JSONKey<'_> {
+    match segment {
+        OwnedSegment::Key(key) => JSONKey::Slice(key),
+        OwnedSegment::Index(idx) => JSONKey::Index(*idx),
+    }
+}
+
+fn borrow_segments(segments: &[OwnedSegment]) -> Vec<JSONKey<'_>> {
+    segments.iter().map(borrow_segment).collect()
+}
+
+/// Calls `f` with the segments as a key chain. Chains of up to [`INLINE_KEYS`] are put in an
+/// array on the stack, so passing a value to the callback does not allocate
+fn with_keys<R>(segments: &[OwnedSegment], f: impl FnOnce(&[JSONKey]) -> R) -> R {
+    if segments.len() <= INLINE_KEYS {
+        let mut keys = [JSONKey::Index(0); INLINE_KEYS];
+        for (key, segment) in keys.iter_mut().zip(segments) {
+            *key = borrow_segment(segment);
+        }
+        f(&keys[..segments.len()])
+    } else {
+        f(&borrow_segments(segments))
+    }
+}
+
+const INLINE_KEYS: usize = 16;
+
+/// Drives [`Machine`] with parts of the source. Only keeps the source of the current key
+/// or value (which can span multiple chunks)
+struct Chunked {
+    machine: Machine,
+    key_chain: Vec<OwnedSegment>,
+    /// Source from `buffer_start` up to what has been pushed
+    buffer: String,
+    buffer_start: usize,
+    exit_on_first_value: bool,
+    typed_numbers: bool,
+    progress: ProgressTracker,
+}
+
+impl Chunked {
+    fn new(options: &ParseOptions) -> Self {
+        Self {
+            machine: Machine::new(options),
+            key_chain: Vec::new(),
+            buffer: String::new(),
+            buffer_start: 0,
+            exit_on_first_value: options.exits_on_first_value(),
+            typed_numbers: options.typed_numbers,
+            progress: ProgressTracker::new(options),
+        }
+    }
+
+    /// Returns `Some` with the end offset if parsing should stop
+    fn push_str(
+        &mut self,
+        chunk: &str,
+        cb: &mut impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    ) -> Result<Option<usize>, JSONParseError> {
+        let offset = self.buffer_start + self.buffer.len();
+        self.buffer.push_str(chunk);
+
+        for (idx, chr) in chunk.char_indices() {
+            let idx = offset + idx;
+            loop {
+                let step = self
+                    .machine
+                    .push_with_keys(idx, chr, &mut self.key_chain)
+                    .map_err(|err| err.with_path(&borrow_segments(&self.key_chain)))?;
+                if let Some(end) = self.handle(step, cb) {
+                    return Ok(Some(end));
+                }
+                if !matches!(step, Step::Value { retry: true, .. }) {
+                    break;
+                }
+            }
+        }
+
+        let keep_from = self
+            .machine
+            .token_start()
+            .unwrap_or(self.buffer_start + self.buffer.len());
+        self.buffer.drain(..(keep_from - self.buffer_start));
+        self.buffer_start = keep_from;
+
+        let end = offset + chunk.len();
+        if self.progress.due(end) {
+            let progress = &mut self.progress;
+            with_keys(&self.key_chain, |keys| progress.report(end, keys))?;
+        }
+
+        Ok(None)
+    }
+
+    fn finish(
+        &mut self,
+        cb: &mut impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    ) -> Result<usize, JSONParseError> {
+        let length = self.buffer_start + self.buffer.len();
+        while let Some(step) = self
+            .machine
+            .finish(length)
+            .map_err(|err| err.with_path(&borrow_segments(&self.key_chain)))?
+        {
+            if let Some(end) = self.handle(step, cb) {
+                return Ok(end);
+            }
+        }
+        Ok(length)
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &str {
+        &self.buffer[(start - self.buffer_start)..(end - self.buffer_start)]
+    }
+
+    fn handle(
+        &mut self,
+        step: Step,
+        cb: &mut impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+    ) -> Option<usize> {
+        match step {
+            Step::Key { start, end, quoted } => {
+                let (start, end) = key_content(start, end, quoted);
+                let key = self.slice(start, end).to_owned();
+                self.key_chain.push(OwnedSegment::Key(key));
+                None
+            }
+            Step::Value {
+                kind, start, end, ..
+            } => {
+                let value = match kind {
+                    ValueKind::String => RootJSONValue::String(self.slice(start + 1, end - 1)),
+                    ValueKind::Number => {
+                        RootJSONValue::number(self.slice(start, end), self.typed_numbers)
+                    }
+                    ValueKind::Boolean(value) => RootJSONValue::Boolean(value),
+                    ValueKind::Null => RootJSONValue::Null,
+                    ValueKind::NonFinite => RootJSONValue::non_finite(self.slice(start, end)),
+                    ValueKind::EmptyObject => RootJSONValue::EmptyObject,
+                    ValueKind::EmptyArray => RootJSONValue::EmptyArray,
+                };
+                let exit = with_keys(&self.key_chain, |keys| cb(keys, value))
+                    || (self.exit_on_first_value && self.machine.is_at_root());
+                exit.then_some(end)
+            }
+            Step::EndObject { at } | Step::EndArray { at }
+                if self.exit_on_first_value && self.machine.is_at_root() =>
+            {
+                Some(at + 1)
+            }
+            _ => None,
+        }
+    }
+}
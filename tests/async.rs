@@ -0,0 +1,34 @@
+#![cfg(feature = "tokio")]
+
+use simple_json_parser::{parse_async, JSONKey, ParseOptions, RootJSONValue};
+
+#[tokio::test]
+async fn from_async_read() {
+    let (mut writer, reader) = tokio::io::duplex(4);
+
+    let source = r#"{ "name": "ezno", "keywords": ["typescript", "checker"] }"#;
+    let write = async move {
+        use tokio::io::AsyncWriteExt;
+        writer.write_all(source.as_bytes()).await.unwrap();
+    };
+
+    let mut found = Vec::new();
+    let options = ParseOptions::default();
+    let parse = parse_async(
+        reader,
+        |keys, value| {
+            if let [JSONKey::Slice("keywords"), JSONKey::Index(_)] = keys {
+                if let RootJSONValue::String(keyword) = value {
+                    found.push(keyword.to_owned());
+                }
+            }
+            false
+        },
+        &options,
+    );
+
+    let ((), result) = tokio::join!(write, parse);
+
+    assert_eq!(result.unwrap(), source.len());
+    assert_eq!(found, ["typescript", "checker"]);
+}
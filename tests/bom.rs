@@ -0,0 +1,50 @@
+use simple_json_parser::{
+    is_valid, parse_bytes, parse_with_exit_signal, JSONParseErrorReason, ParseOptions, PushParser,
+};
+
+const SOURCE: &str = "\u{FEFF}{ \"a\": 1 }";
+
+#[test]
+fn skipped_at_start() {
+    let options = ParseOptions::default();
+    let mut values = Vec::new();
+    parse_with_exit_signal(
+        SOURCE,
+        |keys, value| {
+            values.push(format!("{keys:?} {value:?}"));
+            false
+        },
+        &options,
+    )
+    .unwrap();
+    assert_eq!(values, [r#"[Slice("a")] Number("1")"#]);
+
+    assert_eq!(
+        parse_bytes(SOURCE.as_bytes(), |_, _| false, &options).unwrap(),
+        SOURCE.len()
+    );
+    assert!(is_valid(SOURCE, &options).is_ok());
+
+    let mut parser = PushParser::new(&options, |_, _| false);
+    parser.feed(SOURCE).unwrap();
+    parser.finish().unwrap();
+}
+
+#[test]
+fn only_at_start() {
+    for source in ["[\u{FEFF}1]", " \u{FEFF}[1]", "[1]\u{FEFF}"] {
+        let err = parse_with_exit_signal(source, |_, _| false, &ParseOptions::default());
+        assert!(err.is_err(), "{source:?}");
+    }
+}
+
+#[test]
+fn rejected_when_disabled() {
+    let options = ParseOptions::builder().allow_bom(false).build();
+    let err = parse_with_exit_signal(SOURCE, |_, _| false, &options).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+    assert_eq!(err.at, 0);
+}
@@ -0,0 +1,92 @@
+use simple_json_parser::{
+    parse, parse_bytes, parse_bytes_lossy, JSONKey, JSONParseErrorReason, ParseOptions,
+    RootJSONValue,
+};
+
+#[test]
+fn same_as_str() {
+    let source = r#"{ "name": "café", "emoji": ["☕", "🦀"], /* ü */ "n": 1.5, "ok": true }"#;
+
+    let mut expected = Vec::new();
+    parse(source, |keys, value| {
+        expected.push((keys.to_vec(), value));
+    })
+    .unwrap();
+
+    let mut found = Vec::new();
+    let result = parse_bytes(
+        source.as_bytes(),
+        |keys, value| {
+            found.push((keys.to_vec(), value));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result, source.len());
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn invalid_utf8_only_when_emitted() {
+    // Invalid in a comment is skipped
+    let source = b"{ /* \xFF */ \"a\": \"b\" }";
+    let mut values = Vec::new();
+    parse_bytes(
+        source,
+        |keys, value| {
+            values.push((keys.to_vec(), value));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        values,
+        [(vec![JSONKey::Slice("a")], RootJSONValue::String("b"))]
+    );
+
+    let source = b"{ \"a\": \"b\xFF\" }";
+    let result = parse_bytes(source, |_, _| false, &ParseOptions::default());
+    assert_eq!(result.unwrap_err().at, 9);
+
+    // Stopping before the invalid value
+    let source = b"[1, \"\xFF\"]";
+    let result = parse_bytes(source, |_, _| true, &ParseOptions::default());
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[test]
+fn lossy() {
+    let source =
+        b"{ \"a\": [\"ok\", \"bad \xFF\xFE\"], \"b\xC3\": { \"c\": true }, \"d\": [\"\xE9\"] }";
+    assert!(parse_bytes(source, |_, _| false, &ParseOptions::default()).is_err());
+
+    let mut values = Vec::new();
+    let result = parse_bytes_lossy(
+        source,
+        |keys, value| {
+            values.push(format!("{keys:?} {value:?}"));
+            false
+        },
+        &ParseOptions::default(),
+    );
+    assert_eq!(result.unwrap(), source.len());
+    assert_eq!(
+        values,
+        [
+            r#"[Slice("a"), Index(0)] String("ok")"#,
+            "[Slice(\"a\"), Index(1)] String(\"bad \u{fffd}\u{fffd}\")",
+            "[Slice(\"b\u{fffd}\"), Slice(\"c\")] Boolean(true)",
+            "[Slice(\"d\"), Index(0)] String(\"\u{fffd}\")",
+        ]
+    );
+
+    // Outside of strings it is still an error
+    let result = parse_bytes_lossy(b"[1, \xFF]", |_, _| false, &ParseOptions::default());
+    assert!(matches!(
+        result.unwrap_err().reason,
+        JSONParseErrorReason::InvalidUTF8
+    ));
+}
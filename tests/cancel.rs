@@ -0,0 +1,81 @@
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use simple_json_parser::{
+    is_valid, parse_bytes, parse_reader, parse_with_exit_signal, Cancellation,
+    JSONParseErrorReason, JsonReader, ParseOptions, Progress,
+};
+
+#[test]
+fn checked_every_n_bytes() {
+    let source = r#"{ "a": [1, 2, 3], "b": "a long string value" }"#;
+    let checks = Arc::new(AtomicUsize::new(0));
+    let counter = checks.clone();
+    let options = ParseOptions::builder()
+        .cancellation(Cancellation::new(10, move || {
+            counter.fetch_add(1, Ordering::Relaxed) == 1
+        }))
+        .build();
+
+    let err = parse_with_exit_signal(source, |_, _| false, &options).unwrap_err();
+    assert!(matches!(err.reason, JSONParseErrorReason::Cancelled));
+    assert_eq!(err.at, 20);
+    assert_eq!(err.path.as_deref(), Some(""));
+    assert_eq!(checks.load(Ordering::Relaxed), 2);
+
+    checks.store(0, Ordering::Relaxed);
+    let mut reader = JsonReader::new(source, &options);
+    let err = loop {
+        match reader.next_value() {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("not cancelled"),
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(err.at, 20);
+    assert!(matches!(reader.next_value(), Ok(None)));
+}
+
+#[test]
+fn flag_and_other_sources() {
+    let source = format!("[{}0]", "0, ".repeat(10_000));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let options = ParseOptions::builder()
+        .cancellation(Cancellation::flag(cancelled.clone()))
+        .build();
+
+    assert!(parse_bytes(source.as_bytes(), |_, _| false, &options).is_ok());
+    assert!(is_valid(&source, &options).is_ok());
+
+    cancelled.store(true, Ordering::Relaxed);
+    let err = parse_bytes(source.as_bytes(), |_, _| false, &options).unwrap_err();
+    assert!(matches!(err.reason, JSONParseErrorReason::Cancelled));
+    assert_eq!(err.at, Cancellation::FLAG_EVERY);
+
+    let err = is_valid(&source, &options).unwrap_err();
+    assert_eq!(
+        (err.reason, err.at),
+        (JSONParseErrorReason::Cancelled, Cancellation::FLAG_EVERY)
+    );
+
+    let err = parse_reader(Cursor::new(source), |_, _| false, &options).unwrap_err();
+    assert!(format!("{err}").contains("Cancelled"), "{err}");
+}
+
+#[test]
+fn with_progress() {
+    let source = format!("[{}0]", "0, ".repeat(100));
+    let reports = Arc::new(AtomicUsize::new(0));
+    let counter = reports.clone();
+    let options = ParseOptions::builder()
+        .progress(Progress::new(10, move |_, _| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }))
+        .cancellation(Cancellation::new(100, || false))
+        .build();
+    parse_with_exit_signal(&source, |_, _| false, &options).unwrap();
+    assert_eq!(reports.load(Ordering::Relaxed), 30);
+
+    assert!(ParseOptions::default().cancellation.is_none());
+}
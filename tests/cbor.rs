@@ -0,0 +1,46 @@
+#![cfg(feature = "cbor")]
+
+use simple_json_parser::{json_to_cbor, JSONReadError, ParseOptions};
+
+fn cbor(source: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    json_to_cbor(source, &mut out, &ParseOptions::json5()).unwrap();
+    out
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(cbor("0"), [0x00]);
+    assert_eq!(cbor("23"), [0x17]);
+    assert_eq!(cbor("24"), [0x18, 24]);
+    assert_eq!(cbor("1000"), [0x19, 0x03, 0xE8]);
+    assert_eq!(cbor("-1"), [0x20]);
+    assert_eq!(cbor("-1000"), [0x39, 0x03, 0xE7]);
+    assert_eq!(
+        cbor("18446744073709551615"),
+        [0x1B, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+    assert_eq!(cbor("0x10"), [0x10]);
+    assert_eq!(cbor("1.5"), [0xFB, 0x3F, 0xF8, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(cbor("true"), [0xF5]);
+    assert_eq!(cbor("false"), [0xF4]);
+    assert_eq!(cbor("null"), [0xF6]);
+    assert_eq!(cbor(r#""a\nb""#), [0x63, b'a', b'\n', b'b']);
+}
+
+#[test]
+fn containers() {
+    assert_eq!(cbor("{}"), [0xA0]);
+    assert_eq!(cbor("[]"), [0x80]);
+    assert_eq!(
+        cbor("{ 'k': [1, {}], /* comment */ 'e': [] }"),
+        [0xBF, 0x61, b'k', 0x9F, 0x01, 0xA0, 0xFF, 0x61, b'e', 0x80, 0xFF]
+    );
+}
+
+#[test]
+fn errors() {
+    let mut out = Vec::new();
+    let result = json_to_cbor("[1, }", &mut out, &ParseOptions::default());
+    assert!(matches!(result, Err(JSONReadError::Parse(_))));
+}
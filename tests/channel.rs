@@ -0,0 +1,46 @@
+use simple_json_parser::{parse_to_channel, JSONParseErrorReason, OwnedEvent, ParseOptions, Value};
+
+#[test]
+fn events() {
+    let source = r#"{ "a\"b": [1, "x\ny"] }"#.to_owned();
+    let events = parse_to_channel(source, 1, &ParseOptions::default())
+        .iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(
+        events,
+        [
+            OwnedEvent::StartObject,
+            OwnedEvent::Key("a\"b".to_owned()),
+            OwnedEvent::StartArray,
+            OwnedEvent::Value(Value::Number("1".to_owned())),
+            OwnedEvent::Value(Value::String("x\ny".to_owned())),
+            OwnedEvent::EndArray,
+            OwnedEvent::EndObject,
+        ]
+    );
+}
+
+#[test]
+fn error_is_last() {
+    let source = "[1, 2,, 3]".to_owned();
+    let items = parse_to_channel(source, 0, &ParseOptions::default())
+        .iter()
+        .collect::<Vec<_>>();
+
+    let last = items.last().unwrap().as_ref().unwrap_err();
+    assert!(matches!(
+        last.reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+    assert!(items[..items.len() - 1].iter().all(Result::is_ok));
+}
+
+#[test]
+fn dropped_receiver() {
+    let source = format!("[{}1]", "1, ".repeat(10_000));
+    let receiver = parse_to_channel(source, 4, &ParseOptions::default());
+    assert_eq!(receiver.recv().unwrap().unwrap(), OwnedEvent::StartArray);
+    drop(receiver);
+}
@@ -0,0 +1,113 @@
+use simple_json_parser::{
+    parse_events, parse_with_exit_signal, parse_with_visitor, CommentStyle, JSONEvent, JSONKey,
+    JSONParseErrorReason, JSONVisitor, ParseOptions, ParseOptionsBuilder, Span,
+};
+
+#[test]
+fn comment_events() {
+    let source =
+        "// header\n{\n  \"a\": 1, # hash\n  /* block */ \"b\": [2 /* in array */]\n} // end";
+
+    let mut comments = Vec::new();
+    parse_events(
+        source,
+        |keys, event| {
+            if let JSONEvent::Comment { text, style, span } = event {
+                comments.push((format!("{keys:?}"), text, style, span.slice(source)));
+            }
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        comments,
+        [
+            ("[]".to_owned(), " header", CommentStyle::Line, "// header"),
+            ("[]".to_owned(), " hash", CommentStyle::Hash, "# hash"),
+            (
+                "[]".to_owned(),
+                " block ",
+                CommentStyle::Block,
+                "/* block */"
+            ),
+            (
+                r#"[Slice("b"), Index(0)]"#.to_owned(),
+                " in array ",
+                CommentStyle::Block,
+                "/* in array */"
+            ),
+            ("[]".to_owned(), " end", CommentStyle::Line, "// end"),
+        ]
+    );
+}
+
+#[test]
+fn visitor_comments() {
+    struct Comments(Vec<Span>);
+
+    impl<'a> JSONVisitor<'a> for Comments {
+        fn comment(
+            &mut self,
+            _keys: &[JSONKey<'a>],
+            _text: &'a str,
+            _style: CommentStyle,
+            span: Span,
+        ) {
+            self.0.push(span);
+        }
+    }
+
+    let mut visitor = Comments(Vec::new());
+    parse_with_visitor("[1, /**/ 2] #", &mut visitor, &ParseOptions::default()).unwrap();
+    assert_eq!(
+        visitor.0,
+        [Span { start: 4, end: 8 }, Span { start: 12, end: 13 }]
+    );
+}
+
+#[test]
+fn comment_styles() {
+    let check = |source: &str, options: &ParseOptions| {
+        parse_with_exit_signal(source, |_, _| false, options)
+            .map(|_| ())
+            .map_err(|err| (err.at, format!("{:?}", err.reason)))
+    };
+
+    let no_hash = ParseOptionsBuilder::from(ParseOptions::json5())
+        .allow_hash_comments(false)
+        .build();
+    assert_eq!(check("[1, // line\n /* block */ 2]", &no_hash), Ok(()));
+    assert_eq!(
+        check("[1, # hash\n 2]", &no_hash),
+        Err((4, "ExpectedValue { found: Some('#') }".into()))
+    );
+    assert_eq!(check("[1]# hash", &no_hash).unwrap_err().0, 3);
+
+    let block_only = ParseOptions::builder()
+        .allow_line_comments(false)
+        .allow_hash_comments(false)
+        .build();
+    assert_eq!(check("[1 /* block */, 2]", &block_only), Ok(()));
+    assert_eq!(
+        check("[1, // line\n 2]", &block_only),
+        Err((4, "DisallowedComment".into()))
+    );
+
+    let line_only = ParseOptions::builder()
+        .allow_block_comments(false)
+        .allow_hash_comments(false)
+        .build();
+    assert_eq!(check("[1, // line\n 2]", &line_only), Ok(()));
+    let err = parse_with_exit_signal("[1/* block */]", |_, _| false, &line_only).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::DisallowedComment
+    ));
+    assert_eq!(err.at, 2);
+
+    // `allow_comments` still turns off all of them
+    let none = ParseOptions::builder().allow_comments(false).build();
+    assert!(check("[1] # hash", &none).is_err());
+    assert!(check("[1] // line", &none).is_err());
+}
@@ -0,0 +1,48 @@
+use simple_json_parser::{parse_bytes, parse_with_exit_signal, JSONParseErrorReason, ParseOptions};
+
+fn strict() -> ParseOptions {
+    ParseOptions::builder()
+        .allow_control_characters(false)
+        .build()
+}
+
+#[test]
+fn allowed_by_default() {
+    let source = "{ \"a\tb\": \"line\nbreak\" }";
+    let mut values = Vec::new();
+    parse_with_exit_signal(
+        source,
+        |keys, value| {
+            values.push(format!("{keys:?} {value:?}"));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(values, ["[Slice(\"a\\tb\")] String(\"line\\nbreak\")"]);
+}
+
+#[test]
+fn rejected_when_strict() {
+    for (source, at) in [
+        ("[\"a\tb\"]", 3),
+        ("{ \"key\u{1}\": 1 }", 6),
+        ("{ \"a\": \"\r\n\" }", 8),
+    ] {
+        let err = parse_with_exit_signal(source, |_, _| false, &strict()).unwrap_err();
+        assert!(
+            matches!(err.reason, JSONParseErrorReason::ControlCharacterInString),
+            "{source:?}"
+        );
+        assert_eq!(err.at, at, "{source:?}");
+
+        let err = parse_bytes(source.as_bytes(), |_, _| false, &strict()).unwrap_err();
+        assert_eq!(err.at, at, "{source:?}");
+    }
+}
+
+#[test]
+fn escaped_and_outside_strings() {
+    let source = "{\n\t\"a\": \"tab\\there\",\r\n\t\"b\": [\"\\u0001\"]\n}";
+    assert!(parse_with_exit_signal(source, |_, _| false, &strict()).is_ok());
+}
@@ -0,0 +1,61 @@
+use simple_json_parser::{parse_cst, CstKind, CstNode, ParseOptions, TokenKind};
+
+fn leaves<'a>(node: &CstNode, source: &'a str, out: &mut Vec<&'a str>) {
+    if node.children.is_empty() {
+        out.push(node.text(source));
+    }
+    for child in &node.children {
+        assert!(node.span.start <= child.span.start && child.span.end <= node.span.end);
+        leaves(child, source, out);
+    }
+}
+
+#[test]
+fn lossless() {
+    let source = "// header\n{\n  \"a\": [1, { \"b\": null }, []],\n  \"c\" /* c */ : true,\n}\n";
+    let options = ParseOptions::json5();
+    let document = parse_cst(source, &options).unwrap();
+
+    let mut out = Vec::new();
+    leaves(&document, source, &mut out);
+    assert_eq!(out.concat(), source);
+    assert_eq!(document.span.end, source.len());
+    assert_eq!(
+        document.children[0].kind,
+        CstKind::Token(TokenKind::Comment(simple_json_parser::CommentStyle::Line))
+    );
+
+    let object = document.value().unwrap();
+    assert_eq!(object.kind, CstKind::Object);
+    assert_eq!(object.members().count(), 2);
+
+    let a = object.member(source, "a").unwrap();
+    assert_eq!(a.text(source), "\"a\": [1, { \"b\": null }, []]");
+    let array = a.value().unwrap();
+    assert_eq!(
+        array
+            .items()
+            .map(|item| item.text(source))
+            .collect::<Vec<_>>(),
+        ["1", "{ \"b\": null }", "[]"]
+    );
+
+    let c = object.member(source, "c").unwrap();
+    assert_eq!(c.key().unwrap().text(source), "\"c\"");
+    assert_eq!(c.value().unwrap().text(source), "true");
+    assert!(c
+        .children
+        .iter()
+        .any(|child| matches!(child.kind, CstKind::Token(TokenKind::Comment(_)))));
+
+    assert!(object.member(source, "d").is_none());
+}
+
+#[test]
+fn scalar_root_and_errors() {
+    let document = parse_cst(" 12 ", &ParseOptions::default()).unwrap();
+    assert_eq!(document.children.len(), 3);
+    assert_eq!(document.value().unwrap().text(" 12 "), "12");
+
+    assert!(parse_cst(r#"{ "a": }"#, &ParseOptions::default()).is_err());
+}
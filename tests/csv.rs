@@ -0,0 +1,57 @@
+use simple_json_parser::{json_to_csv, CsvError, CsvOptions};
+
+fn csv(source: &str, options: &CsvOptions) -> Result<String, CsvError> {
+    let mut out = String::new();
+    json_to_csv(source, &mut out, options)?;
+    Ok(out)
+}
+
+#[test]
+fn inferred_header() {
+    let source = r#"[
+        { "id": 1, "name": "a \"quoted\" name", "ok": true },
+        // comment
+        { "id": 2, "note": "two\nlines", "ok": null },
+        { "id": 3, "late": "not in header" }
+    ]"#;
+    let options = CsvOptions {
+        infer_rows: 2,
+        ..CsvOptions::default()
+    };
+    assert_eq!(
+        csv(source, &options).unwrap(),
+        "id,name,ok,note\n1,\"a \"\"quoted\"\" name\",true,\n2,,,\"two\nlines\"\n3,,,\n"
+    );
+    assert_eq!(csv("[]", &options).unwrap(), "");
+}
+
+#[test]
+fn supplied_header_and_delimiter() {
+    let options = CsvOptions {
+        columns: Some(vec!["b".to_owned(), "a".to_owned()]),
+        delimiter: ';',
+        ..CsvOptions::default()
+    };
+    assert_eq!(
+        csv(r#"[{ "a": 1, "b": "x;y" }, {}]"#, &options).unwrap(),
+        "b;a\n\"x;y\";1\n;\n"
+    );
+}
+
+#[test]
+fn not_an_array_of_flat_objects() {
+    let options = CsvOptions::default();
+    assert!(matches!(
+        csv(r#"{ "a": 1 }"#, &options),
+        Err(CsvError::ExpectedArray { at: 0 })
+    ));
+    assert!(matches!(
+        csv("[{}, 2]", &options),
+        Err(CsvError::ExpectedObject { at: 5 })
+    ));
+    assert!(matches!(
+        csv(r#"[{ "a": [1] }]"#, &options),
+        Err(CsvError::NestedValue { at: 8 })
+    ));
+    assert!(matches!(csv("[{}, ", &options), Err(CsvError::Parse(_))));
+}
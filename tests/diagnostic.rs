@@ -0,0 +1,25 @@
+#![cfg(feature = "miette")]
+
+use miette::Diagnostic;
+use simple_json_parser::parse;
+
+#[test]
+fn labels() {
+    let source = r#"{ "a": 1, "b" é 2 }"#;
+    let err = parse(source, |_, _| {}).unwrap_err();
+
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "simple_json_parser::ExpectedColon"
+    );
+    let labels = err.labels().unwrap().collect::<Vec<_>>();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 14);
+    assert_eq!(labels[0].len(), 2);
+    assert_eq!(labels[0].label(), Some("expected ':'"));
+    assert!(labels[0].primary());
+
+    let err = parse("[1, 2", |_, _| {}).unwrap_err();
+    let labels = err.labels().unwrap().collect::<Vec<_>>();
+    assert_eq!((labels[0].offset(), labels[0].len()), (5, 0));
+}
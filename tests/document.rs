@@ -0,0 +1,79 @@
+use simple_json_parser::{insert, remove, set_value, EditError, JsoncDocument};
+
+const SETTINGS: &str = r#"{
+    // editor
+    "editor.tabSize": 2,
+    "files.exclude": { "**/.git": true },
+    "list": [1, 2, 3], /* numbers */
+    "nested": {
+        "a": { "b": [] },
+    },
+}
+"#;
+
+#[test]
+fn several_edits_match_single_edits() {
+    let mut document = JsoncDocument::parse(SETTINGS).unwrap();
+    let mut expected = SETTINGS.to_owned();
+
+    document.set_value("/editor.tabSize", "4").unwrap();
+    expected = set_value(&expected, "/editor.tabSize", "4").unwrap();
+    document
+        .insert("/nested/a", "c", r#"{ "d": [1] }"#)
+        .unwrap();
+    expected = insert(&expected, "/nested/a", "c", r#"{ "d": [1] }"#).unwrap();
+    document.remove("/list/0").unwrap();
+    expected = remove(&expected, "/list/0").unwrap().0;
+    document.set_value("/nested/a/c/d/0", "true").unwrap();
+    expected = set_value(&expected, "/nested/a/c/d/0", "true").unwrap();
+    document.insert("", "new", "null").unwrap();
+    expected = insert(&expected, "", "new", "null").unwrap();
+    // Index 1 moved down after the remove
+    document.set_value("/list/1", "30").unwrap();
+    expected = set_value(&expected, "/list/1", "30").unwrap();
+    let removed = document.remove("/files.exclude").unwrap();
+    expected = remove(&expected, "/files.exclude").unwrap().0;
+
+    assert_eq!(removed, "    \"files.exclude\": { \"**/.git\": true },\n");
+    assert_eq!(document.as_str(), expected);
+    assert_eq!(document.get("/list"), Some("[2, 30]"));
+    assert_eq!(document.get("/nested/a/c"), Some(r#"{ "d": [true] }"#));
+    assert_eq!(document.get("/new"), Some("null"));
+    assert_eq!(document.get("/files.exclude"), None);
+
+    // Agrees with parsing again
+    let reparsed = JsoncDocument::parse(document.as_str()).unwrap();
+    for pointer in [
+        "",
+        "/editor.tabSize",
+        "/list/0",
+        "/nested/a/b",
+        "/nested/a/c/d",
+    ] {
+        assert_eq!(document.get(pointer), reparsed.get(pointer), "{pointer}");
+    }
+}
+
+#[test]
+fn errors() {
+    assert!(JsoncDocument::parse("{ \"a\": 1 ").is_err());
+    let mut document = JsoncDocument::parse(SETTINGS).unwrap();
+    assert!(matches!(
+        document.set_value("/missing", "1"),
+        Err(EditError::NotFound)
+    ));
+    assert!(matches!(
+        document.insert("/list", "a", "1"),
+        Err(EditError::NotAnObject)
+    ));
+    assert!(matches!(
+        document.insert("", "list", "1"),
+        Err(EditError::KeyExists)
+    ));
+    assert!(matches!(
+        document.set_value("/list/0", "[1"),
+        Err(EditError::InvalidValue(_))
+    ));
+    assert!(matches!(document.remove(""), Err(EditError::NotFound)));
+    assert_eq!(document.into_string(), SETTINGS);
+}
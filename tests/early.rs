@@ -1,4 +1,6 @@
-use simple_json_parser::{parse_with_exit_signal, JSONKey, RootJSONValue};
+use simple_json_parser::{
+    parse_with_exit_signal, EndState, JSONKey, ParseOptions, ParseOutcome, RootJSONValue,
+};
 
 #[test]
 fn at_end_of_value() {
@@ -23,13 +25,15 @@ fn at_end_of_value() {
             }
             false
         },
-        true,
-        true,
+        &ParseOptions::builder()
+            .exit_on_first_value(true)
+            .allow_comments(true)
+            .build(),
     )
     .unwrap();
 
     assert_eq!(values, 0);
-    assert_eq!(&source[result..], "</script>");
+    assert_eq!(result.remaining, "</script>");
 }
 
 #[test]
@@ -49,11 +53,46 @@ fn at_found_value() {
                 false
             }
         },
-        true,
-        true,
+        &ParseOptions::builder()
+            .exit_on_first_value(true)
+            .allow_comments(true)
+            .build(),
     )
     .unwrap();
 
     assert_eq!(values, 0);
-    assert_eq!(&source[result..], " }, 6]}");
+    assert_eq!(result.remaining, " }, 6]}");
+}
+
+#[test]
+fn outcome() {
+    let source = r#"{ "a": 1, "b": [2, 3] } "#;
+
+    let outcome = parse_with_exit_signal(source, |_, _| false, &ParseOptions::default()).unwrap();
+    assert_eq!(
+        outcome,
+        ParseOutcome {
+            bytes_consumed: source.len(),
+            remaining: "",
+            values_emitted: 3,
+            exited_early: false,
+            end_state: EndState::EndOfInput,
+        }
+    );
+
+    let outcome = parse_with_exit_signal(
+        source,
+        |_, value| value == RootJSONValue::Number("2"),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!((outcome.bytes_consumed, outcome.values_emitted), (17, 2));
+    assert_eq!(outcome.end_state, EndState::Callback);
+
+    let options = ParseOptions::builder().exit_on_first_value(true).build();
+    let outcome = parse_with_exit_signal(source, |_, _| false, &options).unwrap();
+    assert_eq!(outcome.bytes_consumed, source.len() - 1);
+    assert_eq!(outcome.remaining, " ");
+    assert!(outcome.exited_early);
+    assert_eq!(outcome.end_state, EndState::FirstValue);
 }
@@ -0,0 +1,202 @@
+use simple_json_parser::{insert, remove, set_value, EditError};
+
+const PACKAGE: &str = r#"{
+    // package
+    "name": "app",
+    "version": "1.0.0",
+    "files": [ "a.js",   "b.js" ],
+    "scripts": { "build": "tsc" }
+}
+"#;
+
+#[test]
+fn set_value_keeps_formatting() {
+    assert_eq!(
+        set_value(PACKAGE, "/version", r#""2.0.0""#).unwrap(),
+        PACKAGE.replace("1.0.0", "2.0.0")
+    );
+    assert_eq!(
+        set_value(PACKAGE, "/files/1", r#""c.js""#).unwrap(),
+        PACKAGE.replace("b.js", "c.js")
+    );
+    assert_eq!(
+        set_value(PACKAGE, "/scripts", "{}").unwrap(),
+        PACKAGE.replace(r#"{ "build": "tsc" }"#, "{}")
+    );
+    assert_eq!(set_value(" 1 // one", "", "[2]").unwrap(), " [2] // one");
+}
+
+#[test]
+fn set_value_errors() {
+    assert!(matches!(
+        set_value(PACKAGE, "/missing", "1"),
+        Err(EditError::NotFound)
+    ));
+    assert!(matches!(
+        set_value(PACKAGE, "version", "1"),
+        Err(EditError::InvalidPath)
+    ));
+    assert!(matches!(
+        set_value(PACKAGE, "/version", "{"),
+        Err(EditError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        set_value(PACKAGE, "/version", "1 // comment"),
+        Err(EditError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        set_value(PACKAGE, "/version", "1 2"),
+        Err(EditError::InvalidValue(_))
+    ));
+    for empty in ["", " \n "] {
+        let err = set_value(r#"{"a": 1, "b": 2}"#, "/a", empty).unwrap_err();
+        assert!(
+            matches!(&err, EditError::InvalidValue(err) if err.reason.name() == "ExpectedValue"),
+            "{empty:?}"
+        );
+    }
+    assert!(matches!(
+        set_value("{ \"a\": ", "/a", "1"),
+        Err(EditError::Parse(_))
+    ));
+}
+
+#[test]
+fn insert_follows_formatting() {
+    let inserted = insert(PACKAGE, "", "private", "true").unwrap();
+    assert_eq!(
+        inserted,
+        PACKAGE.replace(
+            "\"scripts\": { \"build\": \"tsc\" }\n",
+            "\"scripts\": { \"build\": \"tsc\" },\n    \"private\": true\n"
+        )
+    );
+    let inserted = insert(PACKAGE, "/scripts", "test", "\"node\"").unwrap();
+    assert_eq!(
+        inserted,
+        PACKAGE.replace(r#""build": "tsc" }"#, r#""build": "tsc", "test": "node" }"#)
+    );
+
+    // Comments after the last member and trailing commas
+    let source = "{\n\t\"a\": 1, // one\n}";
+    assert_eq!(
+        insert(source, "", "b", "2").unwrap(),
+        "{\n\t\"a\": 1, // one\n\t\"b\": 2,\n}"
+    );
+    let source = "{\n  \"a\": 1 // one\n}";
+    assert_eq!(
+        insert(source, "", "b", "[]").unwrap(),
+        "{\n  \"a\": 1, // one\n  \"b\": []\n}"
+    );
+
+    // Empty objects
+    assert_eq!(insert("{}", "", "a/b", "1").unwrap(), r#"{"a/b":1}"#);
+    assert_eq!(
+        insert("{\n  \"o\": {\n  }\n}", "/o", "k", "null").unwrap(),
+        "{\n  \"o\": {\n    \"k\": null\n  }\n}"
+    );
+    assert_eq!(
+        insert(r#"{ "a": [{}] }"#, "/a/0", "k\"", "1").unwrap(),
+        r#"{ "a": [{ "k\"": 1 }] }"#
+    );
+}
+
+#[test]
+fn insert_keeps_line_endings() {
+    let source = PACKAGE.replace('\n', "\r\n");
+    let inserted = insert(&source, "", "private", "true").unwrap();
+    assert_eq!(
+        inserted,
+        source.replace(
+            "\"scripts\": { \"build\": \"tsc\" }\r\n",
+            "\"scripts\": { \"build\": \"tsc\" },\r\n    \"private\": true\r\n"
+        )
+    );
+    assert!(!inserted.replace("\r\n", "").contains('\n'));
+
+    assert_eq!(
+        insert("{\r\n  \"o\": {\r\n  }\r\n}", "/o", "k", "null").unwrap(),
+        "{\r\n  \"o\": {\r\n    \"k\": null\r\n  }\r\n}"
+    );
+}
+
+#[test]
+fn insert_errors() {
+    assert!(matches!(
+        insert(PACKAGE, "", "name", "1"),
+        Err(EditError::KeyExists)
+    ));
+    assert!(matches!(
+        insert(PACKAGE, "/files", "a", "1"),
+        Err(EditError::NotAnObject)
+    ));
+    assert!(matches!(
+        insert(PACKAGE, "/missing", "a", "1"),
+        Err(EditError::NotFound)
+    ));
+    assert!(matches!(
+        insert(PACKAGE, "", "a", "1,"),
+        Err(EditError::InvalidValue(_))
+    ));
+    for empty in ["", "  "] {
+        let err = insert(r#"{"a": 1}"#, "", "b", empty).unwrap_err();
+        assert!(
+            matches!(&err, EditError::InvalidValue(err) if err.reason.name() == "ExpectedValue"),
+            "{empty:?}"
+        );
+    }
+}
+
+#[test]
+fn remove_members_and_items() {
+    let (removed, span) = remove(PACKAGE, "/version").unwrap();
+    assert_eq!(
+        removed,
+        PACKAGE.replace("    \"version\": \"1.0.0\",\n", "")
+    );
+    assert_eq!(span.slice(PACKAGE), "    \"version\": \"1.0.0\",\n");
+
+    // Comments above are attached to the member
+    let (removed, _) = remove(PACKAGE, "/name").unwrap();
+    assert_eq!(
+        removed,
+        PACKAGE.replace("    // package\n    \"name\": \"app\",\n", "")
+    );
+
+    // The last member takes the comma before it
+    let (removed, _) = remove(PACKAGE, "/scripts").unwrap();
+    assert_eq!(
+        removed,
+        PACKAGE.replace(",\n    \"scripts\": { \"build\": \"tsc\" }\n", "\n")
+    );
+
+    let (removed, span) = remove(PACKAGE, "/files/1").unwrap();
+    assert_eq!(
+        removed,
+        PACKAGE.replace(r#"[ "a.js",   "b.js" ]"#, r#"[ "a.js" ]"#)
+    );
+    assert_eq!(span.slice(PACKAGE), r#",   "b.js""#);
+    let (removed, _) = remove(PACKAGE, "/files/0").unwrap();
+    assert_eq!(
+        removed,
+        PACKAGE.replace(r#"[ "a.js",   "b.js" ]"#, r#"[ "b.js" ]"#)
+    );
+
+    let source = "{\n  \"a\": 1, // one\n  \"b\": 2 /* two */\n}";
+    assert_eq!(remove(source, "/b").unwrap().0, "{\n  \"a\": 1 // one\n}");
+    assert_eq!(
+        remove(source, "/a").unwrap().0,
+        "{\n  \"b\": 2 /* two */\n}"
+    );
+    assert_eq!(remove(r#"{"a":1}"#, "/a").unwrap().0, "{}");
+}
+
+#[test]
+fn remove_errors() {
+    assert!(matches!(
+        remove(PACKAGE, "/missing"),
+        Err(EditError::NotFound)
+    ));
+    assert!(matches!(remove(PACKAGE, ""), Err(EditError::NotFound)));
+    assert!(matches!(remove("[1,", "/0"), Err(EditError::Parse(_))));
+}
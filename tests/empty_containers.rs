@@ -0,0 +1,102 @@
+use simple_json_parser::{
+    parse_events, parse_with_spans, tokenize, JSONEvent, ParseOptions, ParseOptionsBuilder,
+    RootJSONValue, TokenKind,
+};
+
+fn options() -> ParseOptions {
+    ParseOptions::builder().empty_containers(true).build()
+}
+
+#[test]
+fn empty_values() {
+    let source = r#"{"a": {}, "b": [ /* nothing */ ], "c": [{}], "d": {"e": 1}}"#;
+    let mut values = Vec::new();
+    parse_with_spans(
+        source,
+        |keys, value, span| {
+            values.push(format!("{keys:?} {value:?} {}", span.slice(source)));
+            false
+        },
+        &options(),
+    )
+    .unwrap();
+    assert_eq!(
+        values,
+        [
+            r#"[Slice("a")] EmptyObject {}"#,
+            r#"[Slice("b")] EmptyArray [ /* nothing */ ]"#,
+            r#"[Slice("c"), Index(0)] EmptyObject {}"#,
+            r#"[Slice("d"), Slice("e")] Number("1") 1"#,
+        ]
+    );
+
+    // Not by default
+    let mut count = 0;
+    parse_with_spans(
+        source,
+        |_, _, _| {
+            count += 1;
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn empty_root() {
+    let mut values = Vec::new();
+    let options = ParseOptionsBuilder::from(options())
+        .exit_on_first_value(true)
+        .build();
+    let end = parse_with_spans(
+        "[] 2",
+        |_, value, _| {
+            values.push(value == RootJSONValue::EmptyArray);
+            false
+        },
+        &options,
+    )
+    .unwrap();
+    assert_eq!(values, [true]);
+    assert_eq!(end, 2);
+}
+
+#[test]
+fn events_unchanged() {
+    let source = r#"{"a": {}, "b": [1,]}"#;
+    let collect = |options: &ParseOptions| {
+        let mut events = Vec::new();
+        parse_events(
+            source,
+            |_, event| events.push(format!("{event:?}")),
+            options,
+        )
+        .unwrap();
+        events
+    };
+    let json5 = ParseOptions::builder().allow_trailing_commas(true).build();
+    assert_eq!(
+        collect(
+            &ParseOptionsBuilder::from(json5.clone())
+                .empty_containers(true)
+                .build()
+        ),
+        collect(&json5)
+    );
+    assert!(collect(&json5).contains(&format!("{:?}", JSONEvent::EndObject)));
+
+    let tokens: Vec<_> = tokenize("[{}]", &options())
+        .map(|token| token.unwrap().kind)
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            TokenKind::StartArray,
+            TokenKind::StartObject,
+            TokenKind::EndObject,
+            TokenKind::EndArray
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+use simple_json_parser::{parse_bytes, parse_with_exit_signal, ParseOptions, PushParser};
+
+fn strict(exit_on_first_value: bool) -> ParseOptions {
+    ParseOptions::builder()
+        .require_end_of_input(true)
+        .exit_on_first_value(exit_on_first_value)
+        .build()
+}
+
+fn check(source: &str, options: &ParseOptions) -> Result<usize, (usize, String)> {
+    let result = parse_with_exit_signal(source, |_, _| false, options)
+        .map(|outcome| outcome.bytes_consumed)
+        .map_err(|err| (err.at, format!("{:?}", err.reason)));
+    let from_bytes = parse_bytes(source.as_bytes(), |_, _| false, options)
+        .map_err(|err| (err.at, format!("{:?}", err.reason)));
+    assert_eq!(result, from_bytes, "{source}");
+    result
+}
+
+#[test]
+fn trailing_content() {
+    for options in [strict(false), strict(true)] {
+        for source in [r#"{"a":1} garbage"#, r#"{"a":1} {"b":2}"#, "[1]]", "12 3"] {
+            let err = check(source, &options).unwrap_err();
+            assert!(err.1.starts_with("UnexpectedTrailingContent"), "{source}");
+        }
+        assert_eq!(
+            check(r#"{"a":1} garbage"#, &options),
+            Err((8, "UnexpectedTrailingContent { found: 'g' }".into()))
+        );
+
+        let mut parser = PushParser::new(&options, |_, _| false);
+        parser.feed(r#"{"a":1} "#).unwrap();
+        let err = parser.feed("x").unwrap_err();
+        assert_eq!(err.at, 8);
+    }
+}
+
+#[test]
+fn whitespace_and_comments() {
+    for options in [strict(false), strict(true)] {
+        let source = "{\"a\": [1, 2]} // end\n /* more */ \n";
+        assert_eq!(check(source, &options), Ok(source.len()));
+        assert_eq!(check("1 ", &options), Ok(2));
+    }
+}
+
+#[test]
+fn without_option() {
+    // Returns at the end of the first value
+    let options = ParseOptions::builder().exit_on_first_value(true).build();
+    assert_eq!(check(r#"{"a":1} garbage"#, &options), Ok(7));
+    assert_eq!(
+        check(r#"{"a":1} garbage"#, &ParseOptions::default()),
+        Err((8, "ExpectedEndOfValue { found: Some('g') }".into()))
+    );
+}
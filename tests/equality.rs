@@ -0,0 +1,73 @@
+use simple_json_parser::{json_equals, EqualityOptions};
+
+#[test]
+fn structural_equality() {
+    let options = EqualityOptions::default();
+    let equal = [
+        (r#"{"a": 1, "b": 2}"#, r#"{"b":2,"a":1}"#),
+        ("[1, [2, {}]]", "[1,[2,{ }]] // comment"),
+        (
+            r#"{"a": {"x": null, "y": true}}"#,
+            r#"{"a": {"y": true, "x": null}}"#,
+        ),
+        (r#""A""#, r#""A""#),
+        (r#"{"a": 1, "a": 2}"#, r#"{"a": 2}"#),
+        // The last member with a key is used
+        (r#"{"a": 1, "b": 0, "a": 2}"#, r#"{"b": 0, "a": 3, "a": 2}"#),
+    ];
+    for (a, b) in equal {
+        assert!(json_equals(a, b, options).unwrap(), "{a} == {b}");
+    }
+
+    let not_equal = [
+        ("[1, 2]", "[2, 1]"),
+        (r#"{"a": 1}"#, r#"{"a": 1, "b": 1}"#),
+        (r#"{"a": null}"#, "{}"),
+        ("1", "1.0"),
+        ("1", r#""1""#),
+        (r#"{"a": 1, "a": 2}"#, r#"{"a": 1}"#),
+        (r#"{"a": 2, "a": 1}"#, r#"{"a": 2}"#),
+    ];
+    for (a, b) in not_equal {
+        assert!(!json_equals(a, b, options).unwrap(), "{a} != {b}");
+    }
+}
+
+#[test]
+fn normalized_numbers() {
+    let options = EqualityOptions {
+        normalize_numbers: true,
+    };
+    let equal = [
+        ("1", "1.0"),
+        ("100", "1e2"),
+        ("0.5", "5E-1"),
+        ("0", "-0.0"),
+        ("[12.50]", "[1.25e+1]"),
+        (
+            "123456789012345678901234567890",
+            "1.2345678901234567890123456789e29",
+        ),
+        ("10e9223372036854775807", "10e9223372036854775807"),
+    ];
+    for (a, b) in equal {
+        assert!(json_equals(a, b, options).unwrap(), "{a} == {b}");
+    }
+
+    let not_equal = [
+        ("1", "-1"),
+        ("0.1", "0.10000000000000001"),
+        ("10", "1"),
+        // Exponents which overflow are compared as written
+        ("10e9223372036854775807", "1"),
+        ("0.1e-9223372036854775808", "1"),
+    ];
+    for (a, b) in not_equal {
+        assert!(!json_equals(a, b, options).unwrap(), "{a} != {b}");
+    }
+}
+
+#[test]
+fn invalid() {
+    assert!(json_equals("{", "{}", EqualityOptions::default()).is_err());
+}
@@ -0,0 +1,150 @@
+use simple_json_parser::{
+    parse, parse_with_exit_signal, try_unescape_string_content, JSONParseError,
+    JSONParseErrorReason, Location, ParseOptions,
+};
+
+#[test]
+fn line_and_column() {
+    let source = "{\r\n    \"name\": \"ezno\",\r\n    \"café\" 2\r\n}";
+    let err = parse(source, |_, _| {}).unwrap_err();
+
+    assert_eq!(
+        err.location(source),
+        Location {
+            line: 3,
+            column: 12
+        }
+    );
+    assert_eq!(err.line(source), "    \"café\" 2");
+
+    let source = "[1, 2";
+    let err = parse(source, |_, _| {}).unwrap_err();
+    assert_eq!(err.location(source), Location { line: 1, column: 6 });
+    assert_eq!(err.line(source), "[1, 2");
+}
+
+#[test]
+fn key_chain_path() {
+    let source = r#"{ "build": { "entries": ["a.ts", { "input" "b.ts" }] } }"#;
+    let err = parse(source, |_, _| {}).unwrap_err();
+    assert_eq!(err.path.as_deref(), Some("/build/entries/1/input"));
+    assert_eq!(
+        err.to_string(),
+        "JSONParseError: ExpectedColon, found '\"' at /build/entries/1/input (offset 43)"
+    );
+
+    let source = r#"{ "a/b": [1, 2"#;
+    let err = parse(source, |_, _| {}).unwrap_err();
+    assert_eq!(err.path.as_deref(), Some("/a~1b/1"));
+
+    let err = parse("nul", |_, _| {}).unwrap_err();
+    assert_eq!(err.path.as_deref(), Some(""));
+    assert_eq!(
+        err.to_string(),
+        "JSONParseError: ExpectedTrueFalseNull, found end of input at 3"
+    );
+}
+
+#[test]
+fn found_character() {
+    let err = parse(r#"{ "a" = 1 }"#, |_, _| {}).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedColon { found: Some('=') }
+    ));
+    assert_eq!(err.reason.to_string(), "ExpectedColon, found '='");
+
+    let err = parse("[1 2]", |_, _| {}).unwrap_err();
+    assert_eq!(err.reason.found(), Some('2'));
+
+    let err = parse(r#"{ "a": }"#, |_, _| {}).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { found: Some('}') }
+    ));
+
+    let err = parse(r#"{ "a": 1"#, |_, _| {}).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::UnexpectedEndOfInput
+    ));
+
+    let err = parse("[1, 2}", |_, _| {}).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedBracket { found: Some('}') }
+    ));
+
+    let err = parse("[01]", |_, _| {}).unwrap_err();
+    assert_eq!(err.reason.found(), None);
+}
+
+#[test]
+fn source_snippet() {
+    let source = "[\n\t1,\n\ttru\n]";
+    let err = parse(source, |_, _| {}).unwrap_err();
+    assert_eq!(
+        err.display_with_source(source).to_string(),
+        "error: ExpectedTrueFalseNull, found '\\n' at /1\n --> 3:5\n  |\n3 | \ttru\n  | \t   ^ expected true, false or null"
+    );
+
+    let source = format!("{}[1,,]", "\n".repeat(9));
+    let err = parse(&source, |_, _| {}).unwrap_err();
+    assert_eq!(
+        err.display_with_source(&source).to_string(),
+        "error: ExpectedValue, found ',' at /1\n  --> 10:4\n   |\n10 | [1,,]\n   |    ^ expected a value"
+    );
+}
+
+#[test]
+fn distinct_reasons() {
+    let options = ParseOptions::STRICT_JSON;
+    let reason = |source: &str| {
+        parse_with_exit_signal(source, |_, _| false, &options)
+            .unwrap_err()
+            .reason
+            .name()
+    };
+    assert_eq!(reason("[01]"), "InvalidNumber");
+    assert_eq!(reason("[\"a"), "UnterminatedString");
+    assert_eq!(reason("{\"a"), "UnterminatedString");
+    assert_eq!(reason("[1] 2"), "UnexpectedTrailingContent");
+    assert_eq!(reason("{\"a\": [1"), "UnexpectedEndOfInput");
+    assert_eq!(reason("[1,"), "UnexpectedEndOfInput");
+    assert_eq!(reason("{"), "UnexpectedEndOfInput");
+    assert_eq!(reason("[\"\t\"]"), "ControlCharacterInString");
+    assert_eq!(reason("[1 /* */]"), "ExpectedEndOfValue");
+
+    let deep = ParseOptions::builder().max_depth(1).build();
+    let err = parse_with_exit_signal("[[]]", |_, _| false, &deep).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::DepthLimitExceeded
+    ));
+
+    let err = try_unescape_string_content(r"\q").unwrap_err();
+    assert!(matches!(err.reason, JSONParseErrorReason::InvalidEscape));
+}
+
+#[test]
+fn comparing_errors() {
+    let err = parse("[1 2]", |_, _| {}).unwrap_err();
+    assert_eq!(
+        err,
+        JSONParseError {
+            at: 3,
+            reason: JSONParseErrorReason::ExpectedEndOfValue { found: Some('2') },
+            path: Some("/0".to_owned()),
+        }
+    );
+    assert_eq!(err.clone(), err);
+
+    assert_eq!(err.reason.code(), 2);
+    assert_eq!(err.reason.name(), "ExpectedEndOfValue");
+    let options = ParseOptions::builder().max_depth(0).build();
+    let err = parse_with_exit_signal("[]", |_, _| false, &options).unwrap_err();
+    assert_eq!(
+        (err.reason.code(), err.reason.name()),
+        (12, "DepthLimitExceeded")
+    );
+}
@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+
+use simple_json_parser::{
+    parse, try_unescape_string_content, unescape_string_content, JSONKey, JSONParseErrorReason,
+    RootJSONValue,
+};
+
+#[test]
+fn unicode_escapes() {
+    assert!(matches!(
+        unescape_string_content("no escapes"),
+        Cow::Borrowed("no escapes")
+    ));
+    assert_eq!(unescape_string_content(r"caf\u00e9"), "café");
+    assert_eq!(
+        unescape_string_content(r"\u2615 and \uD83E\uDD80"),
+        "☕ and 🦀"
+    );
+    // Lone surrogates
+    assert_eq!(unescape_string_content(r"\ud83e!"), "\u{FFFD}!");
+    assert_eq!(unescape_string_content(r"\udd80"), "\u{FFFD}");
+    // Malformed
+    assert_eq!(unescape_string_content(r"\u00g1"), r"\u00g1");
+    assert_eq!(unescape_string_content(r"end \u00"), r"end \u00");
+}
+
+#[test]
+fn from_parse() {
+    let source = r#"{ "\u0041": "\"\u00e9\"" }"#;
+    let mut found = Vec::new();
+    parse(source, |keys, value| {
+        if let ([JSONKey::Slice(key)], RootJSONValue::String(value)) = (keys, value) {
+            found.push((
+                unescape_string_content(key).into_owned(),
+                unescape_string_content(value).into_owned(),
+            ));
+        }
+    })
+    .unwrap();
+    assert_eq!(found, [("A".to_owned(), "\"é\"".to_owned())]);
+}
+
+#[test]
+fn short_escapes() {
+    assert_eq!(
+        unescape_string_content(r#"a\nb\tc\rd\be\ff\/g\\h\"i"#),
+        "a\nb\tc\rd\u{8}e\u{c}f/g\\h\"i"
+    );
+    // Lenient keeps unknown escapes
+    assert_eq!(unescape_string_content(r"\x\"), r"\x\");
+}
+
+#[test]
+fn strict_escapes() {
+    assert_eq!(
+        try_unescape_string_content(r"line\nbreak \u00e9").unwrap(),
+        "line\nbreak é"
+    );
+
+    for (content, at) in [
+        (r"abc\x", 3),
+        (r"\u12", 0),
+        (r"ok \ud83e", 3),
+        (r"\udd80 lone low", 0),
+        (r"trailing \", 9),
+    ] {
+        let err = try_unescape_string_content(content).unwrap_err();
+        assert!(
+            matches!(err.reason, JSONParseErrorReason::InvalidEscape),
+            "{content}"
+        );
+        assert_eq!(err.at, at, "{content}");
+    }
+}
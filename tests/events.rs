@@ -0,0 +1,110 @@
+use simple_json_parser::{
+    parse_events, parse_keys, JSONEvent, JSONKey, ParseOptions, RootJSONValue,
+};
+
+#[test]
+fn container_events() {
+    let source = r#"{ "a": { "b": [1, {}] }, "c": [] }"#;
+
+    let mut events = Vec::new();
+    parse_events(
+        source,
+        |keys, event| events.push((keys.to_vec(), event)),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        events,
+        [
+            (vec![], JSONEvent::StartObject),
+            (vec![JSONKey::Slice("a")], JSONEvent::Key("a")),
+            (vec![JSONKey::Slice("a")], JSONEvent::StartObject),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b")],
+                JSONEvent::Key("b")
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b")],
+                JSONEvent::StartArray
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b"), JSONKey::Index(0)],
+                JSONEvent::Value(RootJSONValue::Number("1"))
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b"), JSONKey::Index(1)],
+                JSONEvent::StartObject
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b"), JSONKey::Index(1)],
+                JSONEvent::EndObject
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Slice("b")],
+                JSONEvent::EndArray
+            ),
+            (vec![JSONKey::Slice("a")], JSONEvent::EndObject),
+            (vec![JSONKey::Slice("c")], JSONEvent::Key("c")),
+            (vec![JSONKey::Slice("c")], JSONEvent::StartArray),
+            (vec![JSONKey::Slice("c")], JSONEvent::EndArray),
+            (vec![], JSONEvent::EndObject),
+        ]
+    );
+}
+
+#[test]
+fn count_members() {
+    let source =
+        r#"{ "scripts": { "build": "tsc", "test": "node test.mjs", "nested": { "x": 1 } } }"#;
+
+    let mut depth = 0;
+    let mut members = 0;
+    parse_events(
+        source,
+        |keys, event| match event {
+            JSONEvent::StartObject => depth += 1,
+            JSONEvent::EndObject => depth -= 1,
+            JSONEvent::Key(_) if depth == 2 && keys[0] == JSONKey::Slice("scripts") => {
+                members += 1;
+            }
+            _ => {}
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(members, 3);
+}
+
+#[test]
+fn keys_only() {
+    let source = r#"{ "a": { "b": [1, { "c": {} }] }, "d": [], "e": null }"#;
+
+    let mut keys = Vec::new();
+    parse_keys(
+        source,
+        |chain| keys.push(chain.to_vec()),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        keys,
+        [
+            vec![JSONKey::Slice("a")],
+            vec![JSONKey::Slice("a"), JSONKey::Slice("b")],
+            vec![
+                JSONKey::Slice("a"),
+                JSONKey::Slice("b"),
+                JSONKey::Index(1),
+                JSONKey::Slice("c")
+            ],
+            vec![JSONKey::Slice("d")],
+            vec![JSONKey::Slice("e")],
+        ]
+    );
+
+    // Values are still checked
+    assert!(parse_keys(r#"{ "a": tru }"#, |_| {}, &ParseOptions::default()).is_err());
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, c_void, CStr};
+
+use simple_json_parser::ffi::{
+    sjp_error_reason_name, sjp_parse, SjpError, SjpOptions, SjpValueKind,
+};
+
+extern "C" fn collect(
+    user_data: *mut c_void,
+    path: *const c_char,
+    path_len: usize,
+    kind: SjpValueKind,
+    value: *const c_char,
+    value_len: usize,
+) -> bool {
+    let values = unsafe { &mut *user_data.cast::<Vec<(String, SjpValueKind, String)>>() };
+    let path = unsafe { std::slice::from_raw_parts(path.cast::<u8>(), path_len) };
+    let value = unsafe { std::slice::from_raw_parts(value.cast::<u8>(), value_len) };
+    values.push((
+        String::from_utf8(path.to_vec()).unwrap(),
+        kind,
+        String::from_utf8(value.to_vec()).unwrap(),
+    ));
+    false
+}
+
+fn parse(
+    source: &str,
+    options: Option<&SjpOptions>,
+) -> (isize, Vec<(String, SjpValueKind, String)>, SjpError) {
+    let mut values = Vec::new();
+    let mut error = SjpError::default();
+    let result = unsafe {
+        sjp_parse(
+            source.as_ptr(),
+            source.len(),
+            options.map_or(std::ptr::null(), std::ptr::from_ref),
+            collect,
+            std::ptr::from_mut(&mut values).cast(),
+            std::ptr::from_mut(&mut error),
+        )
+    };
+    (result, values, error)
+}
+
+#[test]
+fn values_and_paths() {
+    let (result, values, _) = parse(r#"{ "a/b": [1.5, "x", true], "c": null }"#, None);
+    assert_eq!(result, 38);
+    let expected = [
+        ("/a~1b/0", SjpValueKind::Number, "1.5"),
+        ("/a~1b/1", SjpValueKind::String, "x"),
+        ("/a~1b/2", SjpValueKind::Boolean, "true"),
+        ("/c", SjpValueKind::Null, "null"),
+    ];
+    assert_eq!(values.len(), expected.len());
+    for ((path, kind, value), expected) in values.iter().zip(expected) {
+        assert_eq!((path.as_str(), *kind, value.as_str()), expected);
+    }
+}
+
+#[test]
+fn options() {
+    let options = SjpOptions {
+        allow_comments: false,
+        allow_trailing_commas: true,
+        json5: false,
+        empty_containers: true,
+        max_depth: 0,
+    };
+    let (result, values, _) = parse(r#"{ "a": [], }"#, Some(&options));
+    assert!(result > 0);
+    assert_eq!(values[0].1, SjpValueKind::EmptyArray);
+    assert_eq!(values[0].2, "[]");
+
+    let (result, _, error) = parse(
+        r#"{ "a": 1 // comment
+    }"#,
+        Some(&options),
+    );
+    assert_eq!(result, -1);
+    assert_eq!(error.at, 9);
+}
+
+#[test]
+fn errors() {
+    let (result, _, error) = parse(r#"{ "a" 1 }"#, None);
+    assert_eq!(result, -1);
+    assert_eq!(error.at, 6);
+    let name = unsafe { CStr::from_ptr(sjp_error_reason_name(error.reason)) };
+    assert_eq!(name.to_str().unwrap(), "ExpectedColon");
+    assert!(sjp_error_reason_name(0).is_null());
+}
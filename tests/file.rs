@@ -0,0 +1,61 @@
+#![cfg(feature = "mmap")]
+
+use simple_json_parser::{parse_file, JSONParseErrorReason, JSONReadError, ParseOptions};
+
+fn temp_file(name: &str, content: &[u8]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "simple-json-parser-{}-{name}.json",
+        std::process::id()
+    ));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn parse_mapped_file() {
+    let path = temp_file("valid", br#"{ "name": "ezno", "keywords": ["compiler"] }"#);
+    let mut values = Vec::new();
+    let end = parse_file(
+        &path,
+        |keys, value| {
+            values.push(format!("{keys:?} {value:?}"));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(end, 44);
+    assert_eq!(
+        values,
+        [
+            r#"[Slice("name")] String("ezno")"#,
+            r#"[Slice("keywords"), Index(0)] String("compiler")"#
+        ]
+    );
+}
+
+#[test]
+fn invalid_utf8_and_missing() {
+    // Only emitted values are checked
+    let path = temp_file("invalid", b"[\"\xFF\", 1]");
+    let result = parse_file(&path, |_, _| false, &ParseOptions::default());
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        result,
+        Err(JSONReadError::Parse(err)) if matches!(err.reason, JSONParseErrorReason::InvalidUTF8)
+    ));
+
+    let path = temp_file("empty", b"");
+    let result = parse_file(&path, |_, _| false, &ParseOptions::default());
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.unwrap(), 0);
+
+    let result = parse_file(
+        "does-not-exist.json",
+        |_, _| false,
+        &ParseOptions::default(),
+    );
+    assert!(matches!(result, Err(JSONReadError::IO(_))));
+}
@@ -0,0 +1,97 @@
+use simple_json_parser::{
+    flatten, unflatten, JSONParseError, JSONWriter, ParseOptions, PathStyle, UnflattenError, Value,
+};
+
+fn pairs(source: &str, style: PathStyle) -> Result<Vec<(String, Value)>, JSONParseError> {
+    flatten(source, style, &ParseOptions::default()).collect()
+}
+
+#[test]
+fn dotted_and_pointer_paths() -> Result<(), JSONParseError> {
+    let source = r#"{
+        "name": "ezno",
+        // comment
+        "a/b": { "c~d": [null, "A"] },
+        "empty": []
+    }"#;
+
+    assert_eq!(
+        pairs(source, PathStyle::Dotted)?,
+        [
+            ("name".to_owned(), Value::String("ezno".to_owned())),
+            ("a/b.c~d.0".to_owned(), Value::Null),
+            ("a/b.c~d.1".to_owned(), Value::String("A".to_owned())),
+            ("empty".to_owned(), Value::Array(Vec::new())),
+        ]
+    );
+    assert_eq!(
+        pairs(source, PathStyle::Pointer)?,
+        [
+            ("/name".to_owned(), Value::String("ezno".to_owned())),
+            ("/a~1b/c~0d/0".to_owned(), Value::Null),
+            ("/a~1b/c~0d/1".to_owned(), Value::String("A".to_owned())),
+            ("/empty".to_owned(), Value::Array(Vec::new())),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn root_value_and_errors() {
+    assert_eq!(
+        pairs("4", PathStyle::Dotted).unwrap(),
+        [(String::new(), Value::Number("4".to_owned()))]
+    );
+    assert!(pairs(r#"{ "a": 1, }"#, PathStyle::Dotted).is_err());
+}
+
+fn unflattened(pairs: &[(String, Value)], style: PathStyle) -> Result<String, UnflattenError> {
+    let mut writer = JSONWriter::new(String::new());
+    unflatten(pairs.iter().cloned(), style, &mut writer)?;
+    Ok(writer.into_inner())
+}
+
+#[test]
+fn round_trip() -> Result<(), JSONParseError> {
+    let source = r#"{"a/b":{"c.d":[null,"A",[],[{"x":1}]]},"e":"\"quoted\"","f":{}}"#;
+    let dotted = pairs(source, PathStyle::Dotted)?;
+    let pointer = pairs(source, PathStyle::Pointer)?;
+
+    assert_eq!(unflattened(&pointer, PathStyle::Pointer).unwrap(), source);
+    // Keys with `.` are split by the dotted style
+    assert_eq!(
+        unflattened(&dotted, PathStyle::Dotted).unwrap(),
+        r#"{"a/b":{"c":{"d":[null,"A",[],[{"x":1}]]}},"e":"\"quoted\"","f":{}}"#
+    );
+    assert_eq!(
+        unflattened(&pairs("[1, 2]", PathStyle::Dotted)?, PathStyle::Dotted).unwrap(),
+        "[1,2]"
+    );
+    assert_eq!(
+        unflattened(&pairs("true", PathStyle::Pointer)?, PathStyle::Pointer).unwrap(),
+        "true"
+    );
+    Ok(())
+}
+
+#[test]
+fn unflatten_errors() {
+    let pair = |path: &str| (path.to_owned(), Value::Null);
+
+    assert!(matches!(
+        unflattened(&[pair("a"), pair("a.b")], PathStyle::Dotted),
+        Err(UnflattenError::Conflict { index: 1 })
+    ));
+    assert!(matches!(
+        unflattened(&[pair("0"), pair("a")], PathStyle::Dotted),
+        Err(UnflattenError::Conflict { index: 1 })
+    ));
+    assert!(matches!(
+        unflattened(&[pair("a.0"), pair("a.2")], PathStyle::Dotted),
+        Err(UnflattenError::OutOfOrder { index: 1 })
+    ));
+    assert!(matches!(
+        unflattened(&[pair("a")], PathStyle::Pointer),
+        Err(UnflattenError::InvalidPath { index: 0 })
+    ));
+}
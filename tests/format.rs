@@ -0,0 +1,189 @@
+use simple_json_parser::{
+    format, json_equals, minify, strip_extensions, EqualityOptions, FormatOptions, JSONFormatError,
+};
+
+#[test]
+fn minify_jsonc() {
+    let source = r#"// Config
+{
+    "compilerOptions": {
+        "strict": true, /* important */
+        "paths": ["./src/*", "./lib/*",],
+    },
+    # hash comment
+    "empty": { },
+    "string with spaces": "a b  c",
+    "number": -1.5e3
+}
+"#;
+
+    let mut out = String::new();
+    minify(source, &mut out).unwrap();
+    assert_eq!(
+        out,
+        r#"{"compilerOptions":{"strict":true,"paths":["./src/*","./lib/*"]},"empty":{},"string with spaces":"a b  c","number":-1.5e3}"#
+    );
+
+    let mut out = String::new();
+    minify(" 42 ", &mut out).unwrap();
+    assert_eq!(out, "42");
+}
+
+#[test]
+fn minify_errors() {
+    let mut out = String::new();
+    assert!(matches!(
+        minify(r#"{"a" 1}"#, &mut out),
+        Err(JSONFormatError::Parse(_))
+    ));
+}
+
+fn format_default(source: &str) -> String {
+    let mut out = String::new();
+    format(source, &mut out, &FormatOptions::default()).unwrap();
+    out
+}
+
+#[test]
+fn format_document() {
+    let source =
+        r#"{"name":"ezno","keywords":["typescript","checker",],"empty":{},"nested":{"a":[[]]}}"#;
+    assert_eq!(
+        format_default(source),
+        r#"{
+    "name": "ezno",
+    "keywords": [
+        "typescript",
+        "checker"
+    ],
+    "empty": {},
+    "nested": {
+        "a": [
+            []
+        ]
+    }
+}
+"#
+    );
+    assert_eq!(format_default("  1 "), "1\n");
+    assert_eq!(format_default(""), "");
+}
+
+#[test]
+fn format_comments() {
+    let source = "// header\n{ \"a\": 1, // trailing\n  /* leading */\n  \"b\": [ // open\n  ],\n \"c\": /* inline */ 2\n} # end";
+    assert_eq!(
+        format_default(source),
+        r#"// header
+{
+    "a": 1, // trailing
+    /* leading */
+    "b": [ // open
+    ],
+    "c": /* inline */ 2
+} # end
+"#
+    );
+
+    let mut out = String::new();
+    let options = FormatOptions {
+        indent: "\t".into(),
+        newline: "\r\n".into(),
+        preserve_comments: false,
+        final_newline: false,
+        sort_keys: false,
+    };
+    format(source, &mut out, &options).unwrap();
+    assert_eq!(out, "{\r\n\t\"a\": 1,\r\n\t\"b\": [],\r\n\t\"c\": 2\r\n}");
+
+    // Stays valid when a line comment is before a value
+    let formatted = format_default("{\"a\": // comment\n 1}");
+    assert_eq!(formatted, "{\n    \"a\": // comment\n    1\n}\n");
+
+    // Commas go before comments on the same line as the previous item
+    let source = "[1, /* a */ 2 // b\n, 3]";
+    let formatted = format_default(source);
+    assert_eq!(formatted, "[\n    1, /* a */\n    2, // b\n    3\n]\n");
+    assert!(json_equals(source, &formatted, EqualityOptions::default()).unwrap());
+    assert_eq!(format_default(&formatted), formatted);
+}
+
+#[test]
+fn strip_to_json() {
+    use std::borrow::Cow;
+
+    let source = "/* café */ {\n  \"a\": [1, 2,], # hash\n  \"b\": \"// not a comment\",\n}";
+    let stripped = strip_extensions(source, true).unwrap();
+    assert_eq!(stripped.len(), source.len());
+    assert_eq!(
+        stripped,
+        "            {\n  \"a\": [1, 2 ],       \n  \"b\": \"// not a comment\" \n}"
+    );
+
+    let kept_commas = strip_extensions(source, false).unwrap();
+    assert!(kept_commas.contains("[1, 2,]"));
+
+    assert!(matches!(
+        strip_extensions(r#"{"a": [1, 2]}"#, true).unwrap(),
+        Cow::Borrowed(_)
+    ));
+    assert!(strip_extensions("[1, ", true).is_err());
+}
+
+#[test]
+fn sorted_keys() {
+    let options = FormatOptions {
+        indent: "  ".into(),
+        sort_keys: true,
+        ..FormatOptions::default()
+    };
+    let source = r#"{
+        "version": "1.0.0",
+        // the name
+        "name": "ezno",
+        "scripts": { "test": "t", "build": "b", },
+        "files": [{ "z": 1, "a": 2 }, "b", "a"],
+        "ab": 1,
+    }"#;
+
+    let mut out = String::new();
+    format(source, &mut out, &options).unwrap();
+    assert_eq!(
+        out,
+        r#"{
+  "ab": 1,
+  "files": [
+    {
+      "a": 2,
+      "z": 1
+    },
+    "b",
+    "a"
+  ],
+  // the name
+  "name": "ezno",
+  "scripts": {
+    "build": "b",
+    "test": "t"
+  },
+  "version": "1.0.0"
+}
+"#
+    );
+
+    // Duplicates keep their order
+    let mut out = String::new();
+    format(r#"{"b": 1, "a": 2, "b": 3, "a": {}}"#, &mut out, &options).unwrap();
+    assert_eq!(
+        out,
+        "{\n  \"a\": 2,\n  \"a\": {},\n  \"b\": 1,\n  \"b\": 3\n}\n"
+    );
+    // Comments after a comma on the same line stay with the member before
+    let source = "{\n  \"c\": 0,\n  \"b\": 1, // bee\n  \"a\": 2 /* a */, # last\n}";
+    let mut out = String::new();
+    format(source, &mut out, &options).unwrap();
+    assert_eq!(
+        out,
+        "{\n  \"a\": 2, /* a */ # last\n  \"b\": 1, // bee\n  \"c\": 0\n}\n"
+    );
+}
@@ -0,0 +1,31 @@
+use simple_json_parser::{gron, ParseOptions};
+
+fn lines(source: &str) -> String {
+    let mut out = String::new();
+    gron(source, &mut out, &ParseOptions::json5()).unwrap();
+    out
+}
+
+#[test]
+fn assignments() {
+    let source = r#"{
+        // comment
+        name: 'say "hi"',
+        "with space": [1.5, true, [], {}],
+        "_$id2": { "0": -1 }
+    }"#;
+    assert_eq!(
+        lines(source),
+        r#"json = {};
+json.name = "say \"hi\"";
+json["with space"] = [];
+json["with space"][0] = 1.5;
+json["with space"][1] = true;
+json["with space"][2] = [];
+json["with space"][3] = {};
+json._$id2 = {};
+json._$id2["0"] = -1;
+"#
+    );
+    assert_eq!(lines("null"), "json = null;\n");
+}
@@ -0,0 +1,38 @@
+use std::hash::{DefaultHasher, Hasher};
+
+use simple_json_parser::{hash, ParseOptions};
+
+fn digest(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash(source, &mut hasher, &ParseOptions::default()).unwrap();
+    hasher.finish()
+}
+
+#[test]
+fn formatting_does_not_change_hash() {
+    let source = r#"{ "name": "ezno", "list": [1, true, null, {}], "nested": { "a": "\u0041" } }"#;
+    let reformatted = "{\n  // comment\n  \"name\": \"ezno\",\n  \"list\": [\n    1,\n    true,\n    null,\n    {}\n  ],\n  \"nested\": { \"\\u0061\": \"A\" }\n}\n";
+    assert_eq!(digest(source), digest(reformatted));
+}
+
+#[test]
+fn content_changes_hash() {
+    let source = r#"{ "a": [1, 2], "b": "x" }"#;
+    for changed in [
+        r#"{ "a": [1, 3], "b": "x" }"#,
+        r#"{ "a": [1, 2], "b": "y" }"#,
+        r#"{ "b": "x", "a": [1, 2] }"#,
+        r#"{ "a": [[1, 2]], "b": "x" }"#,
+        r#"{ "a": [1, 2], "c": "x" }"#,
+        r#"{ "a": [1, 2], "b": "x", "c": [] }"#,
+        r#"{ "ab": [1, 2], "": "x" }"#,
+    ] {
+        assert_ne!(digest(source), digest(changed), "{changed}");
+    }
+}
+
+#[test]
+fn errors() {
+    let mut hasher = DefaultHasher::new();
+    assert!(hash("[1,", &mut hasher, &ParseOptions::default()).is_err());
+}
@@ -0,0 +1,73 @@
+use simple_json_parser::{
+    parse_with_exit_signal, parse_with_interned_keys, InternedKey, KeyInterner, ParseOptions,
+};
+
+#[test]
+fn same_key_chains() {
+    let source = r#"{
+        "items": [
+            { "id": 1, "tags": ["a", "b"], "meta": { "id": "x" } },
+            { "id": 2, "tags": [], "meta": { "id": "y", "extra": [[1], [2, 3]] } }
+        ],
+        "id": 3
+    }"#;
+
+    let mut expected = Vec::new();
+    parse_with_exit_signal(
+        source,
+        |keys, value| {
+            expected.push(format!("{keys:?} {value:?}"));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    let mut interner = KeyInterner::new();
+    let mut values = Vec::new();
+    let mut interned_chains = Vec::new();
+    parse_with_interned_keys(
+        source,
+        &mut interner,
+        |keys, value, interner| {
+            values.push(format!("{:?} {value:?}", interner.resolve_chain(keys)));
+            interned_chains.push(keys.to_vec());
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(values, expected);
+    // items, id, tags, meta, extra
+    assert_eq!(interner.len(), 5);
+
+    let id = interner.get("id").unwrap();
+    let ids = interned_chains
+        .iter()
+        .filter(|keys| keys.last() == Some(&InternedKey::Key(id)))
+        .count();
+    assert_eq!(ids, 5);
+}
+
+#[test]
+fn reused_interner() {
+    let mut interner = KeyInterner::new();
+    let first = interner.intern("name");
+    parse_with_interned_keys(
+        r#"{ "version": 1, "name": "b" }"#,
+        &mut interner,
+        |keys, _, _| {
+            assert!(matches!(keys, [InternedKey::Key(_)]));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(interner.get("name"), Some(first));
+    assert_eq!(
+        interner.resolve(interner.get("version").unwrap()),
+        "version"
+    );
+    assert_eq!(first.index(), 0);
+}
@@ -0,0 +1,101 @@
+use simple_json_parser::{
+    parse_with_exit_signal, unescape_string_content, JSONKey, JSONParseErrorReason, ParseOptions,
+};
+
+fn values(source: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    parse_with_exit_signal(
+        source,
+        |keys, value| {
+            found.push(format!("{keys:?} -> {value:?}"));
+            false
+        },
+        &ParseOptions::json5(),
+    )
+    .unwrap();
+    found
+}
+
+#[test]
+fn json5_document() {
+    let source = r"{
+        // comments
+        unquoted: 'and you can quote me on that',
+        singleQuotes: 'I can use &quot;double quotes&quot; here',
+        $dollar_1:0xdecaf,
+        leadingPlus: +1,
+        positive: +Infinity, negative: -Infinity, nan: NaN,
+        lineBreaks: 'Look, Mom! \
+No \\n',
+        'quoted key': [1, 2,],
+    }";
+
+    assert_eq!(
+        values(source),
+        [
+            r#"[Slice("unquoted")] -> String("and you can quote me on that")"#,
+            r#"[Slice("singleQuotes")] -> String("I can use &quot;double quotes&quot; here")"#,
+            r#"[Slice("$dollar_1")] -> Number("0xdecaf")"#,
+            r#"[Slice("leadingPlus")] -> Number("+1")"#,
+            r#"[Slice("positive")] -> Number("+Infinity")"#,
+            r#"[Slice("negative")] -> Number("-Infinity")"#,
+            r#"[Slice("nan")] -> Number("NaN")"#,
+            r#"[Slice("lineBreaks")] -> String("Look, Mom! \\\nNo \\\\n")"#,
+            r#"[Slice("quoted key"), Index(0)] -> Number("1")"#,
+            r#"[Slice("quoted key"), Index(1)] -> Number("2")"#,
+        ]
+    );
+}
+
+#[test]
+fn unquoted_key_endings() {
+    let mut keys = Vec::new();
+    parse_with_exit_signal(
+        "{a:1, b :2, c/* comment */: 3, d\n:4}",
+        |chain, _| {
+            if let [JSONKey::Slice(key)] = chain {
+                keys.push((*key).to_owned());
+            }
+            false
+        },
+        &ParseOptions::json5(),
+    )
+    .unwrap();
+    assert_eq!(keys, ["a", "b", "c", "d"]);
+
+    let err = parse_with_exit_signal("{a-b: 1}", |_, _| false, &ParseOptions::json5()).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedColon { .. }
+    ));
+    assert_eq!(err.at, 2);
+}
+
+#[test]
+fn escapes() {
+    assert_eq!(unescape_string_content(r"it\'s"), "it's");
+    assert_eq!(unescape_string_content("one \\\ntwo"), "one two");
+    assert_eq!(unescape_string_content(r"\x41\v\0"), "A\u{b}\0");
+}
+
+#[test]
+fn not_enabled_by_default() {
+    for source in ["{a: 1}", "'string'", "+1", "0x10", "Infinity", "NaN"] {
+        assert!(
+            parse_with_exit_signal(source, |_, _| false, &ParseOptions::default()).is_err(),
+            "{source}"
+        );
+    }
+
+    let mut found = None;
+    parse_with_exit_signal(
+        "0x1F",
+        |_, value| {
+            found = Some(format!("{value:?}"));
+            false
+        },
+        &ParseOptions::json5(),
+    )
+    .unwrap();
+    assert_eq!(found.as_deref(), Some(r#"Number("0x1F")"#));
+}
@@ -0,0 +1,83 @@
+use simple_json_parser::{
+    query_json_path, JSONPath, JSONPathErrorReason, ParseOptions, PathMatch, RootJSONValue,
+};
+
+const STORE: &str = r#"{
+    "store": {
+        "book": [
+            { "category": "reference", "author": "Nigel Rees", "price": 8.95 },
+            { "category": "fiction", "author": "Evelyn Waugh", "price": 12.99 },
+            { "category": "fiction", "author": "Herman Melville", "price": 8.99 },
+            { "category": "fiction", "author": "J. R. R. Tolkien", "price": 22.99 }
+        ],
+        "bicycle": { "color": "red", "price": 399 }
+    }
+}"#;
+
+fn query(expression: &str) -> Vec<String> {
+    let path = JSONPath::compile(expression).unwrap();
+    let mut found = Vec::new();
+    query_json_path(
+        STORE,
+        &path,
+        |_keys, selected| {
+            found.push(match selected {
+                PathMatch::Value(RootJSONValue::String(value) | RootJSONValue::Number(value)) => {
+                    value.to_owned()
+                }
+                PathMatch::Value(value) => format!("{value:?}"),
+                PathMatch::Object(source) | PathMatch::Array(source) => {
+                    source.split_whitespace().collect::<Vec<_>>().join(" ")
+                }
+            });
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    found
+}
+
+#[test]
+fn selectors() {
+    assert_eq!(
+        query("$.store.book[*].author"),
+        [
+            "Nigel Rees",
+            "Evelyn Waugh",
+            "Herman Melville",
+            "J. R. R. Tolkien"
+        ]
+    );
+    assert_eq!(query("$..price"), ["8.95", "12.99", "8.99", "22.99", "399"]);
+    assert_eq!(query("$.store['bicycle'].color"), ["red"]);
+    assert_eq!(query("$..book[0,2].price"), ["8.95", "8.99"]);
+    assert_eq!(
+        query("$..book[1:3].author"),
+        ["Evelyn Waugh", "Herman Melville"]
+    );
+    assert_eq!(
+        query("$..book[::2].author"),
+        ["Nigel Rees", "Herman Melville"]
+    );
+    assert_eq!(
+        query("$.store.bicycle"),
+        [r#"{ "color": "red", "price": 399 }"#]
+    );
+    assert_eq!(query("$.store.*.color"), ["red"]);
+    assert!(query("$.missing").is_empty());
+}
+
+#[test]
+fn invalid_paths() {
+    for (expression, reason, at) in [
+        ("store", JSONPathErrorReason::ExpectedRoot, 0),
+        ("$.", JSONPathErrorReason::ExpectedSelector, 2),
+        ("$[-1]", JSONPathErrorReason::NegativeIndex, 2),
+        ("$['a", JSONPathErrorReason::ExpectedQuote, 3),
+        ("$[0", JSONPathErrorReason::ExpectedBracket, 3),
+        ("$a", JSONPathErrorReason::ExpectedSelector, 1),
+    ] {
+        let err = JSONPath::compile(expression).unwrap_err();
+        assert_eq!((err.reason, err.at), (reason, at), "{expression}");
+    }
+}
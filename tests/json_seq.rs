@@ -0,0 +1,61 @@
+use simple_json_parser::{parse_json_seq, JSONParseErrorReason, JSONSeqRecord, ParseOptions};
+
+#[test]
+fn records() {
+    let source = concat!(
+        "\u{1E}{\"level\":\"info\",\"message\":\"started\"}\n",
+        "\u{1E}\u{1E}[1, 2]\n",
+        "\u{1E}\"text\"\n",
+        "\u{1E}{\"level\": 3 4}\n",
+        "\u{1E}{\"level\":\"warn\",\"mess",
+    );
+
+    let mut found = Vec::new();
+    let records = parse_json_seq(
+        source,
+        |record, keys, event| found.push(format!("{record} {keys:?} {event:?}")),
+        &ParseOptions::default(),
+    );
+
+    let [JSONSeqRecord::Valid(first), JSONSeqRecord::Valid(second), JSONSeqRecord::Valid(third), JSONSeqRecord::Invalid(err), JSONSeqRecord::Truncated(truncated)] =
+        records.as_slice()
+    else {
+        panic!("{records:?}");
+    };
+    assert_eq!(
+        first.slice(source),
+        r#"{"level":"info","message":"started"}"#
+    );
+    assert_eq!(second.slice(source), "[1, 2]");
+    assert_eq!(third.slice(source), r#""text""#);
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedEndOfValue { .. }
+    ));
+    assert!(source[err.at..].starts_with("4}"));
+    assert_eq!(truncated.slice(source), r#"{"level":"warn","mess"#);
+
+    assert!(found.contains(&r#"1 [Index(1)] Value(Number("2"))"#.to_owned()));
+    assert!(found.contains(&r#"2 [] Value(String("text"))"#.to_owned()));
+}
+
+#[test]
+fn truncated_scalars() {
+    // A number without whitespace after it could have been cut short
+    let records = parse_json_seq(
+        "\u{1E}12\u{1E}12\n\u{1E}true",
+        |_, _, _| {},
+        &ParseOptions::default(),
+    );
+    assert!(matches!(
+        records.as_slice(),
+        [
+            JSONSeqRecord::Truncated(_),
+            JSONSeqRecord::Valid(_),
+            JSONSeqRecord::Truncated(_)
+        ]
+    ));
+
+    let records = parse_json_seq("{}\n", |_, _, _| {}, &ParseOptions::default());
+    assert!(matches!(records.as_slice(), [JSONSeqRecord::Invalid(_)]));
+}
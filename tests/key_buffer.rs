@@ -0,0 +1,108 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use simple_json_parser::{
+    parse_with_key_buffer, JSONKey, JSONParseErrorReason, ParseOptions, RootJSONValue,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn does_not_allocate() {
+    let source = r#"{ "a": [1, { "b": [[], {}, "c"] }], "d": null // comment
+    }"#;
+    let mut keys = [JSONKey::Index(0); 5];
+    let mut values = [None, None, None];
+    let mut count = 0;
+
+    let before = ALLOCATIONS.with(Cell::get);
+    parse_with_key_buffer(
+        source,
+        &mut keys,
+        |keys, value| {
+            if let [JSONKey::Slice("a"), JSONKey::Index(1), JSONKey::Slice("b"), JSONKey::Index(2)] =
+                keys
+            {
+                values[0] = Some(value);
+            } else if let [JSONKey::Slice("d")] = keys {
+                values[1] = Some(value);
+            } else if let [JSONKey::Slice("a"), JSONKey::Index(0)] = keys {
+                values[2] = Some(value);
+            }
+            count += 1;
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+
+    assert_eq!(count, 3);
+    assert_eq!(
+        values,
+        [
+            Some(RootJSONValue::String("c")),
+            Some(RootJSONValue::Null),
+            Some(RootJSONValue::Number("1")),
+        ]
+    );
+}
+
+#[test]
+fn buffer_too_small() {
+    let mut keys = [JSONKey::Index(0); 2];
+    let result =
+        parse_with_key_buffer("[[[1]]]", &mut keys, |_, _| false, &ParseOptions::default());
+    let err = result.unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::DepthLimitExceeded
+    ));
+    assert_eq!(err.at, 2);
+
+    let result = parse_with_key_buffer("[[1]]", &mut keys, |_, _| false, &ParseOptions::default());
+    assert_eq!(result.unwrap(), 5);
+
+    // The smaller of the two limits
+    let options = ParseOptions::builder().max_depth(1).build();
+    assert!(parse_with_key_buffer("[[1]]", &mut keys, |_, _| false, &options).is_err());
+}
+
+#[test]
+fn deeply_nested() {
+    // Past the levels kept without allocating
+    let source = format!("{}1{}", "[".repeat(300), "]".repeat(300));
+    let mut keys = [JSONKey::Index(0); 300];
+    let mut depth = 0;
+    parse_with_key_buffer(
+        &source,
+        &mut keys,
+        |keys, _| {
+            depth = keys.len();
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(depth, 300);
+}
@@ -0,0 +1,145 @@
+use simple_json_parser::{JSONKey, KeyChain, KeyChainErrorReason};
+
+#[test]
+fn helpers() {
+    let keys = [
+        JSONKey::Slice("build"),
+        JSONKey::Slice("entries"),
+        JSONKey::Index(1),
+        JSONKey::Slice("input"),
+    ];
+    let chain = KeyChain::new(&keys);
+
+    assert_eq!(chain.depth(), 4);
+    assert!(chain.starts_with(&[JSONKey::Slice("build"), JSONKey::Slice("entries")]));
+    assert!(!chain.starts_with(&[JSONKey::Slice("entries")]));
+    assert!(chain.ends_with_key("input"));
+    assert!(!chain.ends_with_key("build"));
+    assert_eq!(chain.to_pointer(), "/build/entries/1/input");
+    assert_eq!(chain.parent().unwrap().last(), Some(&JSONKey::Index(1)));
+    assert_eq!(chain.clone().into_owned(), chain);
+    assert_eq!(KeyChain::from(keys.to_vec()), chain);
+}
+
+#[test]
+fn matches() {
+    let keys = [
+        JSONKey::Slice("build"),
+        JSONKey::Slice("entries"),
+        JSONKey::Index(1),
+        JSONKey::Slice("a.b"),
+    ];
+    let chain = KeyChain::new(&keys);
+
+    for path in [
+        r#"build.entries[1]["a.b"]"#,
+        "build.entries[1]['a.b']",
+        r#"build.*[*]["a.b"]"#,
+        "build.**",
+        "**",
+    ] {
+        assert!(chain.matches(path), "{path}");
+    }
+    for path in [
+        "build.entries[1]",
+        "build.entries[0]['a.b']",
+        "build.entries.1['a.b']",
+        "build..entries",
+        "build.entries[x]",
+        "build.**.entries",
+        ".build",
+        "",
+    ] {
+        assert!(!chain.matches(path), "{path}");
+    }
+
+    assert!(KeyChain::new(&[]).matches(""));
+}
+
+#[test]
+fn display() {
+    assert_eq!(JSONKey::Slice("a\\\"b").to_string(), "a\\\"b");
+    assert_eq!(JSONKey::Index(4).to_string(), "4");
+
+    let keys = [
+        JSONKey::Slice("build"),
+        JSONKey::Slice("entries"),
+        JSONKey::Index(1),
+        JSONKey::Slice("input"),
+    ];
+    assert_eq!(KeyChain::new(&keys).to_string(), "build.entries[1].input");
+
+    // Round trips through `matches`
+    let keys = [
+        JSONKey::Index(0),
+        JSONKey::Slice("a.b"),
+        JSONKey::Slice("*"),
+        JSONKey::Slice(r#"say \"hi\" [1]"#),
+        JSONKey::Slice(r#"JSON5 "key""#),
+        JSONKey::Slice("c"),
+    ];
+    let chain = KeyChain::new(&keys);
+    let path = chain.to_string();
+    assert_eq!(
+        path,
+        r#"[0]["a.b"]["*"]["say \"hi\" [1]"]['JSON5 "key"'].c"#
+    );
+    assert!(chain.matches(&path));
+}
+
+#[test]
+fn parse_paths() {
+    let chain = KeyChain::parse("build.entries[1].input").unwrap();
+    assert_eq!(
+        *chain,
+        [
+            JSONKey::Slice("build"),
+            JSONKey::Slice("entries"),
+            JSONKey::Index(1),
+            JSONKey::Slice("input"),
+        ]
+    );
+    let path = chain.to_string();
+    assert_eq!(KeyChain::parse(&path).unwrap().to_string(), path);
+    assert_eq!(KeyChain::parse("").unwrap().depth(), 0);
+
+    for (path, at, reason) in [
+        ("a..b", 1, KeyChainErrorReason::InvalidPath),
+        ("a[1", 1, KeyChainErrorReason::InvalidPath),
+        ("a.*", 1, KeyChainErrorReason::Wildcard),
+        ("a[*].b", 1, KeyChainErrorReason::Wildcard),
+        ("a.b.**", 3, KeyChainErrorReason::Wildcard),
+    ] {
+        let err = KeyChain::parse(path).unwrap_err();
+        assert_eq!((err.at, err.reason), (at, reason), "{path}");
+    }
+}
+
+#[test]
+fn from_pointer() {
+    let chain = KeyChain::from_pointer("/a/10/01/").unwrap();
+    assert_eq!(
+        *chain,
+        [
+            JSONKey::Slice("a"),
+            JSONKey::Index(10),
+            JSONKey::Slice("01"),
+            JSONKey::Slice(""),
+        ]
+    );
+    assert_eq!(KeyChain::from_pointer("").unwrap().depth(), 0);
+
+    let keys = [JSONKey::Slice("a"), JSONKey::Index(0)];
+    let pointer = KeyChain::new(&keys).to_pointer();
+    let chain = KeyChain::from_pointer(&pointer).unwrap();
+    assert_eq!(chain.to_pointer(), pointer);
+    assert_eq!(chain.last(), Some(&JSONKey::Index(0)));
+
+    let err = KeyChain::from_pointer("a/b").unwrap_err();
+    assert_eq!(err.reason, KeyChainErrorReason::ExpectedSlash);
+    let err = KeyChain::from_pointer("/dependencies/@types~1node").unwrap_err();
+    assert_eq!(
+        (err.at, err.reason),
+        (20, KeyChainErrorReason::EscapedSegment)
+    );
+}
@@ -0,0 +1,55 @@
+use simple_json_parser::{key_chain_matches, parse, KeyPattern};
+
+#[test]
+fn match_dependencies() {
+    let source = r#"{
+        "name": "ezno",
+        "dependencies": { "a": "1.0", "b": { "version": "2.0" } },
+        "files": ["lib.rs", "README.md"]
+    }"#;
+
+    let mut dependencies = Vec::new();
+    let mut nested = Vec::new();
+    let mut files = Vec::new();
+    parse(source, |keys, value| {
+        if key_chain_matches(keys, &[KeyPattern::Key("dependencies"), KeyPattern::AnyKey]) {
+            dependencies.push(format!("{value:?}"));
+        }
+        if key_chain_matches(
+            keys,
+            &[KeyPattern::Key("dependencies"), KeyPattern::AnySuffix],
+        ) {
+            nested.push(format!("{value:?}"));
+        }
+        if key_chain_matches(keys, &[KeyPattern::Key("files"), KeyPattern::AnyIndex]) {
+            files.push(format!("{value:?}"));
+        }
+    })
+    .unwrap();
+
+    assert_eq!(dependencies, [r#"String("1.0")"#]);
+    assert_eq!(nested, [r#"String("1.0")"#, r#"String("2.0")"#]);
+    assert_eq!(files, [r#"String("lib.rs")"#, r#"String("README.md")"#]);
+}
+
+#[test]
+fn exact_patterns() {
+    use simple_json_parser::JSONKey;
+
+    let keys = [JSONKey::Slice("files"), JSONKey::Index(1)];
+    assert!(key_chain_matches(
+        &keys,
+        &[KeyPattern::Key("files"), KeyPattern::Index(1)]
+    ));
+    assert!(!key_chain_matches(
+        &keys,
+        &[KeyPattern::Key("files"), KeyPattern::Index(0)]
+    ));
+    assert!(!key_chain_matches(&keys, &[KeyPattern::Key("files")]));
+    assert!(!key_chain_matches(
+        &keys,
+        &[KeyPattern::AnyIndex, KeyPattern::AnyIndex]
+    ));
+    assert!(key_chain_matches(&keys, &[KeyPattern::AnySuffix]));
+    assert!(key_chain_matches(&[], &[KeyPattern::AnySuffix]));
+}
@@ -0,0 +1,94 @@
+use simple_json_parser::lexer::{lex, RawToken};
+
+/// Kind and text of each token
+fn tokens(source: &str) -> Vec<(String, &str)> {
+    let mut lexer = lex(source);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next() {
+        let kind = format!("{token:?}");
+        let kind = kind.split('(').next().unwrap().to_owned();
+        tokens.push((kind, lexer.span().slice(source)));
+    }
+    tokens
+}
+
+#[test]
+fn lossless() {
+    let source = "\u{FEFF}{ \"a\\\"\": [1.5e-3, -2, 'x'], b: null, /* c */ # d\n \"e\" :true }";
+    let tokens = tokens(source);
+    assert_eq!(
+        tokens.iter().map(|(_, text)| *text).collect::<String>(),
+        source
+    );
+    assert_eq!(
+        tokens
+            .iter()
+            .filter(|(kind, _)| kind != "Whitespace")
+            .map(|(kind, text)| format!("{kind} {text}"))
+            .collect::<Vec<_>>(),
+        [
+            "LBrace {",
+            r#"String "a\"""#,
+            "Colon :",
+            "LBracket [",
+            "Number 1.5e-3",
+            "Comma ,",
+            "Number -2",
+            "Comma ,",
+            "String 'x'",
+            "RBracket ]",
+            "Comma ,",
+            "Literal b",
+            "Colon :",
+            "Literal null",
+            "Comma ,",
+            "Comment /* c */",
+            "Comment # d",
+            r#"String "e""#,
+            "Colon :",
+            "Literal true",
+            "RBrace }",
+        ]
+    );
+}
+
+#[test]
+fn invalid_input() {
+    assert_eq!(
+        tokens("]] 01x / @ \"open"),
+        [
+            ("RBracket".to_owned(), "]"),
+            ("RBracket".to_owned(), "]"),
+            ("Whitespace".to_owned(), " "),
+            ("Number".to_owned(), "01x"),
+            ("Whitespace".to_owned(), " "),
+            ("Invalid".to_owned(), "/"),
+            ("Whitespace".to_owned(), " "),
+            ("Invalid".to_owned(), "@"),
+            ("Whitespace".to_owned(), " "),
+            ("String".to_owned(), "\"open"),
+        ]
+    );
+    // An unclosed string stops at the end of the line
+    assert_eq!(
+        tokens("[\"open,\r\n 'a\\\r\nb', 2]"),
+        [
+            ("LBracket".to_owned(), "["),
+            ("String".to_owned(), "\"open,"),
+            ("Whitespace".to_owned(), "\r\n "),
+            ("String".to_owned(), "'a\\\r\nb'"),
+            ("Comma".to_owned(), ","),
+            ("Whitespace".to_owned(), " "),
+            ("Number".to_owned(), "2"),
+            ("RBracket".to_owned(), "]"),
+        ]
+    );
+    assert_eq!(
+        lex("/* open").collect::<Vec<_>>(),
+        [RawToken::Comment(simple_json_parser::Span {
+            start: 0,
+            end: 7
+        })]
+    );
+    assert_eq!(lex("").next(), None);
+}
@@ -0,0 +1,124 @@
+use simple_json_parser::{is_valid, parse_bytes, parse_with_exit_signal, Limits, ParseOptions};
+
+fn options(limits: Limits) -> ParseOptions {
+    ParseOptions::builder().limits(limits).json5(true).build()
+}
+
+fn error(source: &str, limits: Limits) -> Option<(usize, String)> {
+    let options = options(limits);
+    let result = parse_with_exit_signal(source, |_, _| false, &options)
+        .err()
+        .map(|err| (err.at, format!("{:?}", err.reason)));
+    let from_bytes = parse_bytes(source.as_bytes(), |_, _| false, &options)
+        .err()
+        .map(|err| (err.at, format!("{:?}", err.reason)));
+    let from_is_valid = is_valid(source, &options)
+        .err()
+        .map(|err| (err.at, format!("{:?}", err.reason)));
+    assert_eq!(result, from_bytes, "{source}");
+    assert_eq!(result, from_is_valid, "{source}");
+    result
+}
+
+#[test]
+fn string_length() {
+    let limits = Limits {
+        max_string_len: Some(3),
+        ..Limits::default()
+    };
+    assert_eq!(error(r#"{ "abc": "def" }"#, limits), None);
+    assert_eq!(
+        error(r#"{ "abc": "defg" }"#, limits),
+        Some((9, "StringTooLong".into()))
+    );
+    assert_eq!(
+        error(r#"{ "abcd": "e" }"#, limits),
+        Some((2, "StringTooLong".into()))
+    );
+    assert_eq!(
+        error("{ abcd: 'e' }", limits),
+        Some((2, "StringTooLong".into()))
+    );
+    // Escapes are counted as in the source
+    assert_eq!(
+        error(r#"["\n\n"]"#, limits),
+        Some((1, "StringTooLong".into()))
+    );
+}
+
+#[test]
+fn keys_per_object() {
+    let limits = Limits {
+        max_keys_per_object: Some(2),
+        ..Limits::default()
+    };
+    // Counts are per object
+    assert_eq!(
+        error(
+            r#"{ "a": { "b": 1, "c": 2 }, "d": [{ "e": 1, "f": 2 }] }"#,
+            limits
+        ),
+        None
+    );
+    assert_eq!(
+        error(r#"{ "a": { "b": 1, "c": 2 }, "d": 1, "e": 2 }"#, limits),
+        Some((35, "TooManyKeys".into()))
+    );
+}
+
+#[test]
+fn total_values() {
+    let limits = Limits {
+        max_total_values: Some(5),
+        ..Limits::default()
+    };
+    assert_eq!(error(r#"{ "a": [1, 2], "b": {} }"#, limits), None);
+    assert_eq!(error("[1, [2], 3]", limits), None);
+    assert_eq!(
+        error(r#"{ "a": [1, 2], "b": 3, "c": 4 }"#, limits),
+        Some((28, "TooManyValues".into()))
+    );
+}
+
+#[test]
+fn not_enforced_by_default() {
+    let source = format!("[{}]", "\"aaaa\", ".repeat(100) + "1");
+    assert!(parse_with_exit_signal(&source, |_, _| false, &ParseOptions::default()).is_ok());
+}
+
+#[test]
+fn input_size() {
+    let limits = Limits {
+        max_bytes: Some(12),
+        ..Limits::default()
+    };
+    assert_eq!(error("[1, 2, 3, 4]", limits), None);
+    assert_eq!(
+        error("[1, 2, 3, 45]", limits),
+        Some((12, "InputTooLarge".into()))
+    );
+    // Not skipped over in strings
+    assert_eq!(
+        error(r#"["a long string"]"#, limits),
+        Some((12, "InputTooLarge".into()))
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn deadline() {
+    use std::time::{Duration, Instant};
+
+    let source = format!("[{}]", "\"aaaa\", ".repeat(2000) + "1");
+    let passed = Limits {
+        deadline: Some(Instant::now()),
+        ..Limits::default()
+    };
+    assert_eq!(error(&source, passed), Some((0, "DeadlineExceeded".into())));
+
+    let later = Limits {
+        deadline: Some(Instant::now() + Duration::from_mins(1)),
+        ..Limits::default()
+    };
+    assert_eq!(error(&source, later), None);
+}
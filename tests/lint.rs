@@ -0,0 +1,67 @@
+use simple_json_parser::{lint, Lint, LintKind, LintOptions, ParseOptions};
+
+fn kinds_and_text<'a>(source: &'a str, options: &LintOptions) -> Vec<(LintKind, &'a str)> {
+    lint(source, options)
+        .unwrap()
+        .into_iter()
+        .map(|Lint { kind, span }| (kind, span.slice(source)))
+        .collect()
+}
+
+#[test]
+fn config_file() {
+    let source = r#"{
+        // comment
+        "name": "ezno",
+        "paths": ["C:\x41", 'a\'b', "\ud800"],
+        "count": +1,
+        "ratio": 0x10,
+        "nested": { "b": 1, "a": [1, 2,], },
+        "name": "again",
+    }"#;
+
+    assert_eq!(
+        kinds_and_text(source, &LintOptions::default()),
+        [
+            (LintKind::SuspiciousEscape, r"\x"),
+            (LintKind::SuspiciousEscape, r"\'"),
+            (LintKind::SuspiciousEscape, r"\u"),
+            (LintKind::NonStandardNumber, "+1"),
+            (LintKind::NonStandardNumber, "0x10"),
+            (LintKind::TrailingComma, ","),
+            (LintKind::TrailingComma, ","),
+            (LintKind::DuplicateKey, "\"name\""),
+            (LintKind::TrailingComma, ","),
+        ]
+    );
+
+    let sorted_only = LintOptions {
+        duplicate_keys: false,
+        trailing_commas: false,
+        non_standard_numbers: false,
+        unsorted_keys: true,
+        suspicious_escapes: false,
+        ..LintOptions::default()
+    };
+    assert_eq!(
+        kinds_and_text(source, &sorted_only),
+        [
+            (LintKind::UnsortedKey, "\"count\""),
+            (LintKind::UnsortedKey, "\"nested\""),
+            (LintKind::UnsortedKey, "\"a\""),
+            (LintKind::UnsortedKey, "\"name\""),
+        ]
+    );
+}
+
+#[test]
+fn clean_and_invalid() {
+    let source = r#"{ "a": [1, -2.5e3, "\n\u00e9"], "b": { "a": null } }"#;
+    assert!(lint(source, &LintOptions::default()).unwrap().is_empty());
+
+    let strict = LintOptions {
+        parse: ParseOptions::default(),
+        ..LintOptions::default()
+    };
+    assert!(lint("[1,]", &strict).is_err());
+}
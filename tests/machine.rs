@@ -0,0 +1,92 @@
+use simple_json_parser::{Machine, ParseOptions, Step, ValueKind};
+
+/// Drives the machine from chunks (like a rope), collecting the source of each value
+fn values(chunks: &[&str]) -> Result<Vec<String>, usize> {
+    let source = chunks.concat();
+    let mut machine = Machine::new(&ParseOptions::default());
+    let mut values = Vec::new();
+    let mut on_step = |step: Step| {
+        if let Step::Value { start, end, .. } = step {
+            values.push(source[start..end].to_owned());
+        }
+    };
+
+    let mut offset = 0;
+    for chunk in chunks {
+        for (idx, chr) in chunk.char_indices() {
+            loop {
+                let step = machine.push(offset + idx, chr).map_err(|err| err.at)?;
+                on_step(step);
+                if !matches!(step, Step::Value { retry: true, .. }) {
+                    break;
+                }
+            }
+        }
+        offset += chunk.len();
+    }
+    while let Some(step) = machine.finish(offset).map_err(|err| err.at)? {
+        on_step(step);
+    }
+    Ok(values)
+}
+
+#[test]
+fn chunks() {
+    let chunks = [
+        r#"{ "a": [1, "tw"#,
+        r#"o", { "b": nu"#,
+        "ll }], \"c\": 3.5 } ",
+    ];
+    assert_eq!(values(&chunks).unwrap(), ["1", "\"two\"", "null", "3.5"]);
+
+    assert_eq!(values(&["[1, ", "2"]), Err(5));
+    assert_eq!(values(&["12"]).unwrap(), ["12"]);
+}
+
+#[test]
+fn steps() {
+    let source = r#"{"k": []}"#;
+    let mut machine = Machine::new(&ParseOptions::default());
+    let steps = source
+        .char_indices()
+        .map(|(idx, chr)| machine.push(idx, chr).unwrap())
+        .filter(|step| *step != Step::Continue)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        steps,
+        [
+            Step::StartObject { at: 0 },
+            Step::Key {
+                start: 1,
+                end: 4,
+                quoted: true
+            },
+            Step::StartArray { at: 6 },
+            Step::EndArray { at: 7 },
+            Step::EndObject { at: 8 },
+        ]
+    );
+    assert!(machine.is_at_root());
+    assert_eq!(machine.depth(), 0);
+
+    let mut machine = Machine::new(&ParseOptions::default());
+    machine.push(0, '[').unwrap();
+    let step = machine.push(1, 't').unwrap();
+    assert_eq!(step, Step::Continue);
+    assert_eq!(machine.depth(), 1);
+    for (idx, chr) in "rue".char_indices() {
+        let step = machine.push(idx + 2, chr).unwrap();
+        if idx == 2 {
+            assert_eq!(
+                step,
+                Step::Value {
+                    kind: ValueKind::Boolean(true),
+                    start: 1,
+                    end: 5,
+                    retry: false
+                }
+            );
+        }
+    }
+}
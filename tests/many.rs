@@ -0,0 +1,53 @@
+use simple_json_parser::{parse_many, JSONParseErrorReason, ParseOptions, Span};
+
+#[test]
+fn concatenated_documents() {
+    let source = "{\"a\":1} {\"b\":[2]}\n3 \"four\"[]{} // end\n 5";
+
+    let mut found = Vec::new();
+    let documents = parse_many(
+        source,
+        |document, keys, value| found.push(format!("{document} {keys:?} -> {value:?}")),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        documents
+            .iter()
+            .map(|span| span.slice(source))
+            .collect::<Vec<_>>(),
+        ["{\"a\":1}", "{\"b\":[2]}", "3", "\"four\"", "[]", "{}", "5"]
+    );
+    assert_eq!(
+        found,
+        [
+            r#"0 [Slice("a")] -> Number("1")"#,
+            r#"1 [Slice("b"), Index(0)] -> Number("2")"#,
+            "2 [] -> Number(\"3\")",
+            "3 [] -> String(\"four\")",
+            "6 [] -> Number(\"5\")",
+        ]
+    );
+}
+
+#[test]
+fn empty_and_errors() {
+    let documents = parse_many(" \n ", |_, _, _| {}, &ParseOptions::default()).unwrap();
+    assert!(documents.is_empty());
+
+    let documents = parse_many("1", |_, _, _| {}, &ParseOptions::default()).unwrap();
+    assert_eq!(documents, [Span { start: 0, end: 1 }]);
+
+    let err = parse_many(
+        r#"{"a": 1} {"b": }"#,
+        |_, _, _| {},
+        &ParseOptions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+    assert_eq!(err.at, 15);
+}
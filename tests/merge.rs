@@ -0,0 +1,43 @@
+use simple_json_parser::{merge, MergeStrategy};
+
+#[test]
+fn deep_merge() {
+    let base = r#"{
+        "name": "app",
+        "server": { "port": 80, "hosts": ["a"], "tls": { "enabled": false } },
+        "tags": [1, 2]
+    }"#;
+    let overlay = r#"{
+        /* user config */
+        "server": { "hosts": ["b", "c"], "tls": { "enabled": true }, "timeout": null },
+        "tags": {},
+        "extra": [{ "k": 1 }]
+    }"#;
+
+    assert_eq!(
+        merge(base, overlay, MergeStrategy::Replace).unwrap(),
+        r#"{"name":"app","server":{"port":80,"hosts":["b","c"],"tls":{"enabled":true},"timeout":null},"tags":{},"extra":[{"k":1}]}"#
+    );
+    assert_eq!(
+        merge(base, overlay, MergeStrategy::Concat).unwrap(),
+        r#"{"name":"app","server":{"port":80,"hosts":["a","b","c"],"tls":{"enabled":true},"timeout":null},"tags":{},"extra":[{"k":1}]}"#
+    );
+}
+
+#[test]
+fn non_objects() {
+    assert_eq!(
+        merge("[1]", "[2, 3]", MergeStrategy::Concat).unwrap(),
+        "[1,2,3]"
+    );
+    assert_eq!(
+        merge("[1]", "[2, 3]", MergeStrategy::Replace).unwrap(),
+        "[2,3]"
+    );
+    assert_eq!(
+        merge(r#"{ "a": 1 }"#, "null", MergeStrategy::Replace).unwrap(),
+        "null"
+    );
+    assert!(merge(r#"{ "a": 1 "#, "{}", MergeStrategy::Replace).is_err());
+    assert!(merge("{}", "[1,", MergeStrategy::Replace).is_err());
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "msgpack")]
+
+use simple_json_parser::{json_to_msgpack, JSONReadError, ParseOptions};
+
+fn msgpack(source: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    json_to_msgpack(source, &mut out, &ParseOptions::json5()).unwrap();
+    out
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(msgpack("0"), [0x00]);
+    assert_eq!(msgpack("127"), [0x7F]);
+    assert_eq!(msgpack("200"), [0xCC, 200]);
+    assert_eq!(msgpack("1000"), [0xCD, 0x03, 0xE8]);
+    assert_eq!(msgpack("-32"), [0xE0]);
+    assert_eq!(msgpack("-33"), [0xD0, 0xDF]);
+    assert_eq!(msgpack("-1000"), [0xD1, 0xFC, 0x18]);
+    assert_eq!(
+        msgpack("18446744073709551615"),
+        [0xCF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+    assert_eq!(msgpack("1.5"), [0xCB, 0x3F, 0xF8, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(msgpack("true"), [0xC3]);
+    assert_eq!(msgpack("false"), [0xC2]);
+    assert_eq!(msgpack("null"), [0xC0]);
+    assert_eq!(msgpack(r#""a\nb""#), [0xA3, b'a', b'\n', b'b']);
+
+    let long = "x".repeat(40);
+    let mut expected = vec![0xD9, 40];
+    expected.extend_from_slice(long.as_bytes());
+    assert_eq!(msgpack(&format!("'{long}'")), expected);
+}
+
+#[test]
+fn containers() {
+    assert_eq!(msgpack("{}"), [0x80]);
+    assert_eq!(msgpack("[]"), [0x90]);
+    assert_eq!(
+        msgpack("{ 'k': [1, {}, [2]], /* comment */ 'e': [] }"),
+        [0x82, 0xA1, b'k', 0x93, 0x01, 0x80, 0x91, 0x02, 0xA1, b'e', 0x90]
+    );
+
+    let items = vec!["1"; 20].join(",");
+    let mut expected = vec![0xDC, 0, 20];
+    expected.extend([0x01; 20]);
+    assert_eq!(msgpack(&format!("[{items}]")), expected);
+}
+
+#[test]
+fn errors() {
+    let mut out = Vec::new();
+    let result = json_to_msgpack("[1, }", &mut out, &ParseOptions::default());
+    assert!(matches!(result, Err(JSONReadError::Parse(_))));
+    assert!(out.is_empty());
+}
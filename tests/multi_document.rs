@@ -0,0 +1,78 @@
+use simple_json_parser::{parse_all, JSONKey, JSONParseErrorReason, ParseOptions, RootJSONValue};
+
+#[test]
+fn documents() {
+    let source = "{\"a\": [1]}{\"b\": true}\n// between\n\"text\" 42 []\n";
+    let mut found = Vec::new();
+    let documents = parse_all(
+        source,
+        |document, keys, value| found.push(format!("{document} {keys:?} {value:?}")),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    let slices = documents
+        .iter()
+        .map(|span| span.slice(source))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        slices,
+        ["{\"a\": [1]}", "{\"b\": true}", "\"text\"", "42", "[]"]
+    );
+    assert_eq!(
+        found,
+        [
+            r#"0 [Slice("a"), Index(0)] Number("1")"#,
+            r#"1 [Slice("b")] Boolean(true)"#,
+            r#"2 [] String("text")"#,
+            r#"3 [] Number("42")"#,
+        ]
+    );
+
+    assert!(parse_all("", |_, _, _| {}, &ParseOptions::default())
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        parse_all("[1] [2]\n", |_, _, _| {}, &ParseOptions::STRICT_JSON)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn after_script_tag() {
+    let source = r#"<script type="application/json">{"org": 10}</script>"#;
+    let source = source
+        .strip_prefix(r#"<script type="application/json">"#)
+        .unwrap();
+
+    let err = parse_all(
+        source,
+        |_, keys, value| {
+            assert_eq!(keys, [JSONKey::Slice("org")]);
+            assert_eq!(value, RootJSONValue::Number("10"));
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap_err();
+    assert_eq!(&source[err.at..], "</script>");
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { found: Some('<') }
+    ));
+}
+
+#[test]
+fn error_offset() {
+    let source = "[1] [2, ] [3]";
+    let mut documents = Vec::new();
+    let err = parse_all(
+        source,
+        |document, _, _| documents.push(document),
+        &ParseOptions::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.at, 8);
+    assert_eq!(documents, [0, 1]);
+}
@@ -0,0 +1,204 @@
+use simple_json_parser::{
+    parse, parse_with_exit_signal, JSONParseErrorReason, ParseOptions, RootJSONValue, Value,
+};
+
+#[test]
+fn valid_numbers() {
+    for number in [
+        "0", "-0", "12", "-12", "0.5", "12.25", "1e10", "1E+2", "-1.5e-3", "0e0",
+    ] {
+        let source = format!("[{number}]");
+        let mut found = None;
+        parse(&source, |_keys, value| found = Some(format!("{value:?}"))).unwrap();
+        assert_eq!(
+            found,
+            Some(format!("{:?}", RootJSONValue::Number(number))),
+            "{number}"
+        );
+    }
+
+    // At end of input
+    let mut found = None;
+    parse("-3.5", |_keys, value| found = Some(format!("{value:?}"))).unwrap();
+    assert_eq!(found.as_deref(), Some(r#"Number("-3.5")"#));
+}
+
+#[test]
+fn invalid_numbers() {
+    for (number, at) in [
+        ("1..2", 3),
+        ("-", 2),
+        ("0x10", 2),
+        ("01", 2),
+        ("1.", 3),
+        (".5", 1),
+        ("1e", 3),
+        ("1e+", 4),
+        ("--1", 2),
+        ("1.5.", 4),
+    ] {
+        let source = format!("[{number}]");
+        let result = parse(&source, |_, _| {});
+        let err = result.expect_err(number);
+        if number == ".5" {
+            assert!(matches!(
+                err.reason,
+                JSONParseErrorReason::ExpectedValue { .. }
+            ));
+        } else {
+            assert!(
+                matches!(err.reason, JSONParseErrorReason::InvalidNumber),
+                "{number}"
+            );
+        }
+        assert_eq!(err.at, at, "{number}");
+    }
+
+    let err = parse("12e", |_, _| {}).unwrap_err();
+    assert!(matches!(err.reason, JSONParseErrorReason::InvalidNumber));
+    assert_eq!(err.at, 3);
+}
+
+#[test]
+fn typed_numbers() {
+    fn typed(source: &str, json5: bool) -> Vec<String> {
+        let mut values = Vec::new();
+        parse_with_exit_signal(
+            source,
+            |_keys, value| {
+                values.push(format!("{value:?}"));
+                false
+            },
+            &ParseOptions::builder()
+                .typed_numbers(true)
+                .json5(json5)
+                .build(),
+        )
+        .unwrap();
+        values
+    }
+
+    assert_eq!(
+        typed(
+            "[0, -12, 9223372036854775807, -9223372036854775808, 9223372036854775808, 0.5, 1e2, 1e400, \"1\"]",
+            false
+        ),
+        [
+            "Integer(0)",
+            "Integer(-12)",
+            "Integer(9223372036854775807)",
+            "Integer(-9223372036854775808)",
+            r#"BigNumber("9223372036854775808")"#,
+            "Float(0.5)",
+            "Float(100.0)",
+            r#"BigNumber("1e400")"#,
+            r#"String("1")"#,
+        ]
+    );
+    assert_eq!(
+        typed(
+            "[0x1F, -0xff, +3, Infinity, -Infinity, 0x10000000000000000]",
+            true
+        ),
+        [
+            "Integer(31)",
+            "Integer(-255)",
+            "Integer(3)",
+            "Float(inf)",
+            "Float(-inf)",
+            r#"BigNumber("0x10000000000000000")"#,
+        ]
+    );
+    assert_eq!(typed("NaN", true), ["Float(NaN)"]);
+}
+
+#[test]
+fn lenient_numbers() {
+    let options = ParseOptions::builder().lenient_numbers(true).build();
+    let source = "[0xFF, +1, .5, -.5, 5., 5.e2, 1_000_000, 0xf_f, 1.000_1, 1e1_0]";
+    let mut found = Vec::new();
+    parse_with_exit_signal(
+        source,
+        |_keys, value| {
+            found.push(format!("{value:?}"));
+            false
+        },
+        &options,
+    )
+    .unwrap();
+    assert_eq!(
+        found,
+        [
+            r#"Number("0xFF")"#,
+            r#"Number("+1")"#,
+            r#"Number(".5")"#,
+            r#"Number("-.5")"#,
+            r#"Number("5.")"#,
+            r#"Number("5.e2")"#,
+            r#"Number("1_000_000")"#,
+            r#"Number("0xf_f")"#,
+            r#"Number("1.000_1")"#,
+            r#"Number("1e1_0")"#,
+        ]
+    );
+
+    for (number, at) in [
+        (".", 2),
+        ("-.", 3),
+        ("1__0", 3),
+        ("1_", 3),
+        ("_1", 1),
+        ("0_1", 2),
+        ("1_.5", 3),
+        ("1._5", 3),
+        ("0x", 3),
+        ("0x_f", 3),
+    ] {
+        let source = format!("[{number}]");
+        let err = parse_with_exit_signal(&source, |_, _| false, &options).expect_err(number);
+        assert_eq!(err.at, at, "{number}");
+    }
+
+    // Off by default
+    assert!(parse("[+1]", |_, _| {}).is_err());
+    assert!(parse("[1_0]", |_, _| {}).is_err());
+}
+
+#[test]
+fn non_finite_numbers() {
+    let options = ParseOptions::builder()
+        .allow_non_finite_numbers(true)
+        .build();
+    let mut found = Vec::new();
+    parse_with_exit_signal(
+        r#"{"a": NaN, "b": Infinity, "c": -Infinity, "d": 1}"#,
+        |_keys, value| {
+            found.push(format!("{value:?}"));
+            false
+        },
+        &options,
+    )
+    .unwrap();
+    assert_eq!(
+        found,
+        [
+            "NonFinite(NaN)",
+            "NonFinite(inf)",
+            "NonFinite(-inf)",
+            r#"Number("1")"#
+        ]
+    );
+
+    assert_eq!(
+        Value::from(RootJSONValue::NonFinite(f64::NEG_INFINITY)),
+        Value::Number("-Infinity".to_owned())
+    );
+
+    let err = parse("[NaN]", |_, _| {}).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { found: Some('N') }
+    ));
+    let err = parse_with_exit_signal("[Infinite]", |_, _| false, &options).unwrap_err();
+    assert_eq!(err.at, 8);
+}
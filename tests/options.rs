@@ -1,4 +1,6 @@
-use simple_json_parser::{parse_with_exit_signal, JSONKey, RootJSONValue};
+use simple_json_parser::{
+    parse_with_exit_signal, JSONKey, Limits, ParseOptions, ParseOptionsBuilder, RootJSONValue,
+};
 
 #[test]
 fn disable_comments() {
@@ -17,19 +19,128 @@ fn disable_comments() {
             }
             false
         },
-        true,
-        true,
+        &ParseOptions::builder()
+            .exit_on_first_value(true)
+            .allow_comments(true)
+            .build(),
     );
     let without_comments = parse_with_exit_signal(
         source,
-        |_keys, _value| {
-            eprintln!("{:?}", (_keys, _value));
+        |keys, value| {
+            eprintln!("{:?}", (keys, value));
             false
         },
-        true,
-        false,
+        &ParseOptions::builder()
+            .exit_on_first_value(true)
+            .allow_comments(false)
+            .build(),
     );
 
     assert!(with_comments.is_ok());
     assert!(without_comments.is_err());
 }
+
+#[test]
+fn max_depth() {
+    use simple_json_parser::JSONParseErrorReason;
+
+    let options = ParseOptions::builder().max_depth(2).build();
+    assert!(parse_with_exit_signal(r#"{ "a": [1, 2], "b": {} }"#, |_, _| false, &options).is_ok());
+
+    let source = r#"{ "a": [1, { "b": 2 }] }"#;
+    let err = parse_with_exit_signal(source, |_, _| false, &options).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::DepthLimitExceeded
+    ));
+    assert_eq!(err.at, source.find("{ \"b\"").unwrap());
+
+    let deep = "[".repeat(100_000);
+    let err = parse_with_exit_signal(&deep, |_, _| false, &options).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::DepthLimitExceeded
+    ));
+}
+
+#[test]
+fn trailing_commas() {
+    use simple_json_parser::JSONParseErrorReason;
+
+    let options = ParseOptions::builder().allow_trailing_commas(true).build();
+    for source in [
+        r#"{ "a": 1, }"#,
+        "[1, 2, ]",
+        r#"{ "a": [true,], "b": {"c": null,}, }"#,
+        "[[],{},]",
+    ] {
+        let result = parse_with_exit_signal(source, |_, _| false, &options);
+        assert_eq!(result.unwrap().bytes_consumed, source.len(), "{source}");
+    }
+
+    // Still only one comma
+    for source in ["[1,,]", r#"{ "a": 1,, }"#, "[,]", "{,}"] {
+        assert!(
+            parse_with_exit_signal(source, |_, _| false, &options).is_err(),
+            "{source}"
+        );
+    }
+
+    let err =
+        parse_with_exit_signal("[1, 2, ]", |_, _| false, &ParseOptions::default()).unwrap_err();
+    assert!(matches!(
+        err.reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+}
+
+#[test]
+fn builder_and_presets() {
+    let options = ParseOptions::builder()
+        .allow_comments(false)
+        .max_depth(64)
+        .limits(Limits {
+            max_string_len: Some(10),
+            ..Limits::default()
+        })
+        .build();
+    assert!(!options.allow_comments);
+    assert_eq!(options.max_depth, Some(64));
+    assert_eq!(options.limits.max_string_len, Some(10));
+    assert!(!options.json5);
+
+    let json5 = ParseOptions::JSON5;
+    assert!(json5.json5 && json5.allow_trailing_commas && json5.allow_comments);
+    assert!(parse_with_exit_signal("{ a: 1, }", |_, _| false, &json5).is_ok());
+
+    let strict_json5 = ParseOptionsBuilder::from(ParseOptions::JSON5)
+        .allow_hash_comments(false)
+        .build();
+    assert!(strict_json5.json5);
+    assert!(parse_with_exit_signal("[1] # hash", |_, _| false, &strict_json5).is_err());
+}
+
+#[test]
+fn strict_json_and_jsonc_presets() {
+    let strict = ParseOptions::strict_json();
+    assert!(parse_with_exit_signal(r#"{"a": [1, true, null]}"#, |_, _| false, &strict).is_ok());
+    for source in [
+        "[1] // comment",
+        "[1,]",
+        "\"a\tb\"",
+        "\u{feff}[1]",
+        "[1] [2]",
+        "",
+        " \n\t",
+    ] {
+        assert!(
+            parse_with_exit_signal(source, |_, _| false, &strict).is_err(),
+            "{source:?}"
+        );
+    }
+
+    let jsonc = ParseOptions::jsonc();
+    assert!(parse_with_exit_signal("{ \"a\": 1, /* b */ }", |_, _| false, &jsonc).is_ok());
+    assert!(parse_with_exit_signal("[1] # hash", |_, _| false, &jsonc).is_err());
+    assert!(parse_with_exit_signal("{ a: 1 }", |_, _| false, &jsonc).is_err());
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "rayon")]
+
+use std::fmt::Write;
+
+use simple_json_parser::{
+    parse_with_exit_signal, parse_with_recovery, validate_array_parallel, Cancellation,
+    JSONParseErrorReason, Limits, ParseOptions,
+};
+
+#[test]
+fn valid_arrays() {
+    let mut large = String::from("[");
+    for idx in 0..10_000 {
+        if idx > 0 {
+            large.push(',');
+        }
+        write!(
+            large,
+            r#"{{"id": {idx}, "name": "item, [{idx}]", "tags": ["a", "}}"]}}"#
+        )
+        .unwrap();
+    }
+    large.push(']');
+
+    for source in [
+        "[]",
+        "[ ]",
+        "[1]",
+        " // header\n[1, \"2\", [3, [4]], {\"5\": [6]}] /* end */",
+        "[/* first */ 1, # second\n 2]",
+        &large,
+    ] {
+        let errors = validate_array_parallel(source, &ParseOptions::default());
+        assert!(errors.is_empty(), "{source:?} {errors:?}");
+    }
+
+    let options = ParseOptions::json5();
+    assert!(validate_array_parallel("[1, 'a]', {b: 2},]", &options).is_empty());
+}
+
+#[test]
+fn invalid_items() {
+    let source = r#"[{"a": 1}, {"a": }, [2, 3], tru, 4]"#;
+    let errors = validate_array_parallel(source, &ParseOptions::default());
+    // Matches parsing sequentially and recovering
+    let sequential = parse_with_recovery(source, |_, _| {}, &ParseOptions::default());
+    assert_eq!(
+        errors.iter().map(|err| err.at).collect::<Vec<_>>(),
+        sequential.iter().map(|err| err.at).collect::<Vec<_>>()
+    );
+    assert!(matches!(
+        errors[0].reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+
+    let errors = validate_array_parallel("[1,, 2]", &ParseOptions::default());
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].reason,
+        JSONParseErrorReason::ExpectedValue { .. }
+    ));
+
+    // Trailing comma
+    let errors = validate_array_parallel("[1, 2,]", &ParseOptions::default());
+    assert_eq!(errors.len(), 1);
+    let options = ParseOptions::builder().allow_trailing_commas(true).build();
+    assert!(validate_array_parallel("[1, 2,]", &options).is_empty());
+}
+
+#[test]
+fn invalid_frame() {
+    for source in ["[1, 2] 3", "[1, 2", r#"{"a": 1}"#, "[1, \"2]", "[1}"] {
+        let errors = validate_array_parallel(source, &ParseOptions::default());
+        let sequential = parse_with_recovery(source, |_, _| {}, &ParseOptions::default());
+        assert_eq!(errors.is_empty(), sequential.is_empty(), "{source}");
+        assert_eq!(
+            errors.first().map(|err| err.at),
+            sequential.first().map(|err| err.at),
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn depth() {
+    let options = ParseOptions::builder().max_depth(2).build();
+    assert!(validate_array_parallel("[[1], [2]]", &options).is_empty());
+    let errors = validate_array_parallel("[[1], [[2]]]", &options);
+    assert!(matches!(
+        errors[0].reason,
+        JSONParseErrorReason::DepthLimitExceeded
+    ));
+    assert_eq!(errors[0].at, 7);
+}
+
+#[test]
+fn document_limits() {
+    let source = "[1, [2, 3], {\"a\": 4}, 5]";
+    for limits in [
+        Limits {
+            max_total_values: Some(4),
+            ..Limits::default()
+        },
+        Limits {
+            max_total_values: Some(7),
+            ..Limits::default()
+        },
+        Limits {
+            max_bytes: Some(12),
+            ..Limits::default()
+        },
+    ] {
+        let options = ParseOptions::builder().limits(limits).build();
+        let sequential = parse_with_exit_signal(source, |_, _| false, &options).unwrap_err();
+        let errors = validate_array_parallel(source, &options);
+        assert_eq!(errors.len(), 1, "{limits:?}");
+        assert_eq!(
+            (errors[0].at, &errors[0].reason),
+            (sequential.at, &sequential.reason),
+            "{limits:?}"
+        );
+    }
+
+    let options = ParseOptions::builder()
+        .limits(Limits {
+            max_total_values: Some(8),
+            ..Limits::default()
+        })
+        .build();
+    assert!(validate_array_parallel(source, &options).is_empty());
+}
+
+#[test]
+fn cancelled_between_items() {
+    let source = format!("[{}0]", "0, ".repeat(1000));
+    let options = ParseOptions::builder()
+        .cancellation(Cancellation::new(100, || true))
+        .build();
+    let errors = validate_array_parallel(&source, &options);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].reason, JSONParseErrorReason::Cancelled));
+}
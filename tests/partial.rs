@@ -0,0 +1,67 @@
+use simple_json_parser::{parse_partial, Completeness, Missing, ParseOptions};
+
+fn partial(source: &str) -> (Vec<String>, Completeness) {
+    let mut values = Vec::new();
+    let result = parse_partial(
+        source,
+        |keys, value| values.push(format!("{keys:?} -> {value:?}")),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    (values, result)
+}
+
+#[test]
+fn missing() {
+    use Missing::{
+        Colon, EndOfArray, EndOfComment, EndOfObject, Key, Literal, Number, String, Value,
+    };
+
+    for (source, expected) in [
+        (r#"{"a": [1, 2"#, vec![Number, EndOfArray, EndOfObject]),
+        (r#"{"a": [1, 2,"#, vec![Value, EndOfArray, EndOfObject]),
+        (r#"{"a": [1, 2]"#, vec![EndOfObject]),
+        (r#"{"a": {"b"#, vec![Key, EndOfObject, EndOfObject]),
+        (r#"{"a": {"b" "#, vec![Colon, EndOfObject, EndOfObject]),
+        (r#"{"a": {"b": "#, vec![Value, EndOfObject, EndOfObject]),
+        (r#"{"a": {"b": nu"#, vec![Literal, EndOfObject, EndOfObject]),
+        (r#"{"a": {"b": "te"#, vec![String, EndOfObject, EndOfObject]),
+        (r#"{"a": 1,"#, vec![Key, EndOfObject]),
+        ("[1, /* comment", vec![EndOfComment, Value, EndOfArray]),
+        ("[1 // comment", vec![EndOfArray]),
+        ("-", vec![Number]),
+        ("[", vec![EndOfArray]),
+    ] {
+        assert_eq!(
+            partial(source).1,
+            Completeness::Incomplete { missing: expected },
+            "{source}"
+        );
+    }
+}
+
+#[test]
+fn values_before_end() {
+    let (values, result) = partial(r#"{"name": "ezno", "tags": ["a", "b"], "count": 12"#);
+    assert_eq!(
+        values,
+        [
+            r#"[Slice("name")] -> String("ezno")"#,
+            r#"[Slice("tags"), Index(0)] -> String("a")"#,
+            r#"[Slice("tags"), Index(1)] -> String("b")"#,
+        ]
+    );
+    assert_eq!(
+        result,
+        Completeness::Incomplete {
+            missing: vec![Missing::Number, Missing::EndOfObject]
+        }
+    );
+
+    let (values, result) = partial("[1, 2] ");
+    assert_eq!(values.len(), 2);
+    assert_eq!(result, Completeness::Complete);
+    assert_eq!(partial("12").1, Completeness::Complete);
+
+    assert!(parse_partial(r#"{"a" 1"#, |_, _| {}, &ParseOptions::default()).is_err());
+}
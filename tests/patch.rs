@@ -0,0 +1,142 @@
+use simple_json_parser::{apply_patch, merge_patch, JSONPatchError};
+
+#[test]
+fn operations() {
+    let source = r#"{"a": 1, "b": [1, 2, 3], "c": {"d": null}}"#;
+
+    let cases = [
+        (
+            r#"[{"op": "add", "path": "/e", "value": [true]}]"#,
+            r#"{"a": 1, "b": [1, 2, 3], "c": {"d": null},"e":[true]}"#,
+        ),
+        (
+            r#"[{"op": "add", "path": "/b/1", "value": 5}]"#,
+            r#"{"a": 1, "b": [1, 5,2, 3], "c": {"d": null}}"#,
+        ),
+        (
+            r#"[{"op": "add", "path": "/b/3", "value": 5}]"#,
+            r#"{"a": 1, "b": [1, 2, 3,5], "c": {"d": null}}"#,
+        ),
+        (
+            r#"[{"op": "remove", "path": "/a"}]"#,
+            r#"{"b": [1, 2, 3], "c": {"d": null}}"#,
+        ),
+        (
+            r#"[{"op": "remove", "path": "/b/2"}]"#,
+            r#"{"a": 1, "b": [1, 2], "c": {"d": null}}"#,
+        ),
+        (
+            r#"[{"op": "remove", "path": "/c/d"}]"#,
+            r#"{"a": 1, "b": [1, 2, 3], "c": {}}"#,
+        ),
+        (
+            r#"[{"op": "replace", "path": "/c", "value": "x"}]"#,
+            r#"{"a": 1, "b": [1, 2, 3], "c": "x"}"#,
+        ),
+        (
+            r#"[{"op": "move", "from": "/a", "path": "/c/a"}]"#,
+            r#"{"b": [1, 2, 3], "c": {"d": null,"a":1}}"#,
+        ),
+        (
+            r#"[{"op": "copy", "from": "/b", "path": "/c/d"}]"#,
+            r#"{"a": 1, "b": [1, 2, 3], "c": {"d": [1, 2, 3]}}"#,
+        ),
+        (
+            r#"[{"op": "test", "path": "/c", "value": {"d": null}}]"#,
+            source,
+        ),
+        (r#"[{"op": "replace", "path": "", "value": []}]"#, "[]"),
+    ];
+
+    for (patch, expected) in cases {
+        assert_eq!(apply_patch(source, patch).unwrap(), expected, "{patch}");
+    }
+}
+
+#[test]
+fn keeps_formatting() {
+    let source = "{\n  // comment\n  \"a\": 1,\n  \"b\": 2\n}";
+    let patch = r#"[{"op": "remove", "path": "/a"}, {"op": "add", "path": "/c", "value": 3}]"#;
+    assert_eq!(
+        apply_patch(source, patch).unwrap(),
+        "{\n  // comment\n  \"b\": 2,\"c\":3\n}"
+    );
+}
+
+#[test]
+fn errors() {
+    let source = r#"{"a": [1]}"#;
+
+    let result = apply_patch(source, r#"[{"op": "test", "path": "/a/0", "value": 2}]"#);
+    assert!(matches!(
+        result,
+        Err(JSONPatchError::TestFailed { index: 0 })
+    ));
+
+    let result = apply_patch(
+        source,
+        r#"[{"op": "add", "path": "/b", "value": 1}, {"op": "remove", "path": "/c"}]"#,
+    );
+    assert!(matches!(result, Err(JSONPatchError::NotFound { index: 1 })));
+
+    let result = apply_patch(source, r#"[{"op": "add", "path": "/a/5", "value": 1}]"#);
+    assert!(matches!(result, Err(JSONPatchError::NotFound { index: 0 })));
+
+    let result = apply_patch(source, r#"[{"op": "jump", "path": "/a"}]"#);
+    assert!(matches!(
+        result,
+        Err(JSONPatchError::InvalidOperation { index: 0 })
+    ));
+
+    let result = apply_patch(source, r#"[{"op": "move", "from": "/a", "path": "/a/0"}]"#);
+    assert!(matches!(
+        result,
+        Err(JSONPatchError::InvalidOperation { index: 0 })
+    ));
+
+    let result = apply_patch(source, r#"{"op": "remove", "path": "/a"}"#);
+    assert!(matches!(
+        result,
+        Err(JSONPatchError::InvalidOperation { .. })
+    ));
+}
+
+#[test]
+fn merge_patches() {
+    // From RFC 7386 appendix A
+    let cases = [
+        (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"a":null}"#, "{}"),
+        (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+        (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+        (
+            r#"{"a": {"b": "c"}}"#,
+            r#"{"a": {"b": "d", "c": null}}"#,
+            r#"{"a":{"b":"d"}}"#,
+        ),
+        (r#"{"a": [{"b":"c"}]}"#, r#"{"a": [1]}"#, r#"{"a":[1]}"#),
+        (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+        (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+        (r#"{"a":"foo"}"#, "null", "null"),
+        (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+        (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+        ("[1,2]", r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+        ("{}", r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#),
+    ];
+
+    for (target, patch, expected) in cases {
+        let mut out = String::new();
+        merge_patch(target, patch, &mut out).unwrap();
+        assert_eq!(out, expected, "{target} + {patch}");
+    }
+}
+
+#[test]
+fn merge_patch_comments() {
+    let base = "{\n  // Default\n  \"port\": 80, /* a */ \"host\": \"localhost\"\n}";
+    let mut out = String::new();
+    merge_patch(base, r#"{"port": 8080}"#, &mut out).unwrap();
+    assert_eq!(out, r#"{"port":8080,"host":"localhost"}"#);
+}
@@ -0,0 +1,84 @@
+use simple_json_parser::{
+    get_at_pointer, get_bool_at, get_f64_at, get_i64_at, get_string_at, RootJSONValue,
+};
+
+const SOURCE: &str = r#"{
+    "build": {
+        "entries": [
+            { "builder": "rollup", "input": "./src/index" },
+            { "builder": "rollup", "input": "./src/initialised" }
+        ]
+    },
+    "a/b": 1,
+    "m~n": 2,
+    "\u0041": 3,
+    "": 4
+}"#;
+
+#[test]
+fn pointers() {
+    assert_eq!(
+        get_at_pointer(SOURCE, "/build/entries/1/input").unwrap(),
+        Some(RootJSONValue::String("./src/initialised"))
+    );
+    assert_eq!(
+        get_at_pointer(SOURCE, "/a~1b").unwrap(),
+        Some(RootJSONValue::Number("1"))
+    );
+    assert_eq!(
+        get_at_pointer(SOURCE, "/m~0n").unwrap(),
+        Some(RootJSONValue::Number("2"))
+    );
+    assert_eq!(
+        get_at_pointer(SOURCE, "/A").unwrap(),
+        Some(RootJSONValue::Number("3"))
+    );
+    assert_eq!(
+        get_at_pointer(SOURCE, "/").unwrap(),
+        Some(RootJSONValue::Number("4"))
+    );
+    assert_eq!(
+        get_at_pointer("true", "").unwrap(),
+        Some(RootJSONValue::Boolean(true))
+    );
+
+    // Not found
+    assert_eq!(
+        get_at_pointer(SOURCE, "/build/entries/01/input").unwrap(),
+        None
+    );
+    assert_eq!(get_at_pointer(SOURCE, "/build").unwrap(), None);
+    assert_eq!(get_at_pointer(SOURCE, "build").unwrap(), None);
+}
+
+#[test]
+fn stops_at_value() {
+    // Invalid after the value is not reached
+    let source = r#"{ "a": 1, "b": oops }"#;
+    assert_eq!(
+        get_at_pointer(source, "/a").unwrap(),
+        Some(RootJSONValue::Number("1"))
+    );
+    assert!(get_at_pointer(source, "/c").is_err());
+}
+
+#[test]
+fn typed_getters() {
+    let source = r#"{ "name": "a\"b", "version": 3, "ratio": 0.5, "private": true, "big": 1e3 }"#;
+
+    assert_eq!(
+        get_string_at(source, "/name").unwrap().as_deref(),
+        Some("a\"b")
+    );
+    assert_eq!(get_string_at(source, "/version").unwrap(), None);
+    assert_eq!(get_i64_at(source, "/version").unwrap(), Some(3));
+    assert_eq!(get_i64_at(source, "/ratio").unwrap(), None);
+    assert_eq!(get_i64_at(source, "/big").unwrap(), None);
+    assert_eq!(get_f64_at(source, "/ratio").unwrap(), Some(0.5));
+    assert_eq!(get_f64_at(source, "/version").unwrap(), Some(3.0));
+    assert_eq!(get_bool_at(source, "/private").unwrap(), Some(true));
+    assert_eq!(get_bool_at(source, "/missing").unwrap(), None);
+
+    // Stops parsing at the value
+    assert_eq!(get_i64_at(r#"{"a": 1, "b": "#, "/a").unwrap(), Some(1));
+}
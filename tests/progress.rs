@@ -0,0 +1,70 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use simple_json_parser::{
+    parse_bytes, parse_reader, parse_with_exit_signal, ParseOptions, Progress,
+};
+
+type Calls = Arc<Mutex<Vec<(usize, String)>>>;
+
+fn recording(every: usize) -> (ParseOptions, Calls) {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let recorded = calls.clone();
+    let options = ParseOptions::builder()
+        .progress(Progress::new(every, move |offset, keys| {
+            recorded.lock().unwrap().push((offset, format!("{keys:?}")));
+        }))
+        .build();
+    (options, calls)
+}
+
+#[test]
+fn offsets_and_keys() {
+    let source = r#"{ "a": [1, 2, 3], "b": "a long string value" }"#;
+    let (options, calls) = recording(10);
+    parse_with_exit_signal(source, |_, _| false, &options).unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(
+        *calls,
+        [
+            (10, r#"[Slice("a"), Index(1)]"#.to_owned()),
+            // In the key
+            (20, "[]".to_owned()),
+            // The string content is skipped over
+            (43, r#"[Slice("b")]"#.to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn other_sources() {
+    let source = format!("[{}0]", "0, ".repeat(100));
+
+    let (options, calls) = recording(100);
+    parse_bytes(source.as_bytes(), |_, _| false, &options).unwrap();
+    assert_eq!(
+        calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(offset, _)| *offset)
+            .collect::<Vec<_>>(),
+        [100, 200, 300]
+    );
+
+    // Once for each chunk
+    let (options, calls) = recording(1000);
+    let large = format!("[{}0]", "0, ".repeat(10_000));
+    parse_reader(Cursor::new(large), |_, _| false, &options).unwrap();
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 4);
+    assert!(calls.windows(2).all(|pair| pair[1].0 - pair[0].0 >= 1000));
+}
+
+#[test]
+fn off_by_default() {
+    assert!(ParseOptions::default().progress.is_none());
+    let (options, _) = recording(0);
+    assert!(format!("{options:?}").contains("Progress { every: 1, .. }"));
+}
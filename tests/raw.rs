@@ -0,0 +1,121 @@
+use simple_json_parser::{
+    parse_with_raw_subtrees, raw_slice_at, CapturedValue, KeyPattern, ParseOptions, RootJSONValue,
+};
+
+#[test]
+fn raw_subtrees() {
+    let source = r#"{
+        "name": "pkg",
+        "exports": { ".": { "import": "./index.mjs", "require": "./index.cjs" } },
+        "files": ["dist", "src"],
+        "nested": [{ "data": [1, [2]] }, { "data": {} }],
+        "after": true
+    }"#;
+    let mut values = Vec::new();
+    let result = parse_with_raw_subtrees(
+        source,
+        &[
+            &[KeyPattern::Key("exports")],
+            &[
+                KeyPattern::Key("nested"),
+                KeyPattern::AnyIndex,
+                KeyPattern::Key("data"),
+            ],
+        ],
+        |keys, value| values.push((format!("{keys:?}"), value)),
+        &ParseOptions::default(),
+    );
+    assert_eq!(result.unwrap(), source.len());
+
+    assert_eq!(
+        values,
+        [
+            (
+                r#"[Slice("name")]"#.to_owned(),
+                CapturedValue::Value(RootJSONValue::String("pkg"))
+            ),
+            (
+                r#"[Slice("exports")]"#.to_owned(),
+                CapturedValue::Raw(
+                    r#"{ ".": { "import": "./index.mjs", "require": "./index.cjs" } }"#
+                )
+            ),
+            (
+                r#"[Slice("files"), Index(0)]"#.to_owned(),
+                CapturedValue::Value(RootJSONValue::String("dist"))
+            ),
+            (
+                r#"[Slice("files"), Index(1)]"#.to_owned(),
+                CapturedValue::Value(RootJSONValue::String("src"))
+            ),
+            (
+                r#"[Slice("nested"), Index(0), Slice("data")]"#.to_owned(),
+                CapturedValue::Raw("[1, [2]]")
+            ),
+            (
+                r#"[Slice("nested"), Index(1), Slice("data")]"#.to_owned(),
+                CapturedValue::Raw("{}")
+            ),
+            (
+                r#"[Slice("after")]"#.to_owned(),
+                CapturedValue::Value(RootJSONValue::Boolean(true))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn raw_root() {
+    let source = r#" [1, { "a": 2 }] "#;
+    let mut values = Vec::new();
+    parse_with_raw_subtrees(
+        source,
+        &[&[]],
+        |keys, value| values.push((keys.len(), value)),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(values, [(0, CapturedValue::Raw(r#"[1, { "a": 2 }]"#))]);
+}
+
+#[test]
+fn unbalanced_raw_subtree() {
+    let result = parse_with_raw_subtrees(
+        r#"{ "a": [1, 2 }"#,
+        &[&[KeyPattern::Key("a")]],
+        |_, _| {},
+        &ParseOptions::default(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn slice_at_pointer() {
+    let source = r#"{
+        "a": { "skipped": [1, 2, {}] },
+        "b/c": [10, -2.5e3, "x\"y", { "d": null }],
+        "e": {}
+    }"#;
+    let cases = [
+        ("", Some(source)),
+        ("/a", Some(r#"{ "skipped": [1, 2, {}] }"#)),
+        ("/b~1c/1", Some("-2.5e3")),
+        ("/b~1c/2", Some(r#""x\"y""#)),
+        ("/b~1c/3", Some(r#"{ "d": null }"#)),
+        ("/b~1c/3/d", Some("null")),
+        ("/e", Some("{}")),
+        ("/b~1c/4", None),
+        ("/missing", None),
+        ("no-slash", None),
+    ];
+    for (pointer, expected) in cases {
+        assert_eq!(
+            raw_slice_at(source, pointer).unwrap(),
+            expected,
+            "{pointer}"
+        );
+    }
+
+    // Errors before the value are reported
+    assert!(raw_slice_at(r#"{ "a": [1, }, "b": 2 }"#, "/b").is_err());
+}
@@ -0,0 +1,125 @@
+use simple_json_parser::{JSONKey, JSONParseError, JsonReader, ParseOptions, RootJSONValue};
+
+#[test]
+fn iterate_values() -> Result<(), JSONParseError> {
+    let source = r#"{
+        "name": "ezno",
+        // comment
+        "keywords": ["typescript", "checker"],
+        "private": false,
+        "empty": {},
+        "count": 4
+    }"#;
+
+    let values =
+        JsonReader::new(source, &ParseOptions::default()).collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(
+        values,
+        [
+            (vec![JSONKey::Slice("name")], RootJSONValue::String("ezno")),
+            (
+                vec![JSONKey::Slice("keywords"), JSONKey::Index(0)],
+                RootJSONValue::String("typescript")
+            ),
+            (
+                vec![JSONKey::Slice("keywords"), JSONKey::Index(1)],
+                RootJSONValue::String("checker")
+            ),
+            (
+                vec![JSONKey::Slice("private")],
+                RootJSONValue::Boolean(false)
+            ),
+            (vec![JSONKey::Slice("count")], RootJSONValue::Number("4")),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn next_value_borrows_key_chain() {
+    let mut reader = JsonReader::new("[[1], [2, null]]", &ParseOptions::default());
+
+    let mut found = Vec::new();
+    while let Some(value) = reader.next_value().unwrap() {
+        if let [JSONKey::Index(outer), JSONKey::Index(inner)] = reader.key_chain() {
+            found.push((*outer, *inner, value));
+        }
+    }
+
+    assert_eq!(
+        found,
+        [
+            (0, 0, RootJSONValue::Number("1")),
+            (1, 0, RootJSONValue::Number("2")),
+            (1, 1, RootJSONValue::Null)
+        ]
+    );
+}
+
+#[test]
+fn stops_after_error() {
+    let mut reader = JsonReader::new(r#"{ "a": 1, "b" 2 }"#, &ParseOptions::default());
+
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn skipped_content() {
+    // Content of strings, keys and comments is skipped over rather than decoded
+    let source = "{ \"kéy \\\" ü\": \"välue \\\\\", /* ✓ ** */ \"b\": # 🎉\n 'é\\'' } // ✓";
+    let mut reader = JsonReader::new(source, &ParseOptions::json5());
+    let mut values = Vec::new();
+    while let Some(value) = reader.next_value().unwrap() {
+        values.push((
+            reader.key_chain().to_vec(),
+            value,
+            reader.span().slice(source),
+        ));
+    }
+
+    assert_eq!(
+        values,
+        [
+            (
+                vec![JSONKey::Slice("kéy \\\" ü")],
+                RootJSONValue::String("välue \\\\"),
+                "\"välue \\\\\""
+            ),
+            (
+                vec![JSONKey::Slice("b")],
+                RootJSONValue::String("é\\'"),
+                "'é\\''"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn lending_iterator() {
+    use simple_json_parser::LendingIterator;
+
+    let mut reader = JsonReader::new(
+        r#"{ "a": { "b": 1 }, "c": [null] }"#,
+        &ParseOptions::default(),
+    )
+    .lending();
+
+    let mut found = Vec::new();
+    while let Some(item) = reader.next() {
+        let (keys, value) = item.unwrap();
+        found.push((keys.len(), value));
+    }
+    assert_eq!(
+        found,
+        [(2, RootJSONValue::Number("1")), (2, RootJSONValue::Null)]
+    );
+
+    let mut reader = JsonReader::new("[1,,]", &ParseOptions::default()).lending();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+}
@@ -0,0 +1,74 @@
+use simple_json_parser::{parse_with_recovery, JSONParseErrorReason, ParseOptions};
+
+fn recover(source: &str) -> (Vec<String>, Vec<(usize, JSONParseErrorReason)>) {
+    let mut values = Vec::new();
+    let errors = parse_with_recovery(
+        source,
+        |keys, value| values.push(format!("{keys:?} -> {value:?}")),
+        &ParseOptions::default(),
+    );
+    let errors = errors.into_iter().map(|err| (err.at, err.reason)).collect();
+    (values, errors)
+}
+
+#[test]
+fn collects_errors() {
+    let source = r#"{
+    "a": 1,
+    "b": [1, 2x, 3,, 5],
+    "c": { "d": nul, "e": true },
+    f: 2,
+    "g": "last"
+}"#;
+    let (values, errors) = recover(source);
+
+    assert_eq!(
+        values,
+        [
+            r#"[Slice("a")] -> Number("1")"#,
+            r#"[Slice("b"), Index(0)] -> Number("1")"#,
+            r#"[Slice("b"), Index(2)] -> Number("3")"#,
+            r#"[Slice("b"), Index(4)] -> Number("5")"#,
+            r#"[Slice("c"), Slice("e")] -> Boolean(true)"#,
+            r#"[Slice("g")] -> String("last")"#,
+        ]
+    );
+    let reasons: Vec<_> = errors
+        .iter()
+        .map(|(_, reason)| format!("{reason:?}"))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            "InvalidNumber",
+            "ExpectedValue { found: Some(',') }",
+            "ExpectedTrueFalseNull { found: Some(',') }",
+            "ExpectedKey { found: Some('f') }"
+        ]
+    );
+    assert!(source[errors[0].0..].starts_with("x, 3"));
+    assert!(source[errors[3].0..].starts_with("f: 2"));
+}
+
+#[test]
+fn valid_and_unrecoverable() {
+    let (values, errors) = recover(r#"{ "a": [1, 2] }"#);
+    assert_eq!(values.len(), 2);
+    assert!(errors.is_empty());
+
+    // Mismatched brackets close the containers in between
+    let (values, errors) = recover(r#"[{ "a": 1 ], 2]"#);
+    assert_eq!(values, [r#"[Index(0), Slice("a")] -> Number("1")"#]);
+    assert_eq!(errors.len(), 2);
+
+    // Missing end
+    let (values, errors) = recover(r#"{ "a": [1, "#);
+    assert_eq!(values.len(), 1);
+    assert!(matches!(
+        errors.as_slice(),
+        [(
+            _,
+            JSONParseErrorReason::ExpectedValue { .. } | JSONParseErrorReason::UnexpectedEndOfInput
+        )]
+    ));
+}
@@ -0,0 +1,44 @@
+use simple_json_parser::{redact, KeyPattern, ParseOptions};
+
+#[test]
+fn redacts_matching_values() {
+    let source = r#"{
+        // request body
+        "password": "hunter2",
+        "nested": { "password": "x" },
+        "keys": ["a", "b"],
+        "headers": { "authorization": { "scheme": "Bearer", "token": "t" } }
+    }"#;
+    let patterns: [&[KeyPattern]; 3] = [
+        &[KeyPattern::Key("password")],
+        &[KeyPattern::Key("keys"), KeyPattern::Index(1)],
+        &[KeyPattern::Key("headers"), KeyPattern::Key("authorization")],
+    ];
+    let redacted = redact(
+        source,
+        &patterns,
+        "[\"redacted\"]",
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        redacted,
+        r#"{
+        // request body
+        "password": "[\"redacted\"]",
+        "nested": { "password": "x" },
+        "keys": ["a", "[\"redacted\"]"],
+        "headers": { "authorization": "[\"redacted\"]" }
+    }"#
+    );
+}
+
+#[test]
+fn root_and_errors() {
+    let everything: [&[KeyPattern]; 1] = [&[KeyPattern::AnySuffix]];
+    assert_eq!(
+        redact("[1, 2] ", &everything, "*", &ParseOptions::default()).unwrap(),
+        "\"*\" "
+    );
+    assert!(redact(r#"{ "a": "#, &everything, "*", &ParseOptions::default()).is_err());
+}
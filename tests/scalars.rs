@@ -0,0 +1,104 @@
+use simple_json_parser::{
+    parse_bytes, parse_events, parse_reader, parse_with_exit_signal, JSONEvent, JSONKey,
+    JSONParseErrorReason, JsonReader, ParseOptions, PushParser, RootJSONValue,
+};
+
+const SCALARS: [(&str, RootJSONValue<'static>); 8] = [
+    (r#""hello""#, RootJSONValue::String("hello")),
+    ("42", RootJSONValue::Number("42")),
+    ("-0.5e10", RootJSONValue::Number("-0.5e10")),
+    ("true", RootJSONValue::Boolean(true)),
+    ("false", RootJSONValue::Boolean(false)),
+    ("null", RootJSONValue::Null),
+    (" 42 \n", RootJSONValue::Number("42")),
+    ("7 // trailing comment", RootJSONValue::Number("7")),
+];
+
+#[test]
+fn scalar_documents() {
+    for (source, expected) in SCALARS {
+        let mut found = Vec::new();
+        let result = parse_with_exit_signal(
+            source,
+            |keys, value| {
+                found.push((keys.to_vec(), value));
+                false
+            },
+            &ParseOptions::default(),
+        );
+        assert_eq!(result.unwrap().bytes_consumed, source.len(), "{source}");
+        assert_eq!(found, [(Vec::<JSONKey>::new(), expected)], "{source}");
+
+        let values = JsonReader::new(source, &ParseOptions::default())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values.len(), 1, "{source}");
+
+        let mut events = 0;
+        parse_events(
+            source,
+            |keys, event| {
+                assert!(keys.is_empty());
+                if let JSONEvent::Value(_) = event {
+                    events += 1;
+                }
+            },
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(events, 1, "{source}");
+
+        let mut values = 0;
+        parse_bytes(
+            source.as_bytes(),
+            |keys, _| {
+                assert!(keys.is_empty());
+                values += 1;
+                false
+            },
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(values, 1, "{source}");
+
+        let mut values = 0;
+        parse_reader(
+            source.as_bytes(),
+            |keys, _| {
+                assert!(keys.is_empty());
+                values += 1;
+                false
+            },
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(values, 1, "{source}");
+
+        let mut values = 0;
+        let mut parser = PushParser::new(&ParseOptions::default(), |_, _| {
+            values += 1;
+            false
+        });
+        parser.feed(source).unwrap();
+        parser.finish().unwrap();
+        assert_eq!(values, 1, "{source}");
+    }
+}
+
+#[test]
+fn reject_scalar_root() {
+    let options = ParseOptions::builder().allow_scalar_root(false).build();
+
+    for (source, _) in SCALARS {
+        let err = parse_with_exit_signal(source, |_, _| false, &options).unwrap_err();
+        assert!(
+            matches!(err.reason, JSONParseErrorReason::ExpectedObjectOrArray),
+            "{source}"
+        );
+        assert_eq!(err.at, source.len() - source.trim_start().len(), "{source}");
+    }
+
+    for source in [r#"{ "a": "b" }"#, "[1, true]", " // comment\n []"] {
+        assert!(parse_with_exit_signal(source, |_, _| false, &options).is_ok());
+    }
+}
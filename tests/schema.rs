@@ -0,0 +1,135 @@
+use simple_json_parser::{
+    ParseOptions, Schema, SchemaError, SchemaType, SchemaViolation, ViolationKind,
+};
+
+fn violations(schema: &str, source: &str) -> Vec<(String, ViolationKind)> {
+    let schema = Schema::compile(schema).unwrap();
+    schema
+        .validate(source, &ParseOptions::default())
+        .unwrap()
+        .into_iter()
+        .map(|SchemaViolation { path, kind, .. }| (path, kind))
+        .collect()
+}
+
+#[test]
+fn types() {
+    let schema = r#"{ "items": { "type": ["integer", "null"] } }"#;
+    assert_eq!(
+        violations(schema, r#"[1, 2.0, null, 1.5, "a", {}]"#),
+        [
+            (
+                "/3".to_owned(),
+                ViolationKind::Type {
+                    expected: vec![SchemaType::Integer, SchemaType::Null]
+                }
+            ),
+            (
+                "/4".to_owned(),
+                ViolationKind::Type {
+                    expected: vec![SchemaType::Integer, SchemaType::Null]
+                }
+            ),
+            (
+                "/5".to_owned(),
+                ViolationKind::Type {
+                    expected: vec![SchemaType::Integer, SchemaType::Null]
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn objects() {
+    let schema = r#"{
+        "type": "object",
+        "required": ["name", "version"],
+        "properties": {
+            "name": { "type": "string", "minLength": 1, "maxLength": 8 },
+            "version": { "type": "integer" },
+            "license": { "enum": ["MIT", "Apache-2.0"] },
+            "a/b": { "const": { "x": [1] } }
+        },
+        "additionalProperties": false
+    }"#;
+
+    assert_eq!(
+        violations(
+            schema,
+            r#"{"name": "simple-json-parser", "license": "MIT", "a/b": {"x": [1.0]}}"#
+        ),
+        [
+            ("/name".to_owned(), ViolationKind::MaxLength(8)),
+            (
+                String::new(),
+                ViolationKind::MissingProperty("version".to_owned())
+            ),
+        ]
+    );
+    assert_eq!(
+        violations(
+            schema,
+            r#"{"name": "", "version": 1, "license": "GPL", "a/b": {}, "other": {"deep": 1}}"#
+        ),
+        [
+            ("/name".to_owned(), ViolationKind::MinLength(1)),
+            ("/license".to_owned(), ViolationKind::Enum),
+            ("/a~1b".to_owned(), ViolationKind::Enum),
+            ("/other".to_owned(), ViolationKind::NotAllowed),
+        ]
+    );
+    assert_eq!(
+        violations(schema, "[]"),
+        [(
+            String::new(),
+            ViolationKind::Type {
+                expected: vec![SchemaType::Object]
+            }
+        )]
+    );
+}
+
+#[test]
+fn ranges() {
+    let schema = r#"{
+        "type": "array",
+        "minItems": 2,
+        "maxItems": 3,
+        "items": { "minimum": 0, "exclusiveMaximum": 10 }
+    }"#;
+    assert!(violations(schema, "[0, 9.5]").is_empty());
+    assert_eq!(
+        violations(schema, "[-1, 10, 5, 5]"),
+        [
+            ("/0".to_owned(), ViolationKind::Minimum(0.0)),
+            ("/1".to_owned(), ViolationKind::ExclusiveMaximum(10.0)),
+            (String::new(), ViolationKind::MaxItems(3)),
+        ]
+    );
+    assert_eq!(
+        violations(schema, "[]"),
+        [(String::new(), ViolationKind::MinItems(2))]
+    );
+}
+
+#[test]
+fn spans() {
+    let schema = Schema::compile(r#"{ "properties": { "a": { "type": "string" } } }"#).unwrap();
+    let source = r#"{ "a": [1, 2] }"#;
+    let violations = schema.validate(source, &ParseOptions::default()).unwrap();
+    assert_eq!(violations[0].span.slice(source), "[1, 2]");
+}
+
+#[test]
+fn invalid_schemas() {
+    assert!(matches!(
+        Schema::compile(r#"{ "properties": { "a": { "type": "text" } } }"#),
+        Err(SchemaError::InvalidKeyword { path, keyword }) if path == "/properties/a" && keyword == "type"
+    ));
+    assert!(matches!(
+        Schema::compile(r#"{ "minItems": "2" }"#),
+        Err(SchemaError::InvalidKeyword { .. })
+    ));
+    assert!(matches!(Schema::compile("{"), Err(SchemaError::Parse(_))));
+}
@@ -0,0 +1,150 @@
+use simple_json_parser::{parse_with_signal, JSONEvent, JSONKey, ParseOptions, Signal};
+
+fn events(source: &str, mut skip: impl FnMut(&JSONEvent) -> bool) -> Vec<String> {
+    let mut events = Vec::new();
+    parse_with_signal(
+        source,
+        |keys, event| {
+            let signal = if skip(&event) {
+                Signal::SkipSubtree
+            } else {
+                Signal::Continue
+            };
+            events.push(format!("{keys:?} {event:?}"));
+            signal
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    events
+}
+
+#[test]
+fn skip_key_value() {
+    let source = r#"{ "a": { "b": [1, { "c": 2 }] }, "d": 3, "e": 4 }"#;
+    let found = events(source, |event| matches!(event, JSONEvent::Key("a" | "d")));
+    assert_eq!(
+        found,
+        [
+            "[] StartObject",
+            r#"[Slice("a")] Key("a")"#,
+            r#"[Slice("d")] Key("d")"#,
+            r#"[Slice("e")] Key("e")"#,
+            r#"[Slice("e")] Value(Number("4"))"#,
+            "[] EndObject",
+        ]
+    );
+}
+
+#[test]
+fn skip_started_container() {
+    let source = "[[1, [2]], 3]";
+    let mut starts = 0;
+    let found = events(source, |event| {
+        if let JSONEvent::StartArray = event {
+            starts += 1;
+        }
+        // Skip the inner array
+        matches!(event, JSONEvent::StartArray) && starts == 2
+    });
+    assert_eq!(
+        found,
+        [
+            "[] StartArray",
+            "[Index(0)] StartArray",
+            "[Index(0)] EndArray",
+            "[Index(1)] Value(Number(\"3\"))",
+            "[] EndArray",
+        ]
+    );
+}
+
+#[test]
+fn skip_rest_and_stop() {
+    let source = r#"{ "a": [1, 2, 3], "b": true }"#;
+    let found = events(
+        source,
+        |event| matches!(event, JSONEvent::Value(value) if format!("{value:?}") == r#"Number("1")"#),
+    );
+    assert_eq!(found.len(), 8, "{found:?}");
+    assert_eq!(found[4], "[Slice(\"a\")] EndArray");
+
+    let mut keys = Vec::new();
+    let end = parse_with_signal(
+        source,
+        |chain, event| {
+            if let JSONEvent::Key(key) = event {
+                keys.push(key);
+                assert_eq!(chain.last(), Some(&JSONKey::Slice(key)));
+                return Signal::Stop;
+            }
+            Signal::Continue
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(keys, ["a"]);
+    assert_eq!(end, 5);
+}
+
+#[test]
+fn skipped_content_is_scanned() {
+    fn skip_keys(source: &str, options: &ParseOptions) -> Result<Vec<String>, usize> {
+        let mut values = Vec::new();
+        parse_with_signal(
+            source,
+            |keys, event| match event {
+                JSONEvent::Key("skip") => Signal::SkipSubtree,
+                JSONEvent::Value(value) => {
+                    values.push(format!("{keys:?} {value:?}"));
+                    Signal::Continue
+                }
+                _ => Signal::Continue,
+            },
+            options,
+        )
+        .map(|_| values)
+        .map_err(|err| err.at)
+    }
+
+    let options = ParseOptions::default();
+    // Only brackets, strings and comments are looked at
+    assert_eq!(
+        skip_keys(r#"{ "skip": [tru, 01, "]", {"}": 1}], "a": 2 }"#, &options),
+        Ok(vec![r#"[Slice("a")] Number("2")"#.to_owned()])
+    );
+    assert_eq!(
+        skip_keys(r#"{ "skip": "\"}", "a": [3] }"#, &options),
+        Ok(vec![r#"[Slice("a"), Index(0)] Number("3")"#.to_owned()])
+    );
+    assert_eq!(
+        skip_keys(
+            "{ skip /* ] */ : // }\n { 'a': 1 }, b: true }",
+            &ParseOptions::json5()
+        ),
+        Ok(vec![r#"[Slice("b")] Boolean(true)"#.to_owned()])
+    );
+
+    // Errors after skipped content are still found
+    assert_eq!(
+        skip_keys(r#"{ "skip": [1, 2], "a": tru }"#, &options),
+        Err(26)
+    );
+    assert_eq!(skip_keys(r#"{ "skip" 1 }"#, &options), Err(9));
+    assert_eq!(skip_keys(r#"{ "skip": [1, 2} }"#, &options), Err(15));
+    assert_eq!(skip_keys(r#"{ "skip": "abc }"#, &options), Err(16));
+
+    // Comment styles which are turned off are not skipped over
+    let source = "{ \"skip\": [1, # ]\n 2], \"a\": 3 }";
+    assert!(skip_keys(source, &options).is_ok());
+    let no_hash = ParseOptions::builder().allow_hash_comments(false).build();
+    assert_eq!(skip_keys(source, &no_hash), Err(14));
+    let block_only = ParseOptions::builder()
+        .allow_line_comments(false)
+        .allow_hash_comments(false)
+        .build();
+    assert_eq!(
+        skip_keys("{ \"skip\": [1, // ]\n 2], \"a\": 3 }", &block_only),
+        Err(14)
+    );
+}
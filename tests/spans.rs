@@ -0,0 +1,63 @@
+use simple_json_parser::{parse_with_spans, JSONEvent, JSONKey, JsonReader, ParseOptions, Span};
+
+#[test]
+fn value_spans() {
+    let source = r#"{ "name": "ezno", "items": [1.5, true, null], "café": "☕" }"#;
+
+    let mut spans = Vec::new();
+    parse_with_spans(
+        source,
+        |keys, _value, span| {
+            spans.push((keys.to_vec(), span.slice(source)));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        spans,
+        [
+            (vec![JSONKey::Slice("name")], "\"ezno\""),
+            (vec![JSONKey::Slice("items"), JSONKey::Index(0)], "1.5"),
+            (vec![JSONKey::Slice("items"), JSONKey::Index(1)], "true"),
+            (vec![JSONKey::Slice("items"), JSONKey::Index(2)], "null"),
+            (vec![JSONKey::Slice("café")], "\"☕\""),
+        ]
+    );
+
+    let mut found = None;
+    parse_with_spans(
+        "42",
+        |_keys, _value, span| {
+            found = Some(span);
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(found, Some(Span { start: 0, end: 2 }));
+}
+
+#[test]
+fn key_spans() {
+    let source = r#"{
+    "name": "ezno",
+    "nmae": "typo"
+}"#;
+
+    let known = ["name", "version"];
+    let mut diagnostics = Vec::new();
+    let mut reader = JsonReader::new(source, &ParseOptions::default());
+    while let Some(event) = reader.next_event().unwrap() {
+        if let JSONEvent::Key(key) = event {
+            if !known.contains(&key) {
+                let Span { start, end } = reader.span();
+                diagnostics.push(format!("unknown field `{key}` at {start}..{end}"));
+            }
+        }
+    }
+
+    assert_eq!(diagnostics, ["unknown field `nmae` at 26..32"]);
+    assert_eq!(&source[26..32], "\"nmae\"");
+}
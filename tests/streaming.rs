@@ -0,0 +1,129 @@
+use simple_json_parser::{
+    parse, parse_reader, FeedResult, JSONKey, ParseOptions, PushParser, RootJSONValue,
+};
+
+/// Reads a single byte at a time to test values split across reads
+struct Trickle<'a>(&'a [u8]);
+
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.split_first() {
+            Some((first, rest)) if !buf.is_empty() => {
+                buf[0] = *first;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+fn to_strings(keys: &[JSONKey<'_>], value: &RootJSONValue<'_>) -> String {
+    format!("{keys:?} -> {value:?}")
+}
+
+#[test]
+fn matches_str_parse() {
+    let source = r#"{
+        "name": "café ☕",
+        "keywords": ["typescript", "checker"], // comment
+        "nested": { "escaped \"key\"": -12.5e3 },
+        "flags": [true, false, null],
+        "last": 42
+    }"#;
+
+    let mut expected = Vec::new();
+    parse(source, |keys, value| {
+        expected.push(to_strings(keys, &value));
+    })
+    .unwrap();
+
+    let mut found = Vec::new();
+    let result = parse_reader(
+        Trickle(source.as_bytes()),
+        |keys, value| {
+            found.push(to_strings(keys, &value));
+            false
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result, source.len());
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn deep_key_chains() {
+    for depth in [16, 17, 40] {
+        let source = format!(
+            "{}{{\"a\": 1}}{}",
+            "{\"k\": ".repeat(depth),
+            "}".repeat(depth)
+        );
+        let mut keys = Vec::new();
+        parse_reader(
+            Trickle(source.as_bytes()),
+            |chain, _| {
+                keys.push(chain.len());
+                false
+            },
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(keys, [depth + 1], "{source}");
+    }
+}
+
+#[test]
+fn exit_and_errors() {
+    let source = br#"{ "a": 1, "b": 2 }"#;
+    let result = parse_reader(
+        &source[..],
+        |keys, _value| keys == [JSONKey::Slice("a")],
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(&source[result..], b", \"b\": 2 }");
+
+    let invalid_utf8: &[u8] = b"[\"\xFF\"]";
+    assert!(parse_reader(invalid_utf8, |_, _| false, &ParseOptions::default()).is_err());
+
+    let invalid_json: &[u8] = b"[1, 2";
+    assert!(parse_reader(invalid_json, |_, _| false, &ParseOptions::default()).is_err());
+}
+
+#[test]
+fn push_parser_chunks() {
+    let chunks = [
+        r#"{ "items": [1"#,
+        "2, tr",
+        r#"ue, "a"#,
+        r#"b\"c" ], "after": nu"#,
+        "ll }",
+    ];
+
+    let mut found = Vec::new();
+    let mut parser = PushParser::new(&ParseOptions::default(), |keys, value| {
+        found.push(to_strings(keys, &value));
+        false
+    });
+    for chunk in chunks {
+        assert_eq!(parser.feed(chunk).unwrap(), FeedResult::NeedMoreData);
+    }
+    assert_eq!(parser.finish().unwrap(), chunks.concat().len());
+
+    assert_eq!(
+        found,
+        [
+            r#"[Slice("items"), Index(0)] -> Number("12")"#,
+            r#"[Slice("items"), Index(1)] -> Boolean(true)"#,
+            r#"[Slice("items"), Index(2)] -> String("ab\\\"c")"#,
+            r#"[Slice("after")] -> Null"#,
+        ]
+    );
+
+    let mut parser = PushParser::new(&ParseOptions::default(), |_, _| false);
+    assert_eq!(parser.feed("[1, 2").unwrap(), FeedResult::NeedMoreData);
+    assert!(parser.finish().is_err());
+}
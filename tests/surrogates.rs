@@ -0,0 +1,60 @@
+use simple_json_parser::{
+    is_valid, parse, parse_bytes, JSONParseErrorReason, ParseOptions, PushParser,
+};
+
+fn error(source: &str, options: &ParseOptions) -> Option<(usize, JSONParseErrorReason)> {
+    let result = is_valid(source, options)
+        .err()
+        .map(|err| (err.at, err.reason));
+    let from_bytes = parse_bytes(source.as_bytes(), |_, _| false, options)
+        .err()
+        .map(|err| (err.at, err.reason));
+    assert_eq!(result, from_bytes, "{source}");
+    result
+}
+
+#[test]
+fn lone_surrogates() {
+    let options = ParseOptions::builder().reject_lone_surrogates(true).build();
+
+    for valid in [
+        r#"["🦀"]"#,
+        r#"["é \\ud800 \"A"]"#,
+        r#"{ "😀": "key" }"#,
+        r#"["\u12"]"#,
+    ] {
+        assert_eq!(error(valid, &options), None, "{valid}");
+    }
+
+    for (source, at) in [
+        (r#"["\ud800"]"#, 2),
+        (r#"["ab \ud800 c"]"#, 5),
+        (r#"["\ud800A"]"#, 2),
+        (r#"["\ud800\n"]"#, 2),
+        (r#"["\udd80"]"#, 2),
+        (r#"{ "a\uDBFF": 1 }"#, 4),
+        (r#"["🦀\ud83e"]"#, 6),
+    ] {
+        assert_eq!(
+            error(source, &options),
+            Some((at, JSONParseErrorReason::LoneSurrogate)),
+            "{source}"
+        );
+    }
+
+    // In pieces
+    let mut parser = PushParser::new(&options, |_, _| false);
+    parser.feed(r#"["\ud8"#).unwrap();
+    let err = parser.feed(r#"00x"]"#).unwrap_err();
+    assert_eq!(err.at, 2);
+}
+
+#[test]
+fn strict_json_only() {
+    let source = r#"["\ud800"]"#;
+    assert!(parse(source, |_, _| {}).is_ok());
+    assert_eq!(
+        error(source, &ParseOptions::STRICT_JSON),
+        Some((2, JSONParseErrorReason::LoneSurrogate))
+    );
+}
@@ -0,0 +1,65 @@
+use simple_json_parser::{tokenize, CommentStyle, ParseOptions, TokenKind};
+
+#[test]
+fn lossless() {
+    let sources = [
+        "{\n  \"name\": \"ezno\", // comment\n  \"list\": [1,2 , -3.5e2],\n  \"t\":true,\"n\" :null\n}\n",
+        "  42  ",
+        "[ /* block */ ]",
+        "# only a comment",
+        "",
+    ];
+    for source in sources {
+        let tokens = tokenize(source, &ParseOptions::default())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            tokens.iter().map(|token| token.text).collect::<String>(),
+            source
+        );
+        for token in &tokens {
+            assert_eq!(token.span.slice(source), token.text);
+        }
+    }
+}
+
+#[test]
+fn kinds() {
+    let kinds: Vec<_> = tokenize(r#"{"a": [1,null]} # end"#, &ParseOptions::default())
+        .map(|token| {
+            let token = token.unwrap();
+            (token.kind, token.text)
+        })
+        .collect();
+
+    assert_eq!(
+        kinds,
+        [
+            (TokenKind::StartObject, "{"),
+            (TokenKind::Key, "\"a\""),
+            (TokenKind::Colon, ":"),
+            (TokenKind::Whitespace, " "),
+            (TokenKind::StartArray, "["),
+            (TokenKind::Number, "1"),
+            (TokenKind::Comma, ","),
+            (TokenKind::Null, "null"),
+            (TokenKind::EndArray, "]"),
+            (TokenKind::EndObject, "}"),
+            (TokenKind::Whitespace, " "),
+            (TokenKind::Comment(CommentStyle::Hash), "# end"),
+        ]
+    );
+}
+
+#[test]
+fn stops_after_error() {
+    let mut tokens = tokenize("[1, }", &ParseOptions::default());
+    let mut errored = false;
+    for token in tokens.by_ref() {
+        if token.is_err() {
+            errored = true;
+        }
+    }
+    assert!(errored);
+    assert!(tokens.next().is_none());
+}
@@ -0,0 +1,83 @@
+use simple_json_parser::{TsConfig, TsConfigError, Value};
+
+fn files(path: &str) -> Option<String> {
+    let source = match path {
+        "packages/app/tsconfig.json" => {
+            r#"{
+                // app
+                "extends": ["../../tsconfig.base.json", "@tsconfig/strictest"],
+                "compilerOptions": {
+                    "outDir": "dist",
+                    "paths": { "~/*": ["./src/*"] },
+                },
+                "exclude": ["dist"],
+            }"#
+        }
+        "tsconfig.base.json" => {
+            r#"{
+                "compilerOptions": {
+                    "target": "es2022",
+                    "strict": false,
+                    "paths": { "@shared/*": ["../shared/*"] }
+                },
+                "include": ["src"],
+                "exclude": ["node_modules"]
+            }"#
+        }
+        "@tsconfig/strictest.json" => r#"{ "compilerOptions": { "strict": true } }"#,
+        "a.json" => r#"{ "extends": "./b.json" }"#,
+        "b.json" => r#"{ "extends": "./a.json" }"#,
+        _ => return None,
+    };
+    Some(source.to_owned())
+}
+
+#[test]
+fn resolves_extends_chain() {
+    let config = TsConfig::resolve("packages/app/tsconfig.json", files).unwrap();
+    assert_eq!(
+        config.extends,
+        ["../../tsconfig.base.json", "@tsconfig/strictest"]
+    );
+    let options: Vec<&str> = config
+        .compiler_options
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .collect();
+    assert_eq!(options, ["target", "strict", "paths", "outDir"]);
+    assert_eq!(
+        config.compiler_options[1],
+        ("strict".to_owned(), Value::Boolean(true))
+    );
+    assert_eq!(
+        config.paths,
+        [("~/*".to_owned(), vec!["./src/*".to_owned()])]
+    );
+    assert_eq!(config.include, Some(vec!["src".to_owned()]));
+    assert_eq!(config.exclude, Some(vec!["dist".to_owned()]));
+    assert_eq!(config.files, None);
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(
+        TsConfig::resolve("missing.json", files),
+        Err(TsConfigError::NotFound { path }) if path == "missing.json"
+    ));
+    assert!(matches!(
+        TsConfig::resolve("a.json", files),
+        Err(TsConfigError::Cycle { path }) if path == "a.json"
+    ));
+    assert!(matches!(
+        TsConfig::parse(r#"{ "include": "src" }"#),
+        Err(TsConfigError::InvalidValue { key: "include" })
+    ));
+    assert!(matches!(
+        TsConfig::parse("[]"),
+        Err(TsConfigError::InvalidValue { key: "tsconfig" })
+    ));
+    assert!(matches!(
+        TsConfig::parse("{ "),
+        Err(TsConfigError::Parse(_))
+    ));
+}
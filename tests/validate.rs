@@ -0,0 +1,61 @@
+use simple_json_parser::{is_valid, parse_with_exit_signal, ParseOptions};
+
+const SOURCES: &[&str] = &[
+    r#"{ "a": [1, -2.5e3, "x\"y", true, false, null], "b": {} }"#,
+    r#"{ "名前": "値", "emoji": "😀" }"#,
+    "[1, 2,]",
+    "{ a: 'b', c: 0x1F, d: +Infinity }",
+    "/* start */ [1] // end",
+    "\"string\"",
+    "42",
+    "",
+    " // only a comment\n",
+    r#"{ "a" 1 }"#,
+    r#"{ "a": [1, 2 }"#,
+    "[01]",
+    "[tru]",
+    r#"{ "a": "unterminated }"#,
+    "[1] [2]",
+    "[1 /* unterminated",
+    "[\"a\tb\"]",
+    "[1, 2, 3",
+];
+
+#[test]
+fn same_result_as_parsing() {
+    for options in [
+        ParseOptions::default(),
+        ParseOptions::builder()
+            .allow_trailing_commas(true)
+            .json5(true)
+            .build(),
+        ParseOptions::builder()
+            .allow_comments(false)
+            .allow_scalar_root(false)
+            .allow_control_characters(false)
+            .max_depth(1)
+            .build(),
+        ParseOptions::builder().require_end_of_input(true).build(),
+    ] {
+        for source in SOURCES {
+            let expected = parse_with_exit_signal(source, |_, _| false, &options)
+                .map(|_| ())
+                .map_err(|err| (err.at, format!("{:?}", err.reason)));
+            let result =
+                is_valid(source, &options).map_err(|err| (err.at, format!("{:?}", err.reason)));
+            assert_eq!(result, expected, "{source:?} with {options:?}");
+        }
+    }
+}
+
+#[test]
+fn no_value() {
+    let options = ParseOptions::default();
+    assert!(is_valid("", &options).is_ok());
+
+    let options = ParseOptions::builder().require_end_of_input(true).build();
+    for source in ["", "   ", "/* */\n"] {
+        let err = is_valid(source, &options).unwrap_err();
+        assert_eq!((err.at, err.reason.name()), (source.len(), "ExpectedValue"));
+    }
+}
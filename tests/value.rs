@@ -0,0 +1,60 @@
+use simple_json_parser::{parse_to_value, Value};
+
+#[test]
+fn to_value() {
+    let source = r#"{
+        // comment
+        "name": "ezno",
+        "escaped \"key\"": "line\nbreak",
+        "keywords": ["typescript", 4, true, null, {}, []],
+        "author": { "name": "Ben" }
+    }"#;
+
+    let value = parse_to_value(source).unwrap();
+
+    assert_eq!(
+        value,
+        Value::Object(vec![
+            ("name".into(), Value::String("ezno".into())),
+            (
+                "escaped \"key\"".into(),
+                Value::String("line\nbreak".into())
+            ),
+            (
+                "keywords".into(),
+                Value::Array(vec![
+                    Value::String("typescript".into()),
+                    Value::Number("4".into()),
+                    Value::Boolean(true),
+                    Value::Null,
+                    Value::Object(Vec::new()),
+                    Value::Array(Vec::new()),
+                ])
+            ),
+            (
+                "author".into(),
+                Value::Object(vec![("name".into(), Value::String("Ben".into()))])
+            ),
+        ])
+    );
+
+    assert_eq!(
+        value
+            .get("author")
+            .and_then(|author| author.get("name"))
+            .and_then(Value::as_str),
+        Some("Ben")
+    );
+    assert_eq!(
+        value.get("keywords").and_then(|k| k.get_index(1)),
+        Some(&Value::Number("4".into()))
+    );
+}
+
+#[test]
+fn scalars_and_errors() {
+    assert_eq!(parse_to_value("true").unwrap(), Value::Boolean(true));
+    assert_eq!(parse_to_value(" 12 ").unwrap(), Value::Number("12".into()));
+    assert!(parse_to_value("").is_err());
+    assert!(parse_to_value("[1, 2").is_err());
+}
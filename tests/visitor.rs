@@ -0,0 +1,105 @@
+use simple_json_parser::{
+    parse_with_visitor, Container, JSONKey, JSONVisitor, ParseOptions, RootJSONValue, Span,
+};
+
+/// Collects the name of scripts and the deepest nesting
+#[derive(Default)]
+struct Scripts {
+    names: Vec<String>,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'a> JSONVisitor<'a> for Scripts {
+    fn value(&mut self, keys: &[JSONKey<'a>], value: RootJSONValue<'a>) {
+        if let ([JSONKey::Slice("scripts"), JSONKey::Slice(name)], RootJSONValue::String(_)) =
+            (keys, value)
+        {
+            self.names.push((*name).to_owned());
+        }
+    }
+
+    fn enter_object(&mut self, _keys: &[JSONKey<'a>]) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn exit_object(&mut self, _keys: &[JSONKey<'a>]) {
+        self.depth -= 1;
+    }
+}
+
+#[test]
+fn visitor() {
+    let source = r#"{
+        "name": "ezno",
+        "scripts": { "build": "cargo build", "test": "cargo test" },
+        "build": { "rollup": { "esbuild": { "target": "esnext" } } }
+    }"#;
+
+    let mut visitor = Scripts::default();
+    parse_with_visitor(source, &mut visitor, &ParseOptions::default()).unwrap();
+
+    assert_eq!(visitor.names, ["build", "test"]);
+    assert_eq!(visitor.max_depth, 4);
+    assert_eq!(visitor.depth, 0);
+}
+
+/// Records the key chain and source of each container as it is finished
+#[derive(Default)]
+struct Containers<'a> {
+    entered: Vec<(Vec<JSONKey<'a>>, Container)>,
+    exited: Vec<(Vec<JSONKey<'a>>, Container, &'a str)>,
+    source: &'a str,
+}
+
+impl<'a> JSONVisitor<'a> for Containers<'a> {
+    fn enter(&mut self, keys: &[JSONKey<'a>], kind: Container) {
+        self.entered.push((keys.to_vec(), kind));
+    }
+
+    fn exit(&mut self, keys: &[JSONKey<'a>], kind: Container, span: Span) {
+        self.exited
+            .push((keys.to_vec(), kind, span.slice(self.source)));
+    }
+}
+
+#[test]
+fn enter_and_exit() {
+    let source = r#"{ "a": [1, {}], "b": { "c": [] } }"#;
+
+    for empty_containers in [false, true] {
+        let mut visitor = Containers {
+            source,
+            ..Containers::default()
+        };
+        let options = ParseOptions::builder()
+            .empty_containers(empty_containers)
+            .build();
+        parse_with_visitor(source, &mut visitor, &options).unwrap();
+
+        let a = JSONKey::Slice("a");
+        let b = JSONKey::Slice("b");
+        let c = JSONKey::Slice("c");
+        assert_eq!(
+            visitor.entered,
+            [
+                (vec![], Container::Object),
+                (vec![a], Container::Array),
+                (vec![a, JSONKey::Index(1)], Container::Object),
+                (vec![b], Container::Object),
+                (vec![b, c], Container::Array),
+            ]
+        );
+        assert_eq!(
+            visitor.exited,
+            [
+                (vec![a, JSONKey::Index(1)], Container::Object, "{}"),
+                (vec![a], Container::Array, "[1, {}]"),
+                (vec![b, c], Container::Array, "[]"),
+                (vec![b], Container::Object, r#"{ "c": [] }"#),
+                (vec![], Container::Object, source),
+            ]
+        );
+    }
+}
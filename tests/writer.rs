@@ -0,0 +1,95 @@
+use simple_json_parser::{minify, parse_events, IoWriter, JSONEvent, JSONWriter, ParseOptions};
+
+#[test]
+fn escapes_and_commas() {
+    let mut writer = JSONWriter::new(String::new());
+    writer.begin_array().unwrap();
+    writer.string("tab\tquote\"slash\\ bell\u{7} é").unwrap();
+    writer.begin_object().unwrap();
+    writer.end_object().unwrap();
+    writer.begin_array().unwrap();
+    writer.end_array().unwrap();
+    writer.number(-1.5).unwrap();
+    writer.boolean(false).unwrap();
+    writer.begin_object().unwrap();
+    writer.key("new\nline").unwrap();
+    writer.number(2).unwrap();
+    writer.key("b").unwrap();
+    writer.null().unwrap();
+    writer.end_object().unwrap();
+    writer.end_array().unwrap();
+    assert_eq!(writer.depth(), 0);
+
+    assert_eq!(
+        writer.into_inner(),
+        r#"["tab\tquote\"slash\\ bell\u0007 é",{},[],-1.5,false,{"new\nline":2,"b":null}]"#
+    );
+}
+
+#[test]
+fn round_trip_events() {
+    let source = r#"{ "a": [1, "two\n", { "b": true }], "c": null } // comment"#;
+
+    let mut writer = JSONWriter::new(String::new());
+    parse_events(
+        source,
+        |_keys, event| {
+            match event {
+                JSONEvent::StartObject => writer.begin_object(),
+                JSONEvent::EndObject => writer.end_object(),
+                JSONEvent::StartArray => writer.begin_array(),
+                JSONEvent::EndArray => writer.end_array(),
+                JSONEvent::Key(key) => writer.key(key),
+                JSONEvent::Value(value) => writer.value(&value),
+                JSONEvent::Comment { .. } => Ok(()),
+            }
+            .unwrap();
+        },
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    let mut minified = String::new();
+    minify(source, &mut minified).unwrap();
+    assert_eq!(writer.into_inner(), minified);
+}
+
+#[test]
+fn json5_strings() {
+    let source = r#"['say "hi"', 'it\'s', '\x41\
+B', "caf\u00e9 \"\/", "\u12"]"#;
+    let mut writer = JSONWriter::new(String::new());
+    writer.begin_array().unwrap();
+    parse_events(
+        source,
+        |_keys, event| {
+            if let JSONEvent::Value(value) = event {
+                writer.value(&value).unwrap();
+            }
+        },
+        &ParseOptions::json5(),
+    )
+    .unwrap();
+    writer.end_array().unwrap();
+    assert_eq!(
+        writer.into_inner(),
+        r#"["say \"hi\"","it's","AB","caf\u00e9 \"\/","\\u12"]"#
+    );
+}
+
+#[test]
+fn io_output() {
+    let mut writer = JSONWriter::new(IoWriter::new(Vec::new()));
+    writer.begin_array().unwrap();
+    writer.number(1).unwrap();
+    writer.end_array().unwrap();
+    assert_eq!(writer.into_inner().into_inner(), b"[1]");
+}
+
+#[test]
+#[should_panic = "expected a Object item"]
+fn value_without_key() {
+    let mut writer = JSONWriter::new(String::new());
+    writer.begin_object().unwrap();
+    let _ = writer.number(1);
+}
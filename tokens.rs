@@ -0,0 +1,146 @@
+//! Tokens covering all of the source, for tools which rewrite it
+
+use super::{CommentStyle, JSONParseError, JsonReader, ParseOptions, Span, Step, ValueKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Whitespace,
+    Comment(CommentStyle),
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Colon,
+    Comma,
+    /// Includes the quotes
+    Key,
+    /// Includes the quotes
+    String,
+    Number,
+    Boolean,
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+/// Splits the source into tokens. Concatenating the text of the tokens gives the source.
+/// Iteration stops after an error
+///
+/// ```
+/// use simple_json_parser::{tokenize, ParseOptions, TokenKind};
+///
+/// let source = r#"{ "a": [1, true] } // end"#;
+/// let tokens = tokenize(source, &ParseOptions::default())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(tokens.iter().map(|token| token.text).collect::<String>(), source);
+/// assert_eq!(tokens[2].kind, TokenKind::Key);
+/// ```
+#[must_use]
+pub fn tokenize<'a>(on: &'a str, options: &ParseOptions) -> Tokens<'a> {
+    Tokens {
+        on,
+        reader: JsonReader::new(on, options),
+        position: 0,
+        next: None,
+        finished: false,
+    }
+}
+
+/// Iterator from [`tokenize`]
+pub struct Tokens<'a> {
+    on: &'a str,
+    reader: JsonReader<'a>,
+    /// End of the last token
+    position: usize,
+    /// From the parser, after whitespace, colons and commas before it
+    next: Option<Token<'a>>,
+    finished: bool,
+}
+
+impl<'a> Tokens<'a> {
+    fn token(&self, step: Step) -> Option<Token<'a>> {
+        let span = step.span()?;
+        let kind = match step {
+            Step::Continue => return None,
+            Step::Key { .. } => TokenKind::Key,
+            Step::Value { kind, .. } => match kind {
+                ValueKind::String => TokenKind::String,
+                ValueKind::Number | ValueKind::NonFinite => TokenKind::Number,
+                ValueKind::Boolean(_) => TokenKind::Boolean,
+                ValueKind::Null => TokenKind::Null,
+                // Only the closing bracket, the opening one is already a token
+                ValueKind::EmptyObject => {
+                    return Some(self.slice(TokenKind::EndObject, span.end - 1, span.end));
+                }
+                ValueKind::EmptyArray => {
+                    return Some(self.slice(TokenKind::EndArray, span.end - 1, span.end));
+                }
+            },
+            Step::StartObject { .. } => TokenKind::StartObject,
+            Step::EndObject { .. } => TokenKind::EndObject,
+            Step::StartArray { .. } => TokenKind::StartArray,
+            Step::EndArray { .. } => TokenKind::EndArray,
+            Step::Comment { style, .. } => TokenKind::Comment(style),
+        };
+        Some(self.slice(kind, span.start, span.end))
+    }
+
+    fn slice(&self, kind: TokenKind, start: usize, end: usize) -> Token<'a> {
+        Token {
+            kind,
+            text: &self.on[start..end],
+            span: Span { start, end },
+        }
+    }
+
+    /// The text between tokens from the parser is only whitespace, colons and commas
+    fn gap(&mut self, end: usize) -> Token<'a> {
+        let start = self.position;
+        let text = &self.on[start..end];
+        let (kind, length) = match text.as_bytes()[0] {
+            b':' => (TokenKind::Colon, 1),
+            b',' => (TokenKind::Comma, 1),
+            _ => (
+                TokenKind::Whitespace,
+                text.find([':', ',']).unwrap_or(text.len()),
+            ),
+        };
+        self.position += length;
+        self.slice(kind, start, start + length)
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, JSONParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(next) = self.next {
+                if self.position < next.span.start {
+                    return Some(Ok(self.gap(next.span.start)));
+                }
+                self.position = next.span.end;
+                self.next = None;
+                return Some(Ok(next));
+            }
+            if self.finished {
+                return (self.position < self.on.len()).then(|| Ok(self.gap(self.on.len())));
+            }
+            match self.reader.next_step() {
+                Ok(Some(step)) => self.next = self.token(step),
+                Ok(None) => self.finished = true,
+                Err(err) => {
+                    self.finished = true;
+                    self.position = self.on.len();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Shared driver for the binary encoders, which write each event as it is parsed
+
+use std::{io::Write, vec::Vec};
+
+use super::{
+    unescape_string_content, Container, JSONEvent, JSONParseError, JSONReadError, JsonReader,
+    ParseOptions, RootJSONValue,
+};
+
+/// A format which JSON events are written to
+pub(crate) trait Encoder {
+    /// Whether [`Encoder::start`] needs the number of members or items in non empty objects
+    /// and arrays, which takes an extra pass over the source
+    const NEEDS_LENGTHS: bool;
+
+    /// `length` is `Some(0)` for empty objects and arrays, which are not then ended
+    fn start(
+        out: &mut impl Write,
+        container: Container,
+        length: Option<usize>,
+    ) -> std::io::Result<()>;
+
+    fn end(out: &mut impl Write, container: Container) -> std::io::Result<()>;
+
+    /// `key` is unescaped
+    fn key(out: &mut impl Write, key: &str) -> std::io::Result<()>;
+
+    fn value(out: &mut impl Write, value: &RootJSONValue) -> std::io::Result<()>;
+}
+
+pub(crate) fn transcode<E: Encoder>(
+    source: &str,
+    out: &mut impl Write,
+    options: &ParseOptions,
+) -> Result<(), JSONReadError> {
+    let options = ParseOptions {
+        typed_numbers: true,
+        ..options.clone()
+    };
+    let mut lengths = if E::NEEDS_LENGTHS {
+        container_lengths(source, &options)?
+    } else {
+        Vec::new()
+    }
+    .into_iter();
+
+    let mut reader = JsonReader::new(source, &options);
+    // The start of an object or array is held back until the next event, so empty ones can
+    // be written as such
+    let mut pending_start = None;
+    while let Some(event) = reader.next_event()? {
+        if let Some(container) = pending_start.take() {
+            let length = lengths.next();
+            if let JSONEvent::EndObject | JSONEvent::EndArray = event {
+                E::start(out, container, Some(0))?;
+                continue;
+            }
+            E::start(out, container, length)?;
+        }
+        match event {
+            JSONEvent::StartObject => pending_start = Some(Container::Object),
+            JSONEvent::StartArray => pending_start = Some(Container::Array),
+            JSONEvent::EndObject => E::end(out, Container::Object)?,
+            JSONEvent::EndArray => E::end(out, Container::Array)?,
+            JSONEvent::Key(key) => E::key(out, &unescape_string_content(key))?,
+            JSONEvent::Value(value) => E::value(out, &value)?,
+            JSONEvent::Comment { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+/// The number of members or items in each object and array, in the order they start
+fn container_lengths(source: &str, options: &ParseOptions) -> Result<Vec<usize>, JSONParseError> {
+    let mut lengths = Vec::new();
+    let mut open = Vec::new();
+    let mut reader = JsonReader::new(source, options);
+    while let Some(event) = reader.next_event()? {
+        match event {
+            JSONEvent::StartObject | JSONEvent::StartArray | JSONEvent::Value(_) => {
+                if let Some(&parent) = open.last() {
+                    lengths[parent] += 1;
+                }
+                if !matches!(event, JSONEvent::Value(_)) {
+                    open.push(lengths.len());
+                    lengths.push(0);
+                }
+            }
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                open.pop();
+            }
+            JSONEvent::Key(_) | JSONEvent::Comment { .. } => {}
+        }
+    }
+    Ok(lengths)
+}
+
+/// The sign and magnitude of integers which do not fit in an `i64`, but might fit in the
+/// 64 bit (unsigned) integers of binary formats
+pub(crate) fn big_integer(value: &str) -> Option<(bool, u64)> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let magnitude = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16).ok()?
+    } else if digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        digits.parse().ok()?
+    } else {
+        return None;
+    };
+    // `-0` is written as the integer `0`
+    Some((negative && magnitude != 0, magnitude))
+}
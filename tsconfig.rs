@@ -0,0 +1,278 @@
+//! Reading `tsconfig.json` files and following their `extends`
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use super::{value::parse_to_value_with_options, JSONParseError, ParseOptions, Value};
+
+/// The parts of a `tsconfig.json` file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsConfig {
+    /// `extends`, which can be one path or a list
+    pub extends: Vec<String>,
+    /// Members of `compilerOptions`, including `paths`
+    pub compiler_options: Vec<(String, Value)>,
+    /// `compilerOptions.paths`, the patterns and the paths they map to
+    pub paths: Vec<(String, Vec<String>)>,
+    pub files: Option<Vec<String>>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TsConfigError {
+    Parse(JSONParseError),
+    /// The root or this member does not have the expected type
+    InvalidValue {
+        key: &'static str,
+    },
+    /// The loader did not find this file
+    NotFound {
+        path: String,
+    },
+    /// This file extends itself, through the `extends` chain
+    Cycle {
+        path: String,
+    },
+}
+
+impl core::error::Error for TsConfigError {}
+
+impl core::fmt::Display for TsConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            TsConfigError::Parse(err) => core::fmt::Display::fmt(err, f),
+            TsConfigError::InvalidValue { key } => {
+                f.write_fmt(format_args!("TsConfigError: invalid value for {key}"))
+            }
+            TsConfigError::NotFound { path } => {
+                f.write_fmt(format_args!("TsConfigError: could not load {path}"))
+            }
+            TsConfigError::Cycle { path } => {
+                f.write_fmt(format_args!("TsConfigError: {path} extends itself"))
+            }
+        }
+    }
+}
+
+impl From<JSONParseError> for TsConfigError {
+    fn from(err: JSONParseError) -> Self {
+        TsConfigError::Parse(err)
+    }
+}
+
+impl TsConfig {
+    /// Reads one file, without following `extends`. Unknown members are skipped
+    ///
+    /// ```
+    /// use simple_json_parser::TsConfig;
+    ///
+    /// let source = r#"{
+    ///     "extends": "./base.json",
+    ///     "compilerOptions": {
+    ///         "strict": true,
+    ///         "paths": { "@/*": ["./src/*"] }, // aliases
+    ///     },
+    ///     "include": ["src"],
+    /// }"#;
+    /// let config = TsConfig::parse(source).unwrap();
+    /// assert_eq!(config.extends, ["./base.json"]);
+    /// assert_eq!(config.paths, [("@/*".to_owned(), vec!["./src/*".to_owned()])]);
+    /// assert_eq!(config.include, Some(vec!["src".to_owned()]));
+    /// assert_eq!(config.exclude, None);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if `source` is not valid JSONC or a known member has the wrong type
+    pub fn parse(source: &str) -> Result<Self, TsConfigError> {
+        let Value::Object(members) = parse_to_value_with_options(source, &ParseOptions::JSONC)?
+        else {
+            return Err(TsConfigError::InvalidValue { key: "tsconfig" });
+        };
+        let mut config = TsConfig::default();
+        for (key, value) in members {
+            match key.as_str() {
+                "extends" => {
+                    config.extends = match value {
+                        Value::String(path) => alloc::vec![path],
+                        value => strings(value, "extends")?,
+                    };
+                }
+                "compilerOptions" => {
+                    let Value::Object(options) = value else {
+                        return Err(TsConfigError::InvalidValue {
+                            key: "compilerOptions",
+                        });
+                    };
+                    config.paths = match options.iter().rev().find(|(key, _)| key == "paths") {
+                        Some((_, paths)) => paths_of(paths)?,
+                        None => Vec::new(),
+                    };
+                    config.compiler_options = options;
+                }
+                "files" => config.files = Some(strings(value, "files")?),
+                "include" => config.include = Some(strings(value, "include")?),
+                "exclude" => config.exclude = Some(strings(value, "exclude")?),
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    /// Reads the file at `path` and the files it extends, using `load` to get the source of
+    /// each file. Relative `extends` paths are resolved against the directory of the file
+    /// they are in, other paths (such as packages) are passed to `load` as is. If a path
+    /// without a `.json` extension is not found, it is tried again with one.
+    ///
+    /// Files later in `extends` (and the file itself) override earlier ones. Compiler
+    /// options are merged by key, while `paths`, `files`, `include` and `exclude` replace
+    /// those of the file being extended. Relative paths in the options are not rebased.
+    /// `extends` is that of the file at `path`
+    ///
+    /// ```
+    /// use simple_json_parser::{TsConfig, Value};
+    ///
+    /// let load = |path: &str| match path {
+    ///     "project/tsconfig.json" => Some(r#"{ "extends": "../base", "compilerOptions": { "strict": true } }"#.to_owned()),
+    ///     "base.json" => Some(r#"{ "compilerOptions": { "strict": false, "target": "es2022" }, "include": ["src"] }"#.to_owned()),
+    ///     _ => None,
+    /// };
+    /// let config = TsConfig::resolve("project/tsconfig.json", load).unwrap();
+    /// assert_eq!(
+    ///     config.compiler_options,
+    ///     [
+    ///         ("strict".to_owned(), Value::Boolean(true)),
+    ///         ("target".to_owned(), Value::String("es2022".to_owned())),
+    ///     ]
+    /// );
+    /// assert_eq!(config.include, Some(vec!["src".to_owned()]));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be loaded or parsed, or the `extends` chain has a
+    /// cycle
+    pub fn resolve(
+        path: &str,
+        mut load: impl FnMut(&str) -> Option<String>,
+    ) -> Result<Self, TsConfigError> {
+        resolve(path, &mut load, &mut Vec::new())
+    }
+
+    /// Applies `other` on top of this
+    fn merge(&mut self, other: TsConfig) {
+        if other.compiler_options.iter().any(|(key, _)| key == "paths") {
+            self.paths = other.paths;
+        }
+        for (key, value) in other.compiler_options {
+            match self
+                .compiler_options
+                .iter_mut()
+                .find(|(name, _)| *name == key)
+            {
+                Some((_, existing)) => *existing = value,
+                None => self.compiler_options.push((key, value)),
+            }
+        }
+        self.extends = other.extends;
+        self.files = other.files.or(self.files.take());
+        self.include = other.include.or(self.include.take());
+        self.exclude = other.exclude.or(self.exclude.take());
+    }
+}
+
+fn resolve(
+    path: &str,
+    load: &mut impl FnMut(&str) -> Option<String>,
+    chain: &mut Vec<String>,
+) -> Result<TsConfig, TsConfigError> {
+    let (path, source) = match load(path) {
+        Some(source) => (path.to_owned(), source),
+        None if !path
+            .rsplit_once('.')
+            .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case("json")) =>
+        {
+            let with_extension = format!("{path}.json");
+            match load(&with_extension) {
+                Some(source) => (with_extension, source),
+                None => {
+                    return Err(TsConfigError::NotFound {
+                        path: path.to_owned(),
+                    })
+                }
+            }
+        }
+        None => {
+            return Err(TsConfigError::NotFound {
+                path: path.to_owned(),
+            })
+        }
+    };
+    if chain.contains(&path) {
+        return Err(TsConfigError::Cycle { path });
+    }
+
+    let config = TsConfig::parse(&source)?;
+    chain.push(path.clone());
+    let mut resolved = TsConfig::default();
+    for extends in &config.extends {
+        let extends = if extends.starts_with("./") || extends.starts_with("../") {
+            join(directory(&path), extends)
+        } else {
+            extends.clone()
+        };
+        resolved.merge(resolve(&extends, load, chain)?);
+    }
+    chain.pop();
+    resolved.merge(config);
+    Ok(resolved)
+}
+
+/// The directory part of `path`, empty for files in the current directory
+fn directory(path: &str) -> &str {
+    path.rfind('/').map_or("", |idx| &path[..idx])
+}
+
+/// Joins `relative` onto `directory`, removing `.` and `..` segments where possible
+fn join(directory: &str, relative: &str) -> String {
+    let mut segments: Vec<&str> = directory
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    for segment in relative.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let joined = segments.join("/");
+    if directory.starts_with('/') {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+fn strings(value: Value, key: &'static str) -> Result<Vec<String>, TsConfigError> {
+    let Value::Array(items) = value else {
+        return Err(TsConfigError::InvalidValue { key });
+    };
+    items
+        .into_iter()
+        .map(|item| match item {
+            Value::String(item) => Ok(item),
+            _ => Err(TsConfigError::InvalidValue { key }),
+        })
+        .collect()
+}
+
+fn paths_of(paths: &Value) -> Result<Vec<(String, Vec<String>)>, TsConfigError> {
+    let Value::Object(members) = paths else {
+        return Err(TsConfigError::InvalidValue { key: "paths" });
+    };
+    members
+        .iter()
+        .map(|(pattern, targets)| Ok((pattern.clone(), strings(targets.clone(), "paths")?)))
+        .collect()
+}
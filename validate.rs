@@ -0,0 +1,50 @@
+//! Checking input is valid without giving anything back
+
+use super::{JSONParseError, Machine, ParseOptions, ProgressTracker, Step};
+
+/// Checks that `source` is valid, as fast as possible. Keys are not collected and nothing is
+/// emitted, so [`ParseOptions::exit_on_first_value`] is not used and
+/// [`ParseOptions::progress`] is called with an empty key chain. Input without a value
+/// (empty or only whitespace and comments) is valid unless
+/// [`ParseOptions::require_end_of_input`] is set
+///
+/// ```
+/// use simple_json_parser::{is_valid, ParseOptions};
+///
+/// let options = ParseOptions::default();
+/// assert!(is_valid(r#"{ "a": [1, 2, { "b": null }] } // comment"#, &options).is_ok());
+/// assert!(is_valid(r#"{ "a": [1, 2 }"#, &options).is_err());
+/// ```
+///
+/// # Errors
+/// Returns the first error in `source`
+pub fn is_valid(source: &str, options: &ParseOptions) -> Result<(), JSONParseError> {
+    let bytes = source.as_bytes();
+    let mut machine = Machine::new(options);
+    let mut progress = ProgressTracker::new(options);
+    let mut idx = 0;
+    loop {
+        idx += machine.skippable(idx, &bytes[idx..]);
+        if progress.due(idx) {
+            progress.report(idx, &[])?;
+        }
+        let Some(&byte) = bytes.get(idx) else {
+            while machine.finish(source.len())?.is_some() {}
+            return Ok(());
+        };
+        let (chr, length) = if byte.is_ascii() {
+            (char::from(byte), 1)
+        } else if machine.in_text() {
+            // Only ASCII changes the state in strings and comments
+            (char::REPLACEMENT_CHARACTER, 1)
+        } else {
+            let chr = source[idx..].chars().next().unwrap_or_default();
+            (chr, chr.len_utf8())
+        };
+        let step = machine.push(idx, chr)?;
+        // Otherwise the character is pushed again
+        if !matches!(step, Step::Value { retry: true, .. }) {
+            idx += length;
+        }
+    }
+}
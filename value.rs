@@ -0,0 +1,231 @@
+//! Building an owned tree from the source
+
+use alloc::{borrow::ToOwned, format, string::String, string::ToString, vec::Vec};
+
+use super::{
+    non_finite_literal, parse_events, unescape_string_content, JSONEvent, JSONParseError,
+    JSONParseErrorReason, ParseOptions, RootJSONValue,
+};
+
+/// Owned representation of a whole document. Keys and strings are unescaped, numbers are
+/// left as their source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Members in the order they appear in the source
+    Object(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    Number(String),
+    Boolean(bool),
+    Null,
+}
+
+impl Value {
+    /// Member of an object. If the key appears multiple times the last one is returned
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        if let Value::Object(members) = self {
+            members
+                .iter()
+                .rev()
+                .find_map(|(name, value)| (name == key).then_some(value))
+        } else {
+            None
+        }
+    }
+
+    /// Item of an array
+    #[must_use]
+    pub fn get_index(&self, idx: usize) -> Option<&Value> {
+        if let Value::Array(items) = self {
+            items.get(idx)
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        if let Value::String(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<RootJSONValue<'_>> for Value {
+    fn from(value: RootJSONValue<'_>) -> Self {
+        match value {
+            RootJSONValue::String(value) => Value::String(unescape_string_content(value).into()),
+            RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                Value::Number(value.to_owned())
+            }
+            RootJSONValue::Integer(value) => Value::Number(value.to_string()),
+            RootJSONValue::Float(value) | RootJSONValue::NonFinite(value) if !value.is_finite() => {
+                Value::Number(non_finite_literal(value).to_owned())
+            }
+            RootJSONValue::NonFinite(value) | RootJSONValue::Float(value) => {
+                Value::Number(format!("{value:?}"))
+            }
+            RootJSONValue::Boolean(value) => Value::Boolean(value),
+            RootJSONValue::Null => Value::Null,
+            RootJSONValue::EmptyObject => Value::Object(Vec::new()),
+            RootJSONValue::EmptyArray => Value::Array(Vec::new()),
+        }
+    }
+}
+
+/// Parses the whole source into a [`Value`] (using [`ParseOptions::default`], so comments
+/// are allowed)
+///
+/// ```
+/// use simple_json_parser::{parse_to_value, Value};
+///
+/// let value = parse_to_value(r#"{ "name": "ezno", "keywords": ["compiler"] }"#).unwrap();
+/// assert_eq!(value.get("name").and_then(Value::as_str), Some("ezno"));
+/// ```
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input or the input is empty
+pub fn parse_to_value(on: &str) -> Result<Value, JSONParseError> {
+    parse_to_value_with_options(on, &ParseOptions::default())
+}
+
+pub(crate) fn parse_to_value_with_options(
+    on: &str,
+    options: &ParseOptions,
+) -> Result<Value, JSONParseError> {
+    // Containers currently open, with their key in the parent
+    let mut stack: Vec<(Option<String>, Value)> = Vec::new();
+    let mut key = None;
+    let mut root = None;
+
+    let mut add = |stack: &mut Vec<(Option<String>, Value)>, key: Option<String>, value| match stack
+        .last_mut()
+    {
+        Some((_, Value::Object(members))) => members.push((key.unwrap_or_default(), value)),
+        Some((_, Value::Array(items))) => items.push(value),
+        _ => root = Some(value),
+    };
+
+    parse_events(
+        on,
+        |_keys, event| match event {
+            JSONEvent::StartObject => stack.push((key.take(), Value::Object(Vec::new()))),
+            JSONEvent::StartArray => stack.push((key.take(), Value::Array(Vec::new()))),
+            JSONEvent::EndObject | JSONEvent::EndArray => {
+                if let Some((key, value)) = stack.pop() {
+                    add(&mut stack, key, value);
+                }
+            }
+            JSONEvent::Key(name) => key = Some(unescape_string_content(name).into_owned()),
+            JSONEvent::Value(value) => add(&mut stack, key.take(), value.into()),
+            JSONEvent::Comment { .. } => {}
+        },
+        options,
+    )?;
+
+    root.ok_or(JSONParseError {
+        at: on.len(),
+        reason: JSONParseErrorReason::ExpectedValue { found: None },
+        path: None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EqualityOptions {
+    /// Compare numbers by their value, so `1`, `1.0` and `10e-1` are equal. Otherwise numbers
+    /// are compared as they are written
+    pub normalize_numbers: bool,
+}
+
+/// Whether two documents are the same, ignoring whitespace, comments, the order of keys and
+/// how strings are escaped. If a key appears multiple times the last one is used (as in
+/// [`Value::get`])
+///
+/// ```
+/// use simple_json_parser::{json_equals, EqualityOptions};
+///
+/// let options = EqualityOptions { normalize_numbers: true };
+/// assert!(json_equals(r#"{"a": 1, "b": [2]}"#, r#"{ "b": [2.0], "a": 1 }"#, options).unwrap());
+/// assert!(!json_equals(r#"{"a": 1}"#, r#"{"a": 1, "b": 2}"#, options).unwrap());
+/// ```
+///
+/// # Errors
+/// Returns an error if either input is not valid JSON
+pub fn json_equals(a: &str, b: &str, options: EqualityOptions) -> Result<bool, JSONParseError> {
+    Ok(values_equal(
+        &parse_to_value(a)?,
+        &parse_to_value(b)?,
+        options,
+    ))
+}
+
+pub(crate) fn values_equal(a: &Value, b: &Value, options: EqualityOptions) -> bool {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let (a, b) = (last_members(a), last_members(b));
+            a.len() == b.len()
+                && a.iter()
+                    .zip(&b)
+                    .all(|((a_key, a), (b_key, b))| a_key == b_key && values_equal(a, b, options))
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b, options))
+        }
+        (Value::Number(a), Value::Number(b)) if options.normalize_numbers => {
+            match (normalize_number(a), normalize_number(b)) {
+                (Some(a), Some(b)) => a == b,
+                _ => a == b,
+            }
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// Members sorted by key, with only the last member for each key
+fn last_members(members: &[(String, Value)]) -> Vec<(&str, &Value)> {
+    // Reversed so the stable sort puts the last member for each key first
+    let mut members: Vec<(&str, &Value)> = members
+        .iter()
+        .rev()
+        .map(|(key, value)| (key.as_str(), value))
+        .collect();
+    members.sort_by_key(|(key, _)| *key);
+    members.dedup_by_key(|(key, _)| *key);
+    members
+}
+
+/// Sign, significant digits and exponent, so that exact values can be compared without
+/// converting to floats. Zero is `(false, "", 0)`
+fn normalize_number(value: &str) -> Option<(bool, String, i64)> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+        None => (value, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if !integer
+        .chars()
+        .chain(fraction.chars())
+        .all(|chr| chr.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let digits = format!("{integer}{fraction}");
+    let digits = digits.trim_start_matches('0');
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    // `None` on overflow, so the text is compared instead
+    let exponent = exponent
+        .checked_sub(i64::try_from(fraction.len()).ok()?)?
+        .checked_add(i64::try_from(digits.len() - trimmed.len()).ok()?)?;
+    Some((negative, trimmed.to_owned(), exponent))
+}
@@ -0,0 +1,280 @@
+//! Writing JSON with a push based API, the opposite of [`crate::parse_events`]
+
+use alloc::vec::Vec;
+
+use core::fmt::Write;
+
+use super::{unescape_string_content, Container, RootJSONValue};
+
+/// Writes compact JSON, adding commas and colons and escaping strings.
+///
+/// ```
+/// use simple_json_parser::JSONWriter;
+///
+/// let mut writer = JSONWriter::new(String::new());
+/// writer.begin_object().unwrap();
+/// writer.key("name").unwrap();
+/// writer.string("say \"hi\"").unwrap();
+/// writer.key("list").unwrap();
+/// writer.begin_array().unwrap();
+/// writer.number(1).unwrap();
+/// writer.null().unwrap();
+/// writer.end_array().unwrap();
+/// writer.end_object().unwrap();
+/// assert_eq!(writer.into_inner(), r#"{"name":"say \"hi\"","list":[1,null]}"#);
+/// ```
+///
+/// # Panics
+/// Methods panic if called where they would make invalid JSON, for example a value in an
+/// object without a key or ending an array with [`JSONWriter::end_object`]
+pub struct JSONWriter<W> {
+    out: W,
+    /// Open objects and arrays and whether they have an item yet
+    containers: Vec<(Container, bool)>,
+    after_key: bool,
+}
+
+impl<W: Write> JSONWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            containers: Vec::new(),
+            after_key: false,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Number of open objects and arrays
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.containers.len()
+    }
+
+    fn before_item(&mut self, container: Container) -> core::fmt::Result {
+        if let Some((current, has_items)) = self.containers.last_mut() {
+            assert!(*current == container, "expected a {current:?} item");
+            if *has_items {
+                self.out.write_char(',')?;
+            }
+            *has_items = true;
+        }
+        Ok(())
+    }
+
+    fn before_value(&mut self) -> core::fmt::Result {
+        if self.after_key {
+            self.after_key = false;
+            Ok(())
+        } else {
+            self.before_item(Container::Array)
+        }
+    }
+
+    fn end(&mut self, container: Container) -> core::fmt::Result {
+        assert!(!self.after_key, "expected value after key");
+        let popped = self.containers.pop().map(|(container, _)| container);
+        assert!(popped == Some(container), "not in a {container:?}");
+        self.out.write_char(match container {
+            Container::Object => '}',
+            Container::Array => ']',
+        })
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn begin_object(&mut self) -> core::fmt::Result {
+        self.before_value()?;
+        self.containers.push((Container::Object, false));
+        self.out.write_char('{')
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn end_object(&mut self) -> core::fmt::Result {
+        self.end(Container::Object)
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn begin_array(&mut self) -> core::fmt::Result {
+        self.before_value()?;
+        self.containers.push((Container::Array, false));
+        self.out.write_char('[')
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn end_array(&mut self) -> core::fmt::Result {
+        self.end(Container::Array)
+    }
+
+    /// `key` is escaped
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    ///
+    /// # Panics
+    /// If not in an object or directly after another key
+    pub fn key(&mut self, key: &str) -> core::fmt::Result {
+        assert!(!self.after_key, "expected value after key");
+        self.before_item(Container::Object)?;
+        write_string(&mut self.out, key)?;
+        self.out.write_char(':')?;
+        self.after_key = true;
+        Ok(())
+    }
+
+    /// `value` is escaped
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn string(&mut self, value: &str) -> core::fmt::Result {
+        self.before_value()?;
+        write_string(&mut self.out, value)
+    }
+
+    /// Written as is, so should be an integer or finite float
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn number(&mut self, value: impl core::fmt::Display) -> core::fmt::Result {
+        self.before_value()?;
+        write!(self.out, "{value}")
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn boolean(&mut self, value: bool) -> core::fmt::Result {
+        self.before_value()?;
+        self.out.write_str(if value { "true" } else { "false" })
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn null(&mut self) -> core::fmt::Result {
+        self.before_value()?;
+        self.out.write_str("null")
+    }
+
+    /// A value from the parser. Strings which are already valid JSON string content are
+    /// written as is, others (such as JSON5 single quoted strings with `"`, `\'` or `\x41`)
+    /// are unescaped and escaped again. Numbers are written as in the source
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn value(&mut self, value: &RootJSONValue<'_>) -> core::fmt::Result {
+        self.before_value()?;
+        match value {
+            RootJSONValue::String(value) if is_json_string_content(value) => {
+                write!(self.out, "\"{value}\"")
+            }
+            RootJSONValue::String(value) => {
+                write_string(&mut self.out, &unescape_string_content(value))
+            }
+            RootJSONValue::Number(value) | RootJSONValue::BigNumber(value) => {
+                self.out.write_str(value)
+            }
+            RootJSONValue::Integer(value) => write!(self.out, "{value}"),
+            // Infinity and NaN cannot be written as JSON
+            RootJSONValue::Float(value) | RootJSONValue::NonFinite(value) if !value.is_finite() => {
+                self.out.write_str("null")
+            }
+            RootJSONValue::NonFinite(value) | RootJSONValue::Float(value) => {
+                write!(self.out, "{value:?}")
+            }
+            RootJSONValue::Boolean(value) => {
+                self.out.write_str(if *value { "true" } else { "false" })
+            }
+            RootJSONValue::Null => self.out.write_str("null"),
+            RootJSONValue::EmptyObject => self.out.write_str("{}"),
+            RootJSONValue::EmptyArray => self.out.write_str("[]"),
+        }
+    }
+}
+
+/// Whether `value` can go between double quotes: it has no unescaped `"` or control
+/// characters, and only has the escapes JSON allows
+fn is_json_string_content(value: &str) -> bool {
+    let mut bytes = value.bytes();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'"' | ..b' ' => return false,
+            b'\\' => match bytes.next() {
+                Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {}
+                Some(b'u') => {
+                    let digits = bytes.by_ref().take(4).filter(u8::is_ascii_hexdigit);
+                    if digits.count() != 4 {
+                        return false;
+                    }
+                }
+                _ => return false,
+            },
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Writes `value` quoted, escaping quotes, backslashes and control characters
+pub(crate) fn write_string(out: &mut impl Write, value: &str) -> core::fmt::Result {
+    out.write_char('"')?;
+    let mut last = 0;
+    for (idx, chr) in value.char_indices() {
+        let escaped = match chr {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\u{8}' => "\\b",
+            '\u{c}' => "\\f",
+            chr if chr < ' ' => "",
+            _ => continue,
+        };
+        out.write_str(&value[last..idx])?;
+        if escaped.is_empty() {
+            write!(out, "\\u{:04x}", u32::from(chr))?;
+        } else {
+            out.write_str(escaped)?;
+        }
+        last = idx + chr.len_utf8();
+    }
+    out.write_str(&value[last..])?;
+    out.write_char('"')
+}
+
+/// For using [`JSONWriter`] with a [`std::io::Write`] output. IO errors become
+/// [`core::fmt::Error`], the last one is available through [`IoWriter::take_error`]
+#[cfg(feature = "std")]
+pub struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
+}